@@ -21,7 +21,16 @@ pub enum FieldType {
     TypeWithParameters(String, Vec<String>),
 }
 
+/// Splits a trailing `:index` modifier off a field type, so `string!:index`
+/// gives `("string!", true)`.
+pub fn split_index_modifier(ftype: &str) -> (&str, bool) {
+    ftype
+        .strip_suffix(":index")
+        .map_or((ftype, false), |ftype| (ftype, true))
+}
+
 pub fn parse_field_type(ftype: &str) -> Result<FieldType> {
+    let (ftype, _) = split_index_modifier(ftype);
     let parts: Vec<&str> = ftype.split(':').collect();
 
     match parts.as_slice() {
@@ -6,6 +6,7 @@ pub use rrgen::{GenResult, RRgen};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 mod controller;
+mod plan;
 use colored::Colorize;
 use std::fmt::Write;
 use std::{
@@ -23,6 +24,7 @@ mod migration;
 mod model;
 #[cfg(feature = "with-db")]
 mod scaffold;
+pub use plan::{FileAction, PlannedFile};
 pub mod template;
 pub mod tera_ext;
 #[cfg(test)]
@@ -322,6 +324,28 @@ pub fn new_generator() -> RRgen {
 ///
 /// This function will return an error if it fails
 pub fn generate(rrgen: &RRgen, component: Component, appinfo: &AppInfo) -> Result<GenerateResults> {
+    render_component(rrgen, component, appinfo, false)
+}
+
+/// Lists the files generating a component in the project at `root` would
+/// create or change, without touching the project. Files produced by running
+/// migrations and refreshing entities after a model is generated are not
+/// included.
+///
+/// # Errors
+///
+/// This function will return an error if the project cannot be copied or a
+/// template cannot be rendered
+pub fn plan(root: &Path, component: Component, appinfo: &AppInfo) -> Result<Vec<PlannedFile>> {
+    plan::plan(root, component, appinfo)
+}
+
+fn render_component(
+    rrgen: &RRgen,
+    component: Component,
+    appinfo: &AppInfo,
+    #[cfg_attr(not(feature = "with-db"), allow(unused_variables))] dry_run: bool,
+) -> Result<GenerateResults> {
     /*
     (1)
     XXX: remove hooks generic from child generator, materialize it here and pass it
@@ -335,14 +359,14 @@ pub fn generate(rrgen: &RRgen, component: Component, appinfo: &AppInfo) -> Resul
             name,
             with_tz,
             fields,
-        } => model::generate(rrgen, &name, with_tz, &fields, appinfo)?,
+        } => model::generate(rrgen, &name, with_tz, &fields, appinfo, dry_run)?,
         #[cfg(feature = "with-db")]
         Component::Scaffold {
             name,
             with_tz,
             fields,
             kind,
-        } => scaffold::generate(rrgen, &name, with_tz, &fields, &kind, appinfo)?,
+        } => scaffold::generate(rrgen, &name, with_tz, &fields, &kind, appinfo, dry_run)?,
         #[cfg(feature = "with-db")]
         Component::Migration {
             name,
@@ -5,7 +5,9 @@ use rrgen::RRgen;
 use serde_json::json;
 
 use crate::{
-    infer, model::get_columns_and_references, render_template, AppInfo, GenerateResults, Result,
+    infer,
+    model::{get_columns_and_references, get_indexes},
+    render_template, AppInfo, GenerateResults, Result,
 };
 
 /// skipping some fields from the generated models.
@@ -28,12 +30,14 @@ pub fn generate(
         // NOTE: re-uses the 'new model' migration template!
         infer::MigrationType::CreateTable { table } => {
             let (columns, references) = get_columns_and_references(fields)?;
-            let vars = json!({"name": table, "ts": ts, "with_tz": with_tz,"pkg_name": pkg_name, "is_link": false, "columns": columns, "references": references});
+            let indexes = get_indexes(fields)?;
+            let vars = json!({"name": table, "ts": ts, "with_tz": with_tz,"pkg_name": pkg_name, "is_link": false, "columns": columns, "references": references, "indexes": indexes});
             render_template(rrgen, Path::new("model/model.t"), &vars)
         }
         infer::MigrationType::AddColumns { table } => {
             let (columns, references) = get_columns_and_references(fields)?;
-            let vars = json!({"name": name, "table": table, "ts": ts, "pkg_name": pkg_name, "is_link": false, "columns": columns, "references": references});
+            let indexes = get_indexes(fields)?;
+            let vars = json!({"name": name, "table": table, "ts": ts, "pkg_name": pkg_name, "is_link": false, "columns": columns, "references": references, "indexes": indexes});
             render_template(rrgen, Path::new("migration/add_columns.t"), &vars)
         }
         infer::MigrationType::RemoveColumns { table } => {
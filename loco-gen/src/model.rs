@@ -7,7 +7,9 @@ use rrgen::RRgen;
 use serde_json::json;
 
 use crate::{
-    get_mappings, infer::parse_field_type, render_template, AppInfo, Error, GenerateResults, Result,
+    get_mappings,
+    infer::{parse_field_type, split_index_modifier},
+    render_template, AppInfo, Error, GenerateResults, Result,
 };
 
 /// skipping some fields from the generated models.
@@ -96,22 +98,43 @@ pub fn get_columns_and_references(
     Ok((columns, references))
 }
 
+/// names of the columns marked with the `:index` modifier, e.g.
+/// `title:string:index`
+pub fn get_indexes(fields: &[(String, String)]) -> Result<Vec<String>> {
+    let mut indexes = Vec::new();
+    for (fname, ftype) in fields {
+        let (ftype, indexed) = split_index_modifier(ftype);
+        if !indexed || IGNORE_FIELDS.contains(&fname.as_str()) {
+            continue;
+        }
+        if ftype.starts_with("references") {
+            return Err(Error::Message(format!(
+                "field `{fname}`: `:index` is not supported on references"
+            )));
+        }
+        indexes.push(fname.to_string());
+    }
+    Ok(indexes)
+}
+
 pub fn generate(
     rrgen: &RRgen,
     name: &str,
     with_tz: bool,
     fields: &[(String, String)],
     appinfo: &AppInfo,
+    dry_run: bool,
 ) -> Result<GenerateResults> {
     let pkg_name: &str = &appinfo.app_name;
     let ts = Utc::now();
 
     let (columns, references) = get_columns_and_references(fields)?;
+    let indexes = get_indexes(fields)?;
 
-    let vars = json!({"name": name, "ts": ts, "with_tz": with_tz,"pkg_name": pkg_name, "columns": columns, "references": references});
+    let vars = json!({"name": name, "ts": ts, "with_tz": with_tz,"pkg_name": pkg_name, "columns": columns, "references": references, "indexes": indexes});
     let gen_result = render_template(rrgen, Path::new("model"), &vars)?;
 
-    if std::env::var("SKIP_MIGRATION").is_err() {
+    if !dry_run && std::env::var("SKIP_MIGRATION").is_err() {
         // generate the model files by migrating and re-running seaorm
         let cwd = current_dir()?;
         let env_map: HashMap<_, _> = std::env::vars().collect();
@@ -216,6 +239,28 @@ mod tests {
         assert_eq!(res, (expected_columns, expected_references));
     }
 
+    #[test]
+    fn test_get_indexes_from_fields() {
+        let fields = [
+            to_field("title", "string!:index"),
+            to_field("body", "text"),
+            to_field("tags", "array:string:index"),
+        ];
+        let res = get_columns_and_references(&fields).expect("Failed to parse fields");
+
+        let expected_columns = vec![
+            to_field("title", "String"),
+            to_field("body", "TextNull"),
+            to_field("tags", "array_null(ArrayColType::String)"),
+        ];
+        assert_eq!(res, (expected_columns, vec![]));
+        assert_eq!(
+            get_indexes(&fields).expect("Failed to parse indexes"),
+            vec!["title".to_string(), "tags".to_string()]
+        );
+        assert!(get_indexes(&[to_field("user", "references:index")]).is_err());
+    }
+
     #[test]
     fn validate_arity() {
         // field not expected arity, but given 2
@@ -0,0 +1,96 @@
+//! Dry runs: generates a component into a scratch copy of the project and
+//! reports how the copy ended up differing from the original.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use serde::Serialize;
+
+use crate::{render_component, tera_ext, AppInfo, Component, RRgen, Result};
+
+/// Directories left out of the scratch copy, templates never write into them.
+const SKIP_DIRS: &[&str] = &["target", ".git", "node_modules"];
+
+static SCRATCH_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// What generating a component would do to a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileAction {
+    Create,
+    /// The file exists and would be overwritten or injected into.
+    Modify,
+}
+
+/// A file a generation would create or change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedFile {
+    pub path: PathBuf,
+    pub action: FileAction,
+}
+
+pub fn plan(root: &Path, component: Component, appinfo: &AppInfo) -> Result<Vec<PlannedFile>> {
+    let scratch = std::env::temp_dir().join(format!(
+        "loco-gen-plan-{}-{}",
+        std::process::id(),
+        SCRATCH_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    let planned = copy_dir(root, &scratch).and_then(|()| {
+        let rrgen = RRgen::with_working_dir(&scratch).add_template_engine(tera_ext::new());
+        render_component(&rrgen, component, appinfo, true)?;
+
+        let mut files = Vec::new();
+        diff_dir(&scratch, root, Path::new(""), &mut files)?;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    });
+    let _ = fs::remove_dir_all(&scratch);
+    planned
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if !SKIP_DIRS.iter().any(|dir| entry.file_name() == *dir) {
+                copy_dir(&entry.path(), &to.join(entry.file_name()))?;
+            }
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), to.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Collects the files under `scratch` that are missing from or differ from
+/// their counterpart under `root`, as paths relative to both.
+fn diff_dir(
+    scratch: &Path,
+    root: &Path,
+    relative: &Path,
+    files: &mut Vec<PlannedFile>,
+) -> Result<()> {
+    for entry in fs::read_dir(scratch.join(relative))? {
+        let entry = entry?;
+        let path = relative.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            diff_dir(scratch, root, &path, files)?;
+            continue;
+        }
+
+        let original = root.join(&path);
+        let action = if !original.exists() {
+            FileAction::Create
+        } else if fs::read(&original)? != fs::read(entry.path())? {
+            FileAction::Modify
+        } else {
+            continue;
+        };
+        files.push(PlannedFile { path, action });
+    }
+    Ok(())
+}
@@ -15,11 +15,12 @@ pub fn generate(
     fields: &[(String, String)],
     kind: &ScaffoldKind,
     appinfo: &AppInfo,
+    dry_run: bool,
 ) -> Result<GenerateResults> {
     // - scaffold is never a link table
     // - never run with migration_only, because the controllers will refer to the
     //   models. the models only arrive after migration and entities sync.
-    let mut gen_result = model::generate(rrgen, name, with_tz, fields, appinfo)?;
+    let mut gen_result = model::generate(rrgen, name, with_tz, fields, appinfo, dry_run)?;
 
     let mut columns = Vec::new();
     for (fname, ftype) in fields {
@@ -25,10 +25,29 @@ impl MigrationTrait for Migration {
         {% for column in columns -%}
         add_column(m, "{{plural_snake}}", "{{column.0}}", ColType::{{column.1}}).await?;
         {% endfor -%}
+        {% for index in indexes -%}
+        m.create_index(
+            Index::create()
+                .name("idx-{{plural_snake}}-{{index}}")
+                .table(Alias::new("{{plural_snake}}"))
+                .col(Alias::new("{{index}}"))
+                .to_owned(),
+        )
+        .await?;
+        {% endfor -%}
         Ok(())
     }
 
     async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        {% for index in indexes -%}
+        m.drop_index(
+            Index::drop()
+                .name("idx-{{plural_snake}}-{{index}}")
+                .table(Alias::new("{{plural_snake}}"))
+                .to_owned(),
+        )
+        .await?;
+        {% endfor -%}
         {% for column in columns -%}
         remove_column(m, "{{plural_snake}}", "{{column.0}}").await?;
         {% endfor -%}
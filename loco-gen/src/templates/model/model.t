@@ -41,7 +41,18 @@ impl MigrationTrait for Migration {
             ("{{ref.0}}", "{{ref.1}}"),
             {% endfor -%}
             ]
-        ).await
+        ).await{% if indexes | length > 0 %}?;
+        {% for index in indexes -%}
+        m.create_index(
+            Index::create()
+                .name("idx-{{plural_snake}}-{{index}}")
+                .table(Alias::new("{{plural_snake}}"))
+                .col(Alias::new("{{index}}"))
+                .to_owned(),
+        )
+        .await?;
+        {% endfor -%}
+        Ok(()){% endif %}
     }
 
     async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
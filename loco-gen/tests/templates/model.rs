@@ -76,6 +76,45 @@ fn can_generate() {
     );
 }
 
+#[test]
+fn can_generate_indexed_fields() {
+    std::env::set_var("SKIP_MIGRATION", "");
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add("migration/src/lib.rs", MIGRATION_SRC_LIB)
+        .add_empty("tests/models/mod.rs")
+        .create()
+        .unwrap();
+
+    let rrgen = RRgen::with_working_dir(&tree_fs.root);
+    let component = Component::Model {
+        name: "movies".to_string(),
+        with_tz: true,
+        fields: vec![
+            ("title".to_string(), "string!:index".to_string()),
+            ("plot".to_string(), "text".to_string()),
+        ],
+    };
+
+    generate(
+        &rrgen,
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+    )
+    .expect("Generation failed");
+
+    let migration_file =
+        guess_file_by_time(&tree_fs.root.join("migration/src"), "m{TIME}_movies.rs", 3)
+            .expect("Failed to find the generated migration file");
+    let migration = fs::read_to_string(&migration_file).expect("Failed to read the migration file");
+
+    assert!(migration.contains(r#"("title", ColType::String),"#));
+    assert!(migration.contains(r#".name("idx-movies-title")"#));
+    assert!(!migration.contains("idx-movies-plot"));
+}
+
 #[test]
 fn fail_when_migration_lib_not_exists() {
     std::env::set_var("SKIP_MIGRATION", "");
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+
+use crate::ai::{KnowledgeAssistant, KnowledgeAssistantError, KnowledgeRequest, KnowledgeResponse};
+
+/// Knowledge assistant that answers with the reasoning context itself.
+///
+/// Useful during local development, where no provider is configured but the
+/// knowledge endpoints should still respond.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EchoKnowledgeAssistant;
+
+#[async_trait]
+impl KnowledgeAssistant for EchoKnowledgeAssistant {
+    async fn respond(
+        &self,
+        request: KnowledgeRequest,
+    ) -> Result<KnowledgeResponse, KnowledgeAssistantError> {
+        Ok(KnowledgeResponse {
+            message: request.context_as_text(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ai::{build_assistant, ReasoningOutcome},
+        config::{AiSettings, KnowledgeAssistantBackend},
+        ontology::value_objects::Iri,
+    };
+
+    #[tokio::test]
+    async fn echo_backend_returns_reasoning_context() {
+        let settings = AiSettings {
+            assistant: Some(KnowledgeAssistantBackend::Echo),
+            ..AiSettings::default()
+        };
+        let assistant = build_assistant(&settings)
+            .expect("echo backend builds")
+            .expect("assistant configured");
+        let request = KnowledgeRequest {
+            prompt: "Explain".to_string(),
+            ontology: Iri::new("https://example.org/ontology").unwrap(),
+            inferences: vec![ReasoningOutcome::Ancestors {
+                class: Iri::new("https://example.org/Child").unwrap(),
+                ancestors: vec![Iri::new("https://example.org/Parent").unwrap()],
+            }],
+        };
+
+        let response = assistant.respond(request.clone()).await.unwrap();
+
+        assert_eq!(response.message, request.context_as_text());
+    }
+}
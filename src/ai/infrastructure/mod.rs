@@ -1,3 +1,5 @@
+pub mod echo;
 pub mod openai;
 
+pub use echo::EchoKnowledgeAssistant;
 pub use openai::OpenAiKnowledgeAssistant;
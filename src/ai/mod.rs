@@ -1,10 +1,14 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
 use thiserror::Error;
 
 use crate::{
-    config::{AiSettings, KnowledgeAssistantBackend},
+    config::{AiSettings, KnowledgeAssistantBackend, KnowledgeCacheSettings},
     ontology::{
         service::{OntologyServiceError, ReasonerHandle},
         value_objects::Iri,
@@ -15,7 +19,7 @@ pub mod infrastructure;
 pub mod task;
 
 /// Request issued to a [`KnowledgeAssistant`] implementation.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct KnowledgeRequest {
     /// Prompt provided by the caller.
     pub prompt: String,
@@ -55,14 +59,29 @@ pub struct KnowledgeResponse {
 /// response.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct KnowledgeSynthesis {
+    /// Correlation id (a UUID) shared by every log line about this request.
+    pub request_id: String,
     /// Assistant message.
     pub message: String,
     /// Reasoning outcomes produced while orchestrating the request.
     pub inferences: Vec<ReasoningOutcome>,
+    /// Timing and size figures collected while orchestrating the request.
+    pub stats: KnowledgeStats,
+}
+
+/// Observability figures describing a [`KnowledgeSynthesis`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KnowledgeStats {
+    /// Time spent executing the reasoning plan.
+    pub reasoning: Duration,
+    /// Time spent waiting for the assistant.
+    pub assistant: Duration,
+    /// Number of IRIs produced across every inference.
+    pub iri_count: usize,
 }
 
 /// Supported reasoning commands executed before delegating to an assistant.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ReasoningCommand {
     /// Fetch the transitive closure of parent classes.
     Ancestors { class: Iri },
@@ -72,11 +91,36 @@ pub enum ReasoningCommand {
     RelatedIndividuals { property: Iri, individual: Iri },
     /// Compute the shortest path between two individuals.
     ShortestPath { start: Iri, end: Iri },
+    /// Retrieve the literal values asserted through a data property.
+    LiteralValues { property: Iri, individual: Iri },
+}
+
+impl ReasoningCommand {
+    /// Human readable name of the command.
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Ancestors { .. } => "ancestors",
+            Self::Descendants { .. } => "descendants",
+            Self::RelatedIndividuals { .. } => "related individuals",
+            Self::ShortestPath { .. } => "shortest path",
+            Self::LiteralValues { .. } => "literal values",
+        }
+    }
+
+    /// Drops repeated commands from `plan`, keeping the first occurrence of
+    /// each in order, and returns how many were removed.
+    pub fn dedup_plan(plan: &mut Vec<Self>) -> usize {
+        let before = plan.len();
+        let mut seen = HashSet::with_capacity(before);
+        plan.retain(|command| seen.insert(command.clone()));
+        before - plan.len()
+    }
 }
 
 /// Canonical representation of reasoning outcomes attached to assistant
 /// invocations.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ReasoningOutcome {
     /// Result of [`ReasoningCommand::Ancestors`].
     Ancestors { class: Iri, ancestors: Vec<Iri> },
@@ -94,9 +138,42 @@ pub enum ReasoningOutcome {
         end: Iri,
         path: Option<Vec<Iri>>,
     },
+    /// Result of [`ReasoningCommand::LiteralValues`].
+    LiteralValues {
+        property: Iri,
+        individual: Iri,
+        values: Vec<String>,
+    },
+    /// The command was not executed because the inference it relies on is
+    /// disabled.
+    Skipped {
+        command: ReasoningCommand,
+        inference: String,
+    },
 }
 
 impl ReasoningOutcome {
+    /// Number of IRIs the outcome produced.
+    #[must_use]
+    pub fn iri_count(&self) -> usize {
+        match self {
+            Self::Ancestors { ancestors, .. } => ancestors.len(),
+            Self::Descendants { descendants, .. } => descendants.len(),
+            Self::RelatedIndividuals { related, .. } => related.len(),
+            Self::ShortestPath { path, .. } => path.as_ref().map_or(0, Vec::len),
+            Self::LiteralValues { .. } | Self::Skipped { .. } => 0,
+        }
+    }
+
+    /// Indicates whether the outcome carries no information for the assistant.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::LiteralValues { values, .. } => values.is_empty(),
+            _ => self.iri_count() == 0,
+        }
+    }
+
     /// Converts the outcome into a human readable string.
     #[must_use]
     pub fn describe(&self) -> String {
@@ -149,6 +226,27 @@ impl ReasoningOutcome {
                 }
                 None => format!("No path discovered between `{start}` and `{end}`."),
             },
+            Self::LiteralValues {
+                property,
+                individual,
+                values,
+            } => {
+                let mut text = format!(
+                    "Values of `{property}` for `{individual}` ({} items):",
+                    values.len()
+                );
+                for value in values {
+                    text.push_str("\n  - ");
+                    text.push_str(value);
+                }
+                text
+            }
+            Self::Skipped { command, inference } => {
+                format!(
+                    "{} skipped: {inference} inference disabled.",
+                    command.label()
+                )
+            }
         }
     }
 }
@@ -164,6 +262,111 @@ pub trait KnowledgeAssistant: Send + Sync {
     ) -> Result<KnowledgeResponse, KnowledgeAssistantError>;
 }
 
+/// Decorator answering repeated identical requests from memory.
+///
+/// Entries are keyed on the whole [`KnowledgeRequest`], so requests that share
+/// a prompt but carry different inferences are cached separately, and a hash
+/// collision can never answer one request with another's response.
+pub struct CachingKnowledgeAssistant {
+    inner: Arc<dyn KnowledgeAssistant>,
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<KnowledgeCache>,
+}
+
+#[derive(Default)]
+struct KnowledgeCache {
+    entries: HashMap<KnowledgeRequest, CachedResponse>,
+    clock: u64,
+}
+
+struct CachedResponse {
+    response: KnowledgeResponse,
+    stored_at: Instant,
+    last_used: u64,
+}
+
+impl CachingKnowledgeAssistant {
+    /// Wraps `inner` with a cache holding at most `max_entries` responses for
+    /// `ttl` each.
+    pub fn new(inner: Arc<dyn KnowledgeAssistant>, ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            inner,
+            ttl,
+            max_entries,
+            entries: Mutex::new(KnowledgeCache::default()),
+        }
+    }
+
+    /// Wraps `inner` using the configured cache settings.
+    pub fn from_settings(
+        inner: Arc<dyn KnowledgeAssistant>,
+        settings: &KnowledgeCacheSettings,
+    ) -> Self {
+        Self::new(inner, settings.ttl(), settings.max_entries)
+    }
+
+    fn lookup(&self, key: &KnowledgeRequest) -> Option<KnowledgeResponse> {
+        let mut cache = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        cache.clock += 1;
+        let clock = cache.clock;
+        let ttl = self.ttl;
+        match cache.entries.get_mut(key) {
+            Some(entry) if entry.stored_at.elapsed() < ttl => {
+                entry.last_used = clock;
+                Some(entry.response.clone())
+            }
+            Some(_) => {
+                cache.entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn store(&self, key: KnowledgeRequest, response: KnowledgeResponse) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let mut cache = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        cache.clock += 1;
+        let clock = cache.clock;
+        if !cache.entries.contains_key(&key) && cache.entries.len() >= self.max_entries {
+            let least_recent = cache
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(least_recent) = least_recent {
+                cache.entries.remove(&least_recent);
+            }
+        }
+        cache.entries.insert(
+            key,
+            CachedResponse {
+                response,
+                stored_at: Instant::now(),
+                last_used: clock,
+            },
+        );
+    }
+}
+
+#[async_trait]
+impl KnowledgeAssistant for CachingKnowledgeAssistant {
+    async fn respond(
+        &self,
+        request: KnowledgeRequest,
+    ) -> Result<KnowledgeResponse, KnowledgeAssistantError> {
+        if let Some(response) = self.lookup(&request) {
+            return Ok(response);
+        }
+        let response = self.inner.respond(request.clone()).await?;
+        self.store(request, response.clone());
+        Ok(response)
+    }
+}
+
 /// Factory error raised when building assistant adapters from configuration.
 #[derive(Debug, Error)]
 pub enum KnowledgeAssistantInitError {
@@ -192,10 +395,15 @@ pub enum KnowledgeAssistantError {
     Provider(String),
 }
 
+/// Message returned instead of an assistant answer when reasoning found
+/// nothing and [`KnowledgeOrchestrator::skip_assistant_when_empty`] is set.
+pub const NO_RELEVANT_INFERENCES: &str = "No relevant inferences were found for this request.";
+
 /// Orchestrates reasoning commands before delegating to an assistant.
 pub struct KnowledgeOrchestrator {
     reasoner: Arc<ReasonerHandle>,
     assistant: Arc<dyn KnowledgeAssistant>,
+    skip_assistant_when_empty: bool,
 }
 
 impl KnowledgeOrchestrator {
@@ -204,9 +412,18 @@ impl KnowledgeOrchestrator {
         Self {
             reasoner,
             assistant,
+            skip_assistant_when_empty: false,
         }
     }
 
+    /// Answers with [`NO_RELEVANT_INFERENCES`] without calling the assistant
+    /// when every reasoning outcome is empty.
+    #[must_use]
+    pub fn skip_assistant_when_empty(mut self, skip: bool) -> Self {
+        self.skip_assistant_when_empty = skip;
+        self
+    }
+
     /// Executes the supplied reasoning plan before invoking the assistant.
     pub async fn run(
         &self,
@@ -214,36 +431,34 @@ impl KnowledgeOrchestrator {
         prompt: String,
         plan: Vec<ReasoningCommand>,
     ) -> Result<KnowledgeSynthesis, KnowledgeOrchestratorError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let reasoning_started = Instant::now();
         let mut inferences = Vec::with_capacity(plan.len());
         for command in plan {
-            match command {
-                ReasoningCommand::Ancestors { class } => {
-                    let ancestors = self.reasoner.ancestors_of(&ontology, &class).await?;
-                    inferences.push(ReasoningOutcome::Ancestors { class, ancestors });
-                }
-                ReasoningCommand::Descendants { class } => {
-                    let descendants = self.reasoner.descendants_of(&ontology, &class).await?;
-                    inferences.push(ReasoningOutcome::Descendants { class, descendants });
-                }
-                ReasoningCommand::RelatedIndividuals {
-                    property,
-                    individual,
-                } => {
-                    let related = self
-                        .reasoner
-                        .related_individuals(&ontology, &property, &individual)
-                        .await?;
-                    inferences.push(ReasoningOutcome::RelatedIndividuals {
-                        property,
-                        individual,
-                        related,
-                    });
-                }
-                ReasoningCommand::ShortestPath { start, end } => {
-                    let path = self.reasoner.shortest_path(&ontology, &start, &end).await?;
-                    inferences.push(ReasoningOutcome::ShortestPath { start, end, path });
+            let outcome = match self.execute(&ontology, command.clone()).await {
+                Ok(outcome) => outcome,
+                Err(OntologyServiceError::InferenceDisabled { inference }) => {
+                    ReasoningOutcome::Skipped {
+                        command,
+                        inference: inference.to_string(),
+                    }
                 }
-            }
+                Err(err) => return Err(err.into()),
+            };
+            inferences.push(outcome);
+        }
+        let reasoning = reasoning_started.elapsed();
+
+        if self.skip_assistant_when_empty && inferences.iter().all(ReasoningOutcome::is_empty) {
+            return Ok(KnowledgeSynthesis {
+                request_id,
+                message: NO_RELEVANT_INFERENCES.to_string(),
+                inferences,
+                stats: KnowledgeStats {
+                    reasoning,
+                    ..KnowledgeStats::default()
+                },
+            });
         }
 
         let request = KnowledgeRequest {
@@ -251,10 +466,97 @@ impl KnowledgeOrchestrator {
             ontology: ontology.clone(),
             inferences: inferences.clone(),
         };
+        let assistant_started = Instant::now();
         let response = self.assistant.respond(request).await?;
+        let stats = KnowledgeStats {
+            reasoning,
+            assistant: assistant_started.elapsed(),
+            iri_count: inferences.iter().map(ReasoningOutcome::iri_count).sum(),
+        };
         Ok(KnowledgeSynthesis {
+            request_id,
             message: response.message,
             inferences,
+            stats,
+        })
+    }
+
+    /// Runs [`Self::run`] to completion on a dedicated current-thread
+    /// runtime, for callers without an async context.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KnowledgeOrchestratorError::Runtime`] when called from
+    /// within a Tokio runtime, where blocking would panic, or when the
+    /// runtime cannot be created. Otherwise fails like [`Self::run`].
+    pub fn run_blocking(
+        &self,
+        ontology: Iri,
+        prompt: String,
+        plan: Vec<ReasoningCommand>,
+    ) -> Result<KnowledgeSynthesis, KnowledgeOrchestratorError> {
+        use tokio::runtime::{Builder, Handle};
+
+        if Handle::try_current().is_ok() {
+            return Err(KnowledgeOrchestratorError::Runtime(
+                "run_blocking cannot be called from within an async runtime; use `run`".to_string(),
+            ));
+        }
+        Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| KnowledgeOrchestratorError::Runtime(err.to_string()))?
+            .block_on(self.run(ontology, prompt, plan))
+    }
+}
+
+impl KnowledgeOrchestrator {
+    async fn execute(
+        &self,
+        ontology: &Iri,
+        command: ReasoningCommand,
+    ) -> Result<ReasoningOutcome, OntologyServiceError> {
+        Ok(match command {
+            ReasoningCommand::Ancestors { class } => {
+                let ancestors = self.reasoner.ancestors_of(ontology, &class).await?;
+                ReasoningOutcome::Ancestors { class, ancestors }
+            }
+            ReasoningCommand::Descendants { class } => {
+                let descendants = self.reasoner.descendants_of(ontology, &class).await?;
+                ReasoningOutcome::Descendants { class, descendants }
+            }
+            ReasoningCommand::RelatedIndividuals {
+                property,
+                individual,
+            } => {
+                let related = self
+                    .reasoner
+                    .related_individuals(ontology, &property, &individual)
+                    .await?;
+                ReasoningOutcome::RelatedIndividuals {
+                    property,
+                    individual,
+                    related,
+                }
+            }
+            ReasoningCommand::ShortestPath { start, end } => {
+                let path = self.reasoner.shortest_path(ontology, &start, &end).await?;
+                ReasoningOutcome::ShortestPath { start, end, path }
+            }
+            ReasoningCommand::LiteralValues {
+                property,
+                individual,
+            } => {
+                let values = self
+                    .reasoner
+                    .literal_values(ontology, &property, &individual)
+                    .await?;
+                ReasoningOutcome::LiteralValues {
+                    property,
+                    individual,
+                    values,
+                }
+            }
         })
     }
 }
@@ -268,6 +570,9 @@ pub enum KnowledgeOrchestratorError {
     /// Assistant invocation failed.
     #[error(transparent)]
     Assistant(#[from] KnowledgeAssistantError),
+    /// The blocking entry point could not drive the orchestration.
+    #[error("cannot run the orchestrator: {0}")]
+    Runtime(String),
 }
 
 /// Builds a knowledge assistant adapter from configuration.
@@ -278,12 +583,17 @@ pub fn build_assistant(
         return Ok(None);
     };
 
-    match backend {
-        KnowledgeAssistantBackend::OpenAi(cfg) => {
-            let adapter = infrastructure::openai::OpenAiKnowledgeAssistant::try_new(cfg)?;
-            Ok(Some(Arc::new(adapter)))
-        }
-    }
+    let assistant: Arc<dyn KnowledgeAssistant> = match backend {
+        KnowledgeAssistantBackend::OpenAi(cfg) => Arc::new(
+            infrastructure::openai::OpenAiKnowledgeAssistant::try_new(cfg)?,
+        ),
+        KnowledgeAssistantBackend::Echo => Arc::new(infrastructure::EchoKnowledgeAssistant),
+    };
+
+    Ok(Some(match settings.cache.as_ref() {
+        Some(cache) => Arc::new(CachingKnowledgeAssistant::from_settings(assistant, cache)),
+        None => assistant,
+    }))
 }
 
 #[cfg(test)]
@@ -299,6 +609,7 @@ pub(crate) mod test_support {
         pub ancestors: Vec<Iri>,
         pub descendants: Vec<Iri>,
         pub related: Vec<Iri>,
+        pub literals: Vec<String>,
         pub path: Option<Vec<Iri>>,
     }
 
@@ -343,6 +654,19 @@ pub(crate) mod test_support {
             Ok(self.related.clone())
         }
 
+        async fn literal_values(
+            &self,
+            _ontology: &Iri,
+            property: &Iri,
+            individual: &Iri,
+        ) -> Result<Vec<String>, Self::Error> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("literals:{individual}:{property}"));
+            Ok(self.literals.clone())
+        }
+
         async fn shortest_path(
             &self,
             _ontology: &Iri,
@@ -355,11 +679,28 @@ pub(crate) mod test_support {
                 .push(format!("path:{start}:{end}"));
             Ok(self.path.clone())
         }
+
+        async fn shortest_path_labeled(
+            &self,
+            _ontology: &Iri,
+            start: &Iri,
+            end: &Iri,
+        ) -> Result<Option<crate::ontology::repositories::LabeledPath>, Self::Error> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("labeled-path:{start}:{end}"));
+            Ok(self
+                .path
+                .clone()
+                .map(|path| path.into_iter().map(|iri| (None, iri)).collect()))
+        }
     }
 
     /// Mock assistant capturing the last request.
     pub struct MockAssistant {
         pub last_request: Mutex<Option<KnowledgeRequest>>,
+        pub calls: Mutex<usize>,
         pub response: KnowledgeResponse,
     }
 
@@ -367,6 +708,7 @@ pub(crate) mod test_support {
         fn default() -> Self {
             Self {
                 last_request: Mutex::new(None),
+                calls: Mutex::new(0),
                 response: KnowledgeResponse {
                     message: String::new(),
                 },
@@ -381,10 +723,178 @@ pub(crate) mod test_support {
             request: KnowledgeRequest,
         ) -> Result<KnowledgeResponse, KnowledgeAssistantError> {
             *self.last_request.lock().unwrap() = Some(request);
+            *self.calls.lock().unwrap() += 1;
             Ok(self.response.clone())
         }
     }
 
+    fn cached_request(ancestors: Vec<Iri>) -> KnowledgeRequest {
+        KnowledgeRequest {
+            prompt: "Explain".to_string(),
+            ontology: Iri::new("https://example.org/ontology").unwrap(),
+            inferences: vec![ReasoningOutcome::Ancestors {
+                class: Iri::new("https://example.org/Class").unwrap(),
+                ancestors,
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn caching_assistant_reuses_identical_responses() {
+        let inner = Arc::new(MockAssistant {
+            response: KnowledgeResponse {
+                message: "cached".to_string(),
+            },
+            ..MockAssistant::default()
+        });
+        let assistant = CachingKnowledgeAssistant::new(inner.clone(), Duration::from_secs(60), 8);
+
+        let first = assistant.respond(cached_request(vec![])).await.unwrap();
+        let second = assistant.respond(cached_request(vec![])).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(*inner.calls.lock().unwrap(), 1);
+
+        assistant
+            .respond(cached_request(vec![
+                Iri::new("https://example.org/Parent").unwrap()
+            ]))
+            .await
+            .unwrap();
+        assert_eq!(*inner.calls.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn caching_assistant_evicts_least_recently_used() {
+        let inner = Arc::new(MockAssistant::default());
+        let assistant = CachingKnowledgeAssistant::new(inner.clone(), Duration::from_secs(60), 1);
+        let parent = Iri::new("https://example.org/Parent").unwrap();
+
+        assistant.respond(cached_request(vec![])).await.unwrap();
+        assistant
+            .respond(cached_request(vec![parent]))
+            .await
+            .unwrap();
+        assistant.respond(cached_request(vec![])).await.unwrap();
+
+        assert_eq!(*inner.calls.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn orchestrator_skips_assistant_when_reasoning_is_empty() {
+        let reasoner = Arc::new(MockReasoner::default());
+        let assistant = Arc::new(MockAssistant::default());
+        let orchestrator =
+            KnowledgeOrchestrator::new(reasoner, assistant.clone()).skip_assistant_when_empty(true);
+
+        let class = Iri::new("https://example.org/Leaf").unwrap();
+        let synthesis = orchestrator
+            .run(
+                Iri::new("https://example.org/ontology").unwrap(),
+                "Explain".to_string(),
+                vec![
+                    ReasoningCommand::Ancestors {
+                        class: class.clone(),
+                    },
+                    ReasoningCommand::Descendants { class },
+                ],
+            )
+            .await
+            .expect("orchestrator to succeed");
+
+        assert_eq!(synthesis.message, NO_RELEVANT_INFERENCES);
+        assert_eq!(synthesis.inferences.len(), 2);
+        assert_eq!(*assistant.calls.lock().unwrap(), 0);
+        assert!(assistant.last_request.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn orchestrator_runs_blocking_outside_a_runtime() {
+        let reasoner = Arc::new(MockReasoner {
+            ancestors: vec![Iri::new("https://example.org/Base").unwrap()],
+            ..MockReasoner::default()
+        });
+        let assistant = Arc::new(MockAssistant::default());
+        let orchestrator = KnowledgeOrchestrator::new(reasoner, assistant.clone());
+
+        let synthesis = orchestrator
+            .run_blocking(
+                Iri::new("https://example.org/ontology").unwrap(),
+                "Explain".to_string(),
+                vec![ReasoningCommand::Ancestors {
+                    class: Iri::new("https://example.org/Leaf").unwrap(),
+                }],
+            )
+            .expect("orchestrator to succeed");
+
+        assert_eq!(synthesis.inferences.len(), 1);
+        assert_eq!(*assistant.calls.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn orchestrator_refuses_to_block_inside_a_runtime() {
+        let orchestrator = KnowledgeOrchestrator::new(
+            Arc::new(MockReasoner::default()),
+            Arc::new(MockAssistant::default()),
+        );
+
+        let result = orchestrator.run_blocking(
+            Iri::new("https://example.org/ontology").unwrap(),
+            "Explain".to_string(),
+            Vec::new(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(KnowledgeOrchestratorError::Runtime(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn orchestrator_reports_disabled_inference_as_skipped() {
+        let mut settings = crate::config::ReasonerSettings::default();
+        settings.inference.class_hierarchy = false;
+        let service = crate::ontology::OntologyService::from_config(
+            &crate::config::OntologySettings::default(),
+            &settings,
+        )
+        .unwrap();
+
+        let ontology = Iri::new("https://example.org/ontology").unwrap();
+        let base = Iri::new("https://example.org/Base").unwrap();
+        let mut aggregate = crate::ontology::Ontology::new(ontology.clone());
+        aggregate
+            .add_class(crate::ontology::Class::new(base.clone()))
+            .unwrap();
+        service.repository().insert(aggregate).await.unwrap();
+
+        let assistant = Arc::new(MockAssistant::default());
+        let orchestrator = KnowledgeOrchestrator::new(service.reasoner(), assistant.clone());
+        let synthesis = orchestrator
+            .run(
+                ontology,
+                "Explain".to_string(),
+                vec![ReasoningCommand::Ancestors {
+                    class: base.clone(),
+                }],
+            )
+            .await
+            .expect("disabled inference is not an error");
+
+        assert_eq!(
+            synthesis.inferences,
+            vec![ReasoningOutcome::Skipped {
+                command: ReasoningCommand::Ancestors { class: base },
+                inference: "class_hierarchy".to_string(),
+            }]
+        );
+        assert_eq!(
+            synthesis.inferences[0].describe(),
+            "ancestors skipped: class_hierarchy inference disabled."
+        );
+        assert!(assistant.last_request.lock().unwrap().is_some());
+    }
+
     #[tokio::test]
     async fn orchestrator_executes_reasoning_plan() {
         let reasoner = Arc::new(MockReasoner {
@@ -422,6 +932,17 @@ pub(crate) mod test_support {
 
         assert_eq!(synthesis.message, "ack");
         assert_eq!(synthesis.inferences.len(), 2);
+        // One ancestor plus a one-hop path.
+        assert_eq!(synthesis.stats.iri_count, 2);
+        assert!(uuid::Uuid::parse_str(&synthesis.request_id).is_ok());
+        assert_eq!(
+            synthesis.stats.iri_count,
+            synthesis
+                .inferences
+                .iter()
+                .map(ReasoningOutcome::iri_count)
+                .sum::<usize>()
+        );
         let calls = reasoner.calls.lock().unwrap();
         assert_eq!(
             calls.as_slice(),
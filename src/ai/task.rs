@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use tracing::info;
+use tracing::{debug, info};
 
 use crate::{
     ai::{KnowledgeOrchestrator, ReasoningCommand},
@@ -18,6 +18,20 @@ impl KnowledgeTask {
         Iri::new(value).map_err(|err| Error::Message(format!("invalid {field} IRI: {err}")))
     }
 
+    /// Reads the prompt inline from `prompt` or from the file named by
+    /// `prompt_file`, which suits long multi-line prompts.
+    fn read_prompt(vars: &Vars) -> Result<String> {
+        match (vars.cli.get("prompt"), vars.cli.get("prompt_file")) {
+            (Some(_), Some(_)) => Err(Error::Message(
+                "`prompt` and `prompt_file` are mutually exclusive".to_string(),
+            )),
+            (None, Some(path)) => std::fs::read_to_string(path).map_err(|err| {
+                Error::Message(format!("failed to read prompt file `{path}`: {err}"))
+            }),
+            _ => Ok(vars.cli_arg("prompt")?.to_string()),
+        }
+    }
+
     fn build_plan(vars: &Vars) -> Result<Vec<ReasoningCommand>> {
         let mut plan = Vec::new();
 
@@ -36,6 +50,15 @@ impl KnowledgeTask {
             });
         }
 
+        if let (Some(property), Some(individual)) =
+            (vars.cli.get("literal_property"), vars.cli.get("individual"))
+        {
+            plan.push(ReasoningCommand::LiteralValues {
+                property: Self::parse_iri(property, "literal_property")?,
+                individual: Self::parse_iri(individual, "individual")?,
+            });
+        }
+
         if let (Some(start), Some(end)) = (vars.cli.get("path_start"), vars.cli.get("path_end")) {
             plan.push(ReasoningCommand::ShortestPath {
                 start: Self::parse_iri(start, "path_start")?,
@@ -43,6 +66,10 @@ impl KnowledgeTask {
             });
         }
 
+        let removed = ReasoningCommand::dedup_plan(&mut plan);
+        if removed > 0 {
+            debug!(removed, "knowledge_plan_deduplicated");
+        }
         Ok(plan)
     }
 }
@@ -58,7 +85,7 @@ impl Task for KnowledgeTask {
     }
 
     async fn run(&self, app_context: &AppContext, vars: &Vars) -> Result<()> {
-        let prompt = vars.cli_arg("prompt")?.to_string();
+        let prompt = Self::read_prompt(vars)?;
         let ontology_id = vars.cli_arg("ontology")?.to_string();
         let ontology = Self::parse_iri(&ontology_id, "ontology")?;
 
@@ -81,9 +108,18 @@ impl Task for KnowledgeTask {
             .await
             .map_err(Error::wrap)?;
 
-        info!(message = %synthesis.message, "knowledge_assistant_response");
+        let request_id = &synthesis.request_id;
+        info!(
+            request_id = %request_id,
+            message = %synthesis.message,
+            "knowledge_assistant_response"
+        );
         for outcome in &synthesis.inferences {
-            info!(context = %outcome.describe(), "knowledge_assistant_reasoning");
+            info!(
+                request_id = %request_id,
+                context = %outcome.describe(),
+                "knowledge_assistant_reasoning"
+            );
         }
 
         Ok(())
@@ -99,70 +135,38 @@ mod tests {
     };
     use crate::config::ReasonerSettings;
     use crate::ontology::service::OntologyService;
-    use crate::ontology::service::{ReasonerHandle, RepositoryHandle};
+    use crate::ontology::service::ReasonerHandle;
     use crate::tests_cfg;
     use std::sync::Arc;
 
-    #[derive(Default)]
-    struct NullRepository;
-
-    #[async_trait]
-    impl crate::ontology::repositories::OntologyRepository for NullRepository {
-        type Error = crate::ontology::service::OntologyServiceError;
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
 
-        async fn insert(
-            &self,
-            _ontology: crate::ontology::entities::Ontology,
-        ) -> Result<(), Self::Error> {
-            Ok(())
-        }
-
-        async fn update(
-            &self,
-            _ontology: crate::ontology::entities::Ontology,
-        ) -> Result<(), Self::Error> {
-            Ok(())
-        }
-
-        async fn get(
-            &self,
-            _iri: &Iri,
-        ) -> Result<Option<crate::ontology::repositories::OntologySnapshot>, Self::Error> {
-            Ok(None)
-        }
-
-        async fn delete(&self, _iri: &Iri) -> Result<(), Self::Error> {
-            Ok(())
+    impl CapturedLogs {
+        fn lines(&self) -> Vec<String> {
+            String::from_utf8_lossy(&self.0.lock().unwrap())
+                .lines()
+                .map(ToString::to_string)
+                .collect()
         }
+    }
 
-        async fn list(
-            &self,
-        ) -> Result<Vec<crate::ontology::repositories::OntologySummary>, Self::Error> {
-            Ok(vec![])
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
         }
 
-        async fn attach_class(
-            &self,
-            _ontology: &Iri,
-            _class: crate::ontology::entities::Class,
-        ) -> Result<(), Self::Error> {
+        fn flush(&mut self) -> std::io::Result<()> {
             Ok(())
         }
+    }
 
-        async fn attach_property(
-            &self,
-            _ontology: &Iri,
-            _property: crate::ontology::entities::Property,
-        ) -> Result<(), Self::Error> {
-            Ok(())
-        }
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for CapturedLogs {
+        type Writer = Self;
 
-        async fn attach_individual(
-            &self,
-            _ontology: &Iri,
-            _individual: crate::ontology::entities::Individual,
-        ) -> Result<(), Self::Error> {
-            Ok(())
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
         }
     }
 
@@ -172,7 +176,7 @@ mod tests {
             ancestors: vec![Iri::new("https://example.org/root").unwrap()],
             ..MockReasoner::default()
         });
-        let repository: Arc<RepositoryHandle> = Arc::new(NullRepository::default());
+        let repository = OntologyService::in_memory(Vec::new()).repository();
         let ontology_service = Arc::new(OntologyService::new(
             repository,
             reasoner.clone() as Arc<ReasonerHandle>,
@@ -198,7 +202,29 @@ mod tests {
             ("class".into(), "https://example.org/root".into()),
         ]);
 
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .finish();
+        let guard = tracing::subscriber::set_default(subscriber);
         task.run(&ctx, &vars).await.expect("task to succeed");
+        drop(guard);
+
+        let request_ids: Vec<String> = logs
+            .lines()
+            .iter()
+            .filter(|line| line.contains("knowledge_assistant_"))
+            .filter_map(|line| {
+                let (_, rest) = line.split_once("request_id=")?;
+                rest.split_whitespace().next().map(ToString::to_string)
+            })
+            .collect();
+        // One response line plus one line per inference.
+        assert_eq!(request_ids.len(), 3);
+        assert!(!request_ids[0].is_empty());
+        assert!(request_ids.iter().all(|id| id == &request_ids[0]));
+
         let calls = reasoner.calls.lock().unwrap();
         assert!(calls.iter().any(|call| call.starts_with("ancestors:")));
         let request = assistant
@@ -209,4 +235,44 @@ mod tests {
             .expect("assistant request");
         assert_eq!(request.inferences.len(), 2); // ancestors + descendants
     }
+
+    #[test]
+    fn prompt_is_read_from_prompt_file() {
+        let tree = tree_fs::TreeBuilder::default()
+            .add_file("prompt.txt", "Summarize\nthe hierarchy")
+            .create()
+            .expect("temp directory");
+        let path = tree.root.join("prompt.txt");
+        let vars = Vars::from_cli_args(vec![(
+            "prompt_file".into(),
+            path.to_string_lossy().into_owned(),
+        )]);
+
+        assert_eq!(
+            KnowledgeTask::read_prompt(&vars).expect("prompt"),
+            "Summarize\nthe hierarchy"
+        );
+    }
+
+    #[test]
+    fn prompt_and_prompt_file_are_mutually_exclusive() {
+        let vars = Vars::from_cli_args(vec![
+            ("prompt".into(), "Summarize".into()),
+            ("prompt_file".into(), "prompt.txt".into()),
+        ]);
+
+        let err = KnowledgeTask::read_prompt(&vars).expect_err("conflicting prompt vars");
+
+        assert!(err.to_string().contains("mutually exclusive"));
+    }
+
+    #[test]
+    fn missing_prompt_file_is_reported() {
+        let vars = Vars::from_cli_args(vec![("prompt_file".into(), "does/not/exist.txt".into())]);
+
+        assert!(matches!(
+            KnowledgeTask::read_prompt(&vars),
+            Err(Error::Message(message)) if message.contains("does/not/exist.txt")
+        ));
+    }
 }
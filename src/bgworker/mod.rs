@@ -463,6 +463,55 @@ impl Queue {
         }
     }
 
+    /// Lists the registered workers by name, paired with each queue they
+    /// consume. Providers without queues report workers without one. When
+    /// `queue` is set only the workers consuming that queue are returned.
+    ///
+    /// Workers are only registered once [`crate::app::Hooks::connect_workers`]
+    /// ran against this queue.
+    #[allow(unused_variables)]
+    pub async fn registered_workers(&self, queue: Option<&str>) -> Vec<(String, Option<String>)> {
+        let mut workers: Vec<(String, Option<String>)> = match self {
+            #[cfg(feature = "bg_redis")]
+            Self::Redis(_, registry, opts, _) => {
+                let queues = redis::get_queues(&opts.queues);
+                registry
+                    .lock()
+                    .await
+                    .handlers()
+                    .keys()
+                    .flat_map(|name| {
+                        queues
+                            .iter()
+                            .map(move |queue| (name.clone(), Some(queue.clone())))
+                    })
+                    .collect()
+            }
+            #[cfg(feature = "bg_pg")]
+            Self::Postgres(_, registry, _, _) => registry
+                .lock()
+                .await
+                .handlers()
+                .keys()
+                .map(|name| (name.clone(), None))
+                .collect(),
+            #[cfg(feature = "bg_sqlt")]
+            Self::Sqlite(_, registry, _, _) => registry
+                .lock()
+                .await
+                .handlers()
+                .keys()
+                .map(|name| (name.clone(), None))
+                .collect(),
+            Self::None => Vec::new(),
+        };
+        if let Some(queue) = queue {
+            workers.retain(|(_, worker_queue)| worker_queue.as_deref() == Some(queue));
+        }
+        workers.sort();
+        workers
+    }
+
     /// Cancels jobs based on the given job name for the configured queue provider.
     ///
     /// # Errors
@@ -912,4 +961,29 @@ mod tests {
 
         assert_eq!(count, 14);
     }
+
+    #[tokio::test]
+    async fn can_list_registered_workers() {
+        let tree_fs = tree_fs::TreeBuilder::default()
+            .drop(true)
+            .create()
+            .expect("create temp folder");
+        let qcfg = sqlite_config(tree_fs.root.as_path());
+        let queue = sqlt::create_provider(&qcfg)
+            .await
+            .expect("create sqlite queue");
+        assert!(queue.registered_workers(None).await.is_empty());
+
+        let ctx = tests_cfg::app::get_app_context().await;
+        queue
+            .register(crate::mailer::MailerWorker::build(&ctx))
+            .await
+            .expect("register worker");
+
+        assert_eq!(
+            queue.registered_workers(None).await,
+            vec![("MailerWorker".to_string(), None)]
+        );
+        assert!(queue.registered_workers(Some("mailer")).await.is_empty());
+    }
 }
@@ -37,7 +37,11 @@ use crate::{
 #[cfg(debug_assertions)]
 use crate::{
     cli::CliScaffoldGenerator,
-    introspection::cli::{adapters::cargo::CargoCliAutomationService, CliAutomationService},
+    doctor::{InitializersDoctorService, SharedDoctorService},
+    introspection::cli::{
+        adapters::cargo::{CargoCliAutomationService, StdCommandExecutor},
+        AsyncCliAutomationService, CommandHistory, StreamingCommandExecutor,
+    },
     introspection::graph::mutation::{GraphMutationService, ScaffoldGenerator},
 };
 
@@ -456,14 +460,14 @@ pub async fn create_app<H: Hooks>(
 /// When could not create the application
 pub async fn run_app<H: Hooks>(mode: &StartMode, app_context: AppContext) -> Result<BootResult> {
     H::before_run(&app_context).await?;
-    let initializers = H::initializers(&app_context).await?;
+    let initializers: Arc<[Box<dyn Initializer>]> = H::initializers(&app_context).await?.into();
 
     info!(
         initializers = ?initializers.iter().map(|init| init.name()).collect::<Vec<_>>().join(","),
         "initializers loaded"
     );
 
-    for initializer in &initializers {
+    for initializer in initializers.iter() {
         initializer.before_run(&app_context).await?;
     }
 
@@ -512,31 +516,42 @@ pub async fn run_app<H: Hooks>(mode: &StartMode, app_context: AppContext) -> Res
 /// Sets up the application's routes based on the provided initializers and hooks.
 async fn setup_routes<H: Hooks>(
     app_context: &AppContext,
-    initializers: &[Box<dyn Initializer>],
+    initializers: &Arc<[Box<dyn Initializer>]>,
 ) -> Result<Router> {
     let app = H::before_routes(app_context).await?;
     let routes_definition = H::routes(app_context);
     let collected_routes = routes_definition.collect();
     let route_descriptors = ApplicationGraphService::collect_route_descriptors(&collected_routes);
-    app_context.shared_store.insert(GraphIntrospectionSeed::new(
-        H::app_name(),
-        route_descriptors,
-    ));
+    app_context.shared_store.insert(
+        GraphIntrospectionSeed::new(H::app_name(), route_descriptors).with_duplicate_routes(
+            ApplicationGraphService::find_duplicate_routes(&collected_routes),
+        ),
+    );
     #[cfg(debug_assertions)]
     {
+        let doctor: SharedDoctorService =
+            Arc::new(InitializersDoctorService::new(Arc::clone(initializers)));
+        app_context.shared_store.insert(doctor);
         let generator: Arc<dyn ScaffoldGenerator> = Arc::new(CliScaffoldGenerator::default());
         app_context
             .shared_store
             .insert(GraphMutationService::new(H::app_name(), generator));
-        let cli_service: Arc<dyn CliAutomationService> =
-            Arc::new(CargoCliAutomationService::default());
+        let console = &app_context.config.introspection.console;
+        let cli_service: Arc<dyn AsyncCliAutomationService> = Arc::new(
+            CargoCliAutomationService::tokio()
+                .with_working_dir(console.working_dir.clone())
+                .with_program(console.automation_program()),
+        );
         app_context.shared_store.insert(cli_service);
+        app_context.shared_store.insert(CommandHistory::default());
+        let streaming_executor: Arc<dyn StreamingCommandExecutor> = Arc::new(StdCommandExecutor);
+        app_context.shared_store.insert(streaming_executor);
     }
 
     let app = routes_definition.to_router::<H>(app_context.clone(), app)?;
     let mut router = H::after_routes(app, app_context).await?;
 
-    for initializer in initializers {
+    for initializer in initializers.iter() {
         router = initializer.after_routes(router, app_context).await?;
     }
 
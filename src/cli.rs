@@ -37,8 +37,8 @@ use crate::introspection::assistant::{
 };
 #[cfg(debug_assertions)]
 use crate::introspection::graph::mutation::{
-    FieldDefinition, GraphMutationService, NodeComponent, NodeCreationCommand, NodeCreationRequest,
-    NodePresentation, ScaffoldGeneration, ScaffoldGenerator,
+    generator_fields, FieldDefinition, GraphMutationService, NodeComponent, NodeCreationCommand,
+    NodeCreationRequest, NodePresentation, ScaffoldGeneration, ScaffoldGenerator,
 };
 use crate::{
     app::{AppContext, Hooks},
@@ -563,6 +563,12 @@ enum JobsCommands {
         #[arg(value_name = "ID")]
         id: String,
     },
+    /// Lists the registered workers and the queues they consume.
+    Workers {
+        /// Only lists the workers consuming this queue.
+        #[arg(long)]
+        queue: Option<String>,
+    },
     /// Change `processing` status to `queue`.
     Requeue {
         /// Change `processing` jobs older than the specified
@@ -1251,7 +1257,7 @@ async fn handle_job_command<H: Hooks>(
     config: Config,
 ) -> crate::Result<()> {
     let app_context = create_context::<H>(environment, config).await?;
-    let queue = app_context.queue_provider.map_or_else(
+    let queue = app_context.queue_provider.clone().map_or_else(
         || {
             println!("queue not configured");
             exit(1);
@@ -1306,6 +1312,17 @@ async fn handle_job_command<H: Hooks>(
             println!("{body}");
             Ok(())
         }
+        JobsCommands::Workers { queue: filter } => {
+            H::connect_workers(&app_context, &queue).await?;
+            let workers: Vec<JsonValue> = queue
+                .registered_workers(filter.as_deref())
+                .await
+                .into_iter()
+                .map(|(name, queue)| json!({ "name": name, "queue": queue }))
+                .collect();
+            println!("{}", serde_json::to_string(&workers)?);
+            Ok(())
+        }
         JobsCommands::Requeue { from_age } => queue.requeue(from_age).await,
     }
 }
@@ -1497,6 +1514,7 @@ fn dispatch_generation<H: Hooks, G: ScaffoldGenerator>(
                 name,
                 with_timestamps: Some(!without_tz),
                 fields: convert_fields(fields),
+                references: vec![],
             };
             run_generation::<H, _>(generator, request)
         }
@@ -1513,6 +1531,7 @@ fn dispatch_generation<H: Hooks, G: ScaffoldGenerator>(
                 name,
                 with_timestamps: Some(!without_tz),
                 fields: convert_fields(fields),
+                references: vec![],
             };
             run_generation::<H, _>(generator, request)
         }
@@ -1534,6 +1553,7 @@ fn dispatch_generation<H: Hooks, G: ScaffoldGenerator>(
                 name,
                 with_timestamps: Some(!without_tz),
                 fields: convert_fields(fields),
+                references: vec![],
                 interface,
             };
             run_generation::<H, _>(generator, request)
@@ -1598,7 +1618,7 @@ fn run_generation<H: Hooks, G: ScaffoldGenerator>(
 fn convert_fields(fields: Vec<(String, String)>) -> Vec<FieldDefinition> {
     fields
         .into_iter()
-        .map(|(name, data_type)| FieldDefinition { name, data_type })
+        .map(|(name, data_type)| FieldDefinition::new(name, data_type))
         .collect()
 }
 
@@ -1652,20 +1672,36 @@ pub struct CliScaffoldGenerator;
 #[cfg(debug_assertions)]
 impl ScaffoldGenerator for CliScaffoldGenerator {
     fn generate(&self, command: NodeCreationCommand) -> crate::Result<ScaffoldGeneration> {
-        let generator = loco_gen::new_generator();
         let component = to_loco_component(command.component);
-        let result = loco_gen::generate(
-            &generator,
-            component,
-            &loco_gen::AppInfo {
-                app_name: command.app_name,
-            },
-        )?;
+        let app_info = loco_gen::AppInfo {
+            app_name: command.app_name,
+        };
+
+        if command.dry_run {
+            return plan_generation(&std::env::current_dir()?, component, &app_info);
+        }
+
+        let generator = loco_gen::new_generator();
+        let result = loco_gen::generate(&generator, component, &app_info)?;
         let message = loco_gen::collect_messages(&result);
         Ok(ScaffoldGeneration::new(message))
     }
 }
 
+#[cfg(debug_assertions)]
+fn plan_generation(
+    root: &std::path::Path,
+    component: loco_gen::Component,
+    app_info: &loco_gen::AppInfo,
+) -> crate::Result<ScaffoldGeneration> {
+    let files = loco_gen::plan(root, component, app_info)?;
+    Ok(ScaffoldGeneration::new(format!(
+        "dry run: {} files would be touched, none were written",
+        files.len()
+    ))
+    .with_files(files))
+}
+
 #[cfg(debug_assertions)]
 fn to_loco_component(component: NodeComponent) -> loco_gen::Component {
     match component {
@@ -1674,31 +1710,34 @@ fn to_loco_component(component: NodeComponent) -> loco_gen::Component {
             name,
             with_timestamps,
             fields,
+            references,
         } => loco_gen::Component::Model {
             name,
             with_tz: with_timestamps,
-            fields: fields.into_iter().map(FieldDefinition::into_pair).collect(),
+            fields: generator_fields(fields, references),
         },
         #[cfg(feature = "with-db")]
         NodeComponent::Migration {
             name,
             with_timestamps,
             fields,
+            references,
         } => loco_gen::Component::Migration {
             name,
             with_tz: with_timestamps,
-            fields: fields.into_iter().map(FieldDefinition::into_pair).collect(),
+            fields: generator_fields(fields, references),
         },
         #[cfg(feature = "with-db")]
         NodeComponent::Scaffold {
             name,
             with_timestamps,
             fields,
+            references,
             interface,
         } => loco_gen::Component::Scaffold {
             name,
             with_tz: with_timestamps,
-            fields: fields.into_iter().map(FieldDefinition::into_pair).collect(),
+            fields: generator_fields(fields, references),
             kind: to_loco_scaffold_kind(interface),
         },
         NodeComponent::Controller {
@@ -1730,6 +1769,7 @@ fn to_loco_scaffold_kind(presentation: NodePresentation) -> loco_gen::ScaffoldKi
 #[cfg(all(test, debug_assertions))]
 mod tests {
     use super::*;
+    use crate::introspection::graph::mutation::{FileAction, PlannedFile};
     use crate::tests_cfg;
     use std::sync::{
         atomic::{AtomicUsize, Ordering},
@@ -1773,6 +1813,58 @@ mod tests {
 
         assert_eq!(generator.call_count(), 1);
     }
+
+    #[test]
+    fn dry_run_lists_files_without_writing_them() {
+        let tree = tree_fs::TreeBuilder::default()
+            .add_file("src/app.rs", "        // tasks-inject\n")
+            .add_file("src/tasks/mod.rs", "")
+            .add_file("tests/tasks/mod.rs", "")
+            .create()
+            .expect("temp directory");
+
+        let generation = plan_generation(
+            &tree.root,
+            to_loco_component(NodeComponent::Task {
+                name: "cleanup".into(),
+            }),
+            &loco_gen::AppInfo {
+                app_name: "demo".into(),
+            },
+        )
+        .expect("dry run to succeed");
+
+        assert_eq!(
+            generation.files,
+            vec![
+                PlannedFile {
+                    path: "src/app.rs".into(),
+                    action: FileAction::Modify,
+                },
+                PlannedFile {
+                    path: "src/tasks/cleanup.rs".into(),
+                    action: FileAction::Create,
+                },
+                PlannedFile {
+                    path: "src/tasks/mod.rs".into(),
+                    action: FileAction::Modify,
+                },
+                PlannedFile {
+                    path: "tests/tasks/cleanup.rs".into(),
+                    action: FileAction::Create,
+                },
+                PlannedFile {
+                    path: "tests/tasks/mod.rs".into(),
+                    action: FileAction::Modify,
+                },
+            ]
+        );
+        assert!(!tree.root.join("src/tasks/cleanup.rs").exists());
+        assert_eq!(
+            std::fs::read_to_string(tree.root.join("src/tasks/mod.rs")).unwrap(),
+            ""
+        );
+    }
 }
 
 #[must_use]
@@ -1,6 +1,10 @@
+use std::collections::BTreeMap;
+
 use crate::introspection::cli::{
-    CliCommand, EnqueueJobRequest, JobStatusRequest, ListGeneratorsRequest, ListJobsRequest,
-    ListTasksRequest, RunDoctorRequest, RunGeneratorRequest, RunTaskRequest,
+    CancelJobRequest, CliCommand, DbStatusRequest, EnqueueJobRequest, JobStatusRequest,
+    ListGeneratorsRequest, ListJobsRequest, ListTasksRequest, ListWorkersRequest,
+    MigrateDownRequest, MigrateRequest, RunDoctorRequest, RunGeneratorRequest, RunTaskRequest,
+    SeedRequest,
 };
 
 #[derive(Default)]
@@ -16,36 +20,44 @@ impl CargoAutomationCommandBuilder {
         }
     }
 
-    fn build_command(args: Vec<String>, environment: Option<&String>) -> CliCommand {
+    fn build_command(
+        args: Vec<String>,
+        environment: Option<&String>,
+        env: &BTreeMap<String, String>,
+    ) -> CliCommand {
         let mut args = args;
         Self::apply_environment(&mut args, environment);
-        CliCommand::new(Self::PROGRAM, args)
+        CliCommand::new(Self::PROGRAM, args).with_env(env.clone())
     }
 
     #[must_use]
     pub fn list_generators(request: &ListGeneratorsRequest) -> CliCommand {
         let args = vec!["loco".into(), "generate".into(), "--help".into()];
-        Self::build_command(args, request.environment.as_ref())
+        Self::build_command(args, request.environment.as_ref(), &request.env)
     }
 
     #[must_use]
     pub fn run_generator(request: &RunGeneratorRequest) -> CliCommand {
         let mut args = vec!["loco".into(), "generate".into(), request.generator.clone()];
         args.extend(request.arguments.clone());
-        Self::build_command(args, request.environment.as_ref())
+        Self::build_command(args, request.environment.as_ref(), &request.env)
+            .with_timeout(request.timeout)
+            .with_capture_nonzero(request.capture_nonzero)
     }
 
     #[must_use]
     pub fn list_tasks(request: &ListTasksRequest) -> CliCommand {
         let args = vec!["loco".into(), "task".into()];
-        Self::build_command(args, request.environment.as_ref())
+        Self::build_command(args, request.environment.as_ref(), &request.env)
     }
 
     #[must_use]
     pub fn run_task(request: &RunTaskRequest) -> CliCommand {
         let mut args = vec!["loco".into(), "task".into(), request.task.clone()];
         args.extend(request.arguments.clone());
-        Self::build_command(args, request.environment.as_ref())
+        Self::build_command(args, request.environment.as_ref(), &request.env)
+            .with_timeout(request.timeout)
+            .with_capture_nonzero(request.capture_nonzero)
     }
 
     #[must_use]
@@ -63,7 +75,7 @@ impl CargoAutomationCommandBuilder {
             args.push("--tag".into());
             args.push(tag.clone());
         }
-        Self::build_command(args, request.environment.as_ref())
+        Self::build_command(args, request.environment.as_ref(), &request.env)
     }
 
     #[must_use]
@@ -91,7 +103,7 @@ impl CargoAutomationCommandBuilder {
             args.push(payload.clone());
         }
         args.extend(request.arguments.clone());
-        Self::build_command(args, request.environment.as_ref())
+        Self::build_command(args, request.environment.as_ref(), &request.env)
     }
 
     #[must_use]
@@ -102,7 +114,7 @@ impl CargoAutomationCommandBuilder {
             "status".into(),
             request.job_id.clone(),
         ];
-        Self::build_command(args, request.environment.as_ref())
+        Self::build_command(args, request.environment.as_ref(), &request.env)
     }
 
     #[must_use]
@@ -121,6 +133,67 @@ impl CargoAutomationCommandBuilder {
         if request.assistant {
             args.push("--assistant".into());
         }
-        Self::build_command(args, request.environment.as_ref())
+        Self::build_command(args, request.environment.as_ref(), &request.env)
+            .with_timeout(request.timeout)
+            .with_capture_nonzero(request.capture_nonzero)
+    }
+
+    #[must_use]
+    pub fn migrate(request: &MigrateRequest) -> CliCommand {
+        let args = vec!["loco".into(), "db".into(), "migrate".into()];
+        Self::build_command(args, request.environment.as_ref(), &request.env)
+            .with_timeout(request.timeout)
+    }
+
+    #[must_use]
+    pub fn migrate_down(request: &MigrateDownRequest) -> CliCommand {
+        let mut args = vec!["loco".into(), "db".into(), "down".into()];
+        if let Some(steps) = request.steps {
+            args.push(steps.to_string());
+        }
+        Self::build_command(args, request.environment.as_ref(), &request.env)
+            .with_timeout(request.timeout)
+    }
+
+    #[must_use]
+    pub fn db_status(request: &DbStatusRequest) -> CliCommand {
+        let args = vec!["loco".into(), "db".into(), "status".into()];
+        Self::build_command(args, request.environment.as_ref(), &request.env)
+    }
+
+    #[must_use]
+    pub fn seed(request: &SeedRequest) -> CliCommand {
+        let mut args = vec!["loco".into(), "db".into(), "seed".into()];
+        if request.reset {
+            args.push("--reset".into());
+        }
+        if let Some(from) = &request.from {
+            args.push("--from".into());
+            args.push(from.clone());
+        }
+        Self::build_command(args, request.environment.as_ref(), &request.env)
+            .with_timeout(request.timeout)
+    }
+
+    #[must_use]
+    pub fn list_workers(request: &ListWorkersRequest) -> CliCommand {
+        let mut args = vec!["loco".into(), "jobs".into(), "workers".into()];
+        if let Some(queue) = &request.queue {
+            args.push("--queue".into());
+            args.push(queue.clone());
+        }
+        Self::build_command(args, request.environment.as_ref(), &request.env)
+    }
+
+    #[must_use]
+    pub fn cancel_job(request: &CancelJobRequest) -> CliCommand {
+        let args = vec![
+            "loco".into(),
+            "jobs".into(),
+            "cancel".into(),
+            "--name".into(),
+            request.name.clone(),
+        ];
+        Self::build_command(args, request.environment.as_ref(), &request.env)
     }
 }
@@ -26,13 +26,18 @@ use std::{
     fs,
     path::{Path, PathBuf},
     sync::OnceLock,
+    time::Duration,
 };
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tracing::info;
 
-use crate::{controller::middleware, environment::Environment, logger, scheduler, Error, Result};
+use crate::{
+    controller::middleware, environment::Environment, introspection::cli::AutomationProgram,
+    logger, scheduler, Error, Result,
+};
 
 static DEFAULT_FOLDER: OnceLock<PathBuf> = OnceLock::new();
 
@@ -642,12 +647,51 @@ impl Default for ReasonerInference {
 ///     model: gpt-4o-mini
 ///     temperature: 0.2
 ///     max_tokens: 512
+///   batch_concurrency: 4
+///   batch_max_prompts: 32
+///   cache:
+///     ttl_secs: 300
+///     max_entries: 256
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+///
+/// Set `assistant: { kind: echo }` to answer with the reasoning context
+/// instead of calling a provider during local development.
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AiSettings {
     /// Configured assistant backend.
     #[serde(default)]
     pub assistant: Option<KnowledgeAssistantBackend>,
+    /// How many prompts of a `POST /ai/knowledge/batch` request run at once.
+    #[serde(default = "AiSettings::default_batch_concurrency")]
+    pub batch_concurrency: usize,
+    /// Largest number of prompts a `POST /ai/knowledge/batch` request may
+    /// carry. Larger batches are rejected with `400 Bad Request`.
+    #[serde(default = "AiSettings::default_batch_max_prompts")]
+    pub batch_max_prompts: usize,
+    /// Caches assistant responses for identical requests when set.
+    #[serde(default)]
+    pub cache: Option<KnowledgeCacheSettings>,
+}
+
+impl Default for AiSettings {
+    fn default() -> Self {
+        Self {
+            assistant: None,
+            batch_concurrency: Self::default_batch_concurrency(),
+            batch_max_prompts: Self::default_batch_max_prompts(),
+            cache: None,
+        }
+    }
+}
+
+impl AiSettings {
+    const fn default_batch_concurrency() -> usize {
+        4
+    }
+
+    const fn default_batch_max_prompts() -> usize {
+        32
+    }
 }
 
 /// Configuration for developer introspection tooling such as the command console.
@@ -656,6 +700,10 @@ pub struct AiSettings {
 pub struct IntrospectionConfig {
     /// Browser-based command console configuration.
     pub console: ConsoleConfig,
+    /// Introspection assistant endpoint configuration.
+    pub assistant: AssistantConfig,
+    /// Readiness probe configuration.
+    pub readiness: ReadinessConfig,
 }
 
 impl IntrospectionConfig {
@@ -672,12 +720,66 @@ pub struct ConsoleConfig {
     /// Enable the console automation routes.
     #[serde(default = "ConsoleConfig::default_enabled")]
     pub enabled: bool,
+    /// Directory `cargo loco` commands are run from, e.g. an app crate inside
+    /// a monorepo. Defaults to the process working directory.
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+    /// Program used instead of `cargo` to run console commands, e.g. a
+    /// prebuilt `./target/release/myapp-cli` or a wrapper script.
+    #[serde(default)]
+    pub program: Option<String>,
+    /// Whether `program` expects the `loco` subcommand (`<program> loco
+    /// generate ...`). Set to `false` for binaries that take the command
+    /// directly (`<program> generate ...`).
+    #[serde(default = "ConsoleConfig::default_loco_subcommand")]
+    pub loco_subcommand: bool,
+    /// `Cargo.toml` of the app crate, passed to `cargo run` as
+    /// `--manifest-path` so commands target the right crate in a workspace.
+    #[serde(default)]
+    pub manifest_path: Option<PathBuf>,
+    /// Workspace member passed to `cargo run` as `-p <package>`.
+    #[serde(default)]
+    pub package: Option<String>,
+    /// Generators the console may run. Empty means every generator is allowed.
+    #[serde(default)]
+    pub allowed_generators: Vec<String>,
+    /// Tasks the console may run. Empty means every task is allowed.
+    #[serde(default)]
+    pub allowed_tasks: Vec<String>,
+    /// Environment variables a request's `env` map may set for the command.
+    /// Any other key is rejected; empty rejects every variable.
+    #[serde(default)]
+    pub allowed_env: Vec<String>,
+    /// Only allow listing and inspection; running generators or tasks and
+    /// cancelling jobs is rejected.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Origins allowed to call the console routes cross-origin, e.g. a
+    /// console UI served from another host. Empty keeps them same-origin only.
+    #[serde(default)]
+    pub cors_origins: Vec<String>,
+    /// Regular expressions whose matches are masked in command output before
+    /// it is returned. When a pattern has a capture group only that group is
+    /// masked.
+    #[serde(default = "ConsoleConfig::default_redactions")]
+    pub redactions: Vec<String>,
 }
 
 impl Default for ConsoleConfig {
     fn default() -> Self {
         Self {
             enabled: Self::default_enabled(),
+            working_dir: None,
+            program: None,
+            loco_subcommand: Self::default_loco_subcommand(),
+            manifest_path: None,
+            package: None,
+            allowed_generators: Vec::new(),
+            allowed_tasks: Vec::new(),
+            allowed_env: Vec::new(),
+            read_only: false,
+            cors_origins: Vec::new(),
+            redactions: Self::default_redactions(),
         }
     }
 }
@@ -686,6 +788,171 @@ impl ConsoleConfig {
     const fn default_enabled() -> bool {
         true
     }
+
+    const fn default_loco_subcommand() -> bool {
+        true
+    }
+
+    /// Program console commands are launched with, `cargo loco` by default.
+    #[must_use]
+    pub fn automation_program(&self) -> AutomationProgram {
+        let program = match &self.program {
+            Some(program) => AutomationProgram {
+                program: program.clone(),
+                loco_subcommand: self.loco_subcommand,
+                ..AutomationProgram::cargo()
+            },
+            None => AutomationProgram::cargo(),
+        };
+        program
+            .with_manifest_path(self.manifest_path.clone())
+            .with_package(self.package.clone())
+    }
+
+    fn default_redactions() -> Vec<String> {
+        vec![
+            r#"(?i)(?:password|passwd|pwd)=([^\s&;"',}\]]+)"#.to_string(),
+            r"://[^:/@\s]+:([^@/\s]+)@".to_string(),
+        ]
+    }
+
+    /// Compiles [`Self::redactions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when a pattern is not a valid regular expression.
+    pub fn redaction_patterns(&self) -> Result<Vec<Regex>> {
+        self.redactions
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|err| {
+                    Error::Message(format!("invalid console redaction `{pattern}`: {err}"))
+                })
+            })
+            .collect()
+    }
+
+    /// Indicates whether the console may run the given generator.
+    #[must_use]
+    pub fn generator_allowed(&self, generator: &str) -> bool {
+        self.allowed_generators.is_empty()
+            || self.allowed_generators.iter().any(|name| name == generator)
+    }
+
+    /// Indicates whether the console may run the given task.
+    #[must_use]
+    pub fn task_allowed(&self, task: &str) -> bool {
+        self.allowed_tasks.is_empty() || self.allowed_tasks.iter().any(|name| name == task)
+    }
+
+    /// Indicates whether a request may set the given environment variable.
+    #[must_use]
+    pub fn env_allowed(&self, key: &str) -> bool {
+        self.allowed_env.iter().any(|name| name == key)
+    }
+}
+
+/// Introspection assistant endpoint configuration.
+///
+/// Example configuration:
+/// ```yaml
+/// introspection:
+///   assistant:
+///     max_per_minute: 30
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AssistantConfig {
+    /// Requests accepted per minute by `POST /__loco/assistant`, shared by the
+    /// whole process. `0` disables the limit.
+    #[serde(default = "AssistantConfig::default_max_per_minute")]
+    pub max_per_minute: u32,
+}
+
+impl Default for AssistantConfig {
+    fn default() -> Self {
+        Self {
+            max_per_minute: Self::default_max_per_minute(),
+        }
+    }
+}
+
+impl AssistantConfig {
+    const fn default_max_per_minute() -> u32 {
+        120
+    }
+}
+
+/// Readiness probe configuration.
+///
+/// Example configuration:
+/// ```yaml
+/// introspection:
+///   readiness:
+///     timeout_ms: 500
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReadinessConfig {
+    /// How long each dependency ping may take before the check is reported as
+    /// failed.
+    #[serde(default = "ReadinessConfig::default_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: Self::default_timeout_ms(),
+        }
+    }
+}
+
+impl ReadinessConfig {
+    const fn default_timeout_ms() -> u64 {
+        2000
+    }
+
+    /// Returns [`Self::timeout_ms`] as a [`Duration`].
+    #[must_use]
+    pub const fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+}
+
+/// Response cache for the knowledge assistant.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KnowledgeCacheSettings {
+    /// How long a cached response stays valid.
+    #[serde(default = "KnowledgeCacheSettings::default_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Maximum cached responses; the least recently used entry is evicted
+    /// first.
+    #[serde(default = "KnowledgeCacheSettings::default_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for KnowledgeCacheSettings {
+    fn default() -> Self {
+        Self {
+            ttl_secs: Self::default_ttl_secs(),
+            max_entries: Self::default_max_entries(),
+        }
+    }
+}
+
+impl KnowledgeCacheSettings {
+    const fn default_ttl_secs() -> u64 {
+        300
+    }
+
+    const fn default_max_entries() -> usize {
+        256
+    }
+
+    /// Returns [`Self::ttl_secs`] as a [`Duration`].
+    #[must_use]
+    pub const fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
 }
 
 /// Supported knowledge assistant backends.
@@ -694,6 +961,9 @@ impl ConsoleConfig {
 pub enum KnowledgeAssistantBackend {
     /// OpenAI chat completion adapter.
     OpenAi(OpenAiSettings),
+    /// Answers with the reasoning context, without calling any provider.
+    /// Intended for local development.
+    Echo,
 }
 
 /// OpenAI adapter configuration options.
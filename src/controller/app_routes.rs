@@ -31,6 +31,8 @@ pub struct ListRoutes {
     pub uri: String,
     pub actions: Vec<axum::http::Method>,
     pub method: axum::routing::MethodRouter<AppContext>,
+    /// Identifier of the handler serving the route, when known.
+    pub handler: Option<String>,
 }
 
 impl fmt::Display for ListRoutes {
@@ -94,6 +96,7 @@ impl AppRoutes {
                         uri,
                         actions: handler.actions.clone(),
                         method: handler.method.clone(),
+                        handler: None,
                     }
                 })
             })
@@ -297,8 +300,12 @@ impl AppRoutes {
         // using the router directly, and ServiceBuilder has been reported to give
         // issues in compile times itself (https://github.com/rust-lang/crates.io/pull/7443).
         //
+        #[cfg(feature = "introspection_console")]
+        let console_cors = super::cli_console::cors_layer(&ctx.config.introspection.console)?;
         for router in self.collect() {
             tracing::info!("{}", router.to_string());
+            #[cfg(feature = "introspection_console")]
+            let router = super::cli_console::with_cors(router, console_cors.as_ref());
             app = app.route(&router.uri, router.method);
         }
 
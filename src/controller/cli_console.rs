@@ -1,18 +1,35 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+    time::{Duration, Instant},
+};
 
 use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::routing::{get, post};
+use futures_util::{stream, Stream};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use tower_http::cors::CorsLayer;
 
 use crate::{
     app::AppContext,
-    controller::{format, Json, Routes},
+    cli::automation::CargoAutomationCommandBuilder,
+    config::ConsoleConfig,
+    controller::{format, middleware::cors::Cors, ErrorDetail, Json, ListRoutes, Routes},
     errors::Error,
     introspection::cli::{
-        CliAutomationService, CommandOutput, JobStatusRequest, JobStatusResponse,
-        ListGeneratorsRequest, ListTasksRequest, RunDoctorRequest, RunGeneratorRequest,
-        RunTaskRequest,
+        redact, redact_text, AsyncCliAutomationService, BlockingCliAutomationService,
+        CancelJobRequest, CliAutomationService, CliCommand, CommandEvent, CommandHistory,
+        CommandHistoryEntry, CommandOutput, JobState, JobStatusRequest, JobStatusResponse,
+        JobStatusWaitRequest, ListGeneratorsRequest, ListTasksRequest, RunDoctorRequest,
+        RunGeneratorRequest, RunTaskRequest, StreamingCommandExecutor,
     },
     Result,
 };
@@ -23,6 +40,14 @@ pub struct ListableCommand {
     pub summary: String,
 }
 
+/// Generator entry enriched with the arguments listed in its usage block.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GeneratorDescriptor {
+    pub command: String,
+    pub summary: String,
+    pub arguments: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GenerationRequest {
     pub generator: String,
@@ -30,6 +55,10 @@ pub struct GenerationRequest {
     pub arguments: Vec<String>,
     #[serde(default)]
     pub environment: Option<String>,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,10 +66,15 @@ pub struct TaskRunRequest {
     pub task: String,
     #[serde(default)]
     pub arguments: Vec<String>,
+    /// Scalar parameters appended as `key:value` arguments.
     #[serde(default)]
-    pub params: BTreeMap<String, String>,
+    pub params: BTreeMap<String, Value>,
     #[serde(default)]
     pub environment: Option<String>,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -55,6 +89,20 @@ pub struct DoctorSnapshotRequest {
     pub graph: bool,
     #[serde(default)]
     pub assistant: bool,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JobCancelRequest {
+    /// Name of the jobs to cancel; every queued job with it is cancelled.
+    pub name: String,
+    #[serde(default)]
+    pub environment: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Serialize, PartialEq, Eq)]
@@ -62,6 +110,18 @@ pub struct CommandExecution {
     pub status: i32,
     pub stdout: String,
     pub stderr: String,
+    /// Wall-clock time the handler spent running the command, or `0` when
+    /// it was not measured.
+    pub duration_ms: u128,
+}
+
+impl CommandExecution {
+    /// Records how long the command took.
+    #[must_use]
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration_ms = duration.as_millis();
+        self
+    }
 }
 
 #[derive(Debug, Serialize, PartialEq)]
@@ -74,7 +134,7 @@ pub struct DoctorSnapshotResponse {
 #[derive(Debug, Serialize, PartialEq)]
 pub struct JobStatusSnapshot {
     pub id: String,
-    pub state: String,
+    pub state: JobState,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<CommandExecution>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -88,14 +148,65 @@ pub struct AutomationQuery {
     environment: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct JobWaitQuery {
+    environment: Option<String>,
+    /// Capped at [`MAX_JOB_WAIT`].
+    timeout_ms: Option<u64>,
+    poll_interval_ms: Option<u64>,
+}
+
+/// Longest a `jobs/{job_id}/wait` request may hold the connection open.
+pub const MAX_JOB_WAIT: Duration = Duration::from_secs(60);
+
+/// Path prefix shared by every console route.
+pub const ROUTE_PREFIX: &str = "/__loco/cli";
+
+/// Builds the CORS layer allowing `introspection.console.cors_origins`, or
+/// `None` when no origin is configured.
+///
+/// # Errors
+///
+/// Returns an error when an origin is not a valid header value.
+pub fn cors_layer(config: &ConsoleConfig) -> Result<Option<CorsLayer>> {
+    if config.cors_origins.is_empty() {
+        return Ok(None);
+    }
+    let cors = Cors {
+        enable: true,
+        allow_origins: config.cors_origins.clone(),
+        ..Cors::default()
+    };
+    cors.cors().map(Some)
+}
+
+/// Wraps console routes with `cors` so they answer `OPTIONS` preflights and
+/// carry `Access-Control-Allow-*` headers. Other routes are returned as-is.
+pub(crate) fn with_cors(mut route: ListRoutes, cors: Option<&CorsLayer>) -> ListRoutes {
+    if let Some(cors) = cors {
+        if route.uri.starts_with(ROUTE_PREFIX) {
+            route.method = route.method.layer(cors.clone());
+        }
+    }
+    route
+}
+
 pub fn routes() -> Routes {
     Routes::new()
         .add("/__loco/cli/generators", get(list_generators))
+        .add(
+            "/__loco/cli/generators/detailed",
+            get(list_generators_detailed),
+        )
         .add("/__loco/cli/generators/run", post(run_generator))
         .add("/__loco/cli/tasks", get(list_tasks))
         .add("/__loco/cli/tasks/run", post(run_task))
+        .add("/__loco/cli/tasks/run/stream", post(run_task_stream))
         .add("/__loco/cli/doctor/snapshot", post(doctor_snapshot))
+        .add("/__loco/cli/jobs/cancel", post(cancel_job))
         .add("/__loco/cli/jobs/{job_id}", get(job_status))
+        .add("/__loco/cli/jobs/{job_id}/wait", get(job_status_wait))
+        .add("/__loco/cli/history", get(command_history))
 }
 
 pub async fn list_generators(
@@ -105,29 +216,69 @@ pub async fn list_generators(
     let service = resolve_service(&ctx)?;
     let request = ListGeneratorsRequest {
         environment: query.environment,
+        env: BTreeMap::new(),
     };
-    let output = service.list_generators(&request)?;
-    let commands = parse_listable_commands(&output.stdout);
+    let output = service.list_generators(&request).await?;
+    let commands = listable_commands(&output)?;
     format::json(commands)
 }
 
+pub async fn list_generators_detailed(
+    State(ctx): State<AppContext>,
+    Query(query): Query<AutomationQuery>,
+) -> Result<axum::response::Response> {
+    let service = resolve_service(&ctx)?;
+    let request = ListGeneratorsRequest {
+        environment: query.environment,
+        env: BTreeMap::new(),
+    };
+    let output = service.list_generators(&request).await?;
+    ensure_listing_succeeded(&output)?;
+    format::json(parse_generator_descriptors(&output.stdout))
+}
+
 pub async fn run_generator(
     State(ctx): State<AppContext>,
     Json(payload): Json<GenerationRequest>,
 ) -> Result<axum::response::Response> {
     let service = resolve_service(&ctx)?;
+    ensure_writable(&ctx)?;
     let GenerationRequest {
         generator,
         arguments,
         environment,
+        timeout_ms,
+        env,
     } = payload;
+    if !ctx
+        .config
+        .introspection
+        .console
+        .generator_allowed(&generator)
+    {
+        return Err(Error::BadRequest(format!(
+            "generator `{generator}` is not allowed by the console configuration"
+        )));
+    }
     let request = RunGeneratorRequest {
         environment,
         generator,
         arguments,
+        timeout: timeout_ms.map(Duration::from_millis),
+        env: allowed_env(&ctx, env)?,
+        capture_nonzero: true,
     };
-    let output = service.run_generator(&request)?;
-    format::json(CommandExecution::from(output))
+    let started = Instant::now();
+    let output = service.run_generator(&request).await;
+    let elapsed = started.elapsed();
+    record_history(
+        &ctx,
+        CargoAutomationCommandBuilder::run_generator(&request),
+        request.environment,
+        output.as_ref().map(|output| output.status),
+    )?;
+    let output = output?;
+    format::json(CommandExecution::from(redacted(&ctx, output)?).with_duration(elapsed))
 }
 
 pub async fn list_tasks(
@@ -137,9 +288,10 @@ pub async fn list_tasks(
     let service = resolve_service(&ctx)?;
     let request = ListTasksRequest {
         environment: query.environment,
+        env: BTreeMap::new(),
     };
-    let output = service.list_tasks(&request)?;
-    let commands = parse_listable_commands(&output.stdout);
+    let output = service.list_tasks(&request).await?;
+    let commands = listable_commands(&output)?;
     format::json(commands)
 }
 
@@ -148,24 +300,118 @@ pub async fn run_task(
     Json(payload): Json<TaskRunRequest>,
 ) -> Result<axum::response::Response> {
     let service = resolve_service(&ctx)?;
+    ensure_writable(&ctx)?;
+    let request = task_request(&ctx, payload)?;
+    let started = Instant::now();
+    let output = service.run_task(&request).await;
+    let elapsed = started.elapsed();
+    record_history(
+        &ctx,
+        CargoAutomationCommandBuilder::run_task(&request),
+        request.environment,
+        output.as_ref().map(|output| output.status),
+    )?;
+    let output = output?;
+    format::json(CommandExecution::from(redacted(&ctx, output)?).with_duration(elapsed))
+}
+
+/// Runs a task and streams its output as server-sent events: one `stdout` or
+/// `stderr` event per line, then `done` with the exit status (or `error`).
+pub async fn run_task_stream(
+    State(ctx): State<AppContext>,
+    Json(payload): Json<TaskRunRequest>,
+) -> Result<Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>> {
+    let executor = resolve_streaming_executor(&ctx)?;
+    ensure_writable(&ctx)?;
+    let request = task_request(&ctx, payload)?;
+    let console = &ctx.config.introspection.console;
+    let task_command = CargoAutomationCommandBuilder::run_task(&request);
+    let command = console
+        .automation_program()
+        .apply(task_command.clone())
+        .with_working_dir(console.working_dir.clone());
+    let patterns = console.redaction_patterns()?;
+
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    let disconnected = CancelOnDrop(Arc::new(AtomicBool::new(false)));
+    let cancel = disconnected.0.clone();
+    tokio::task::spawn_blocking(move || {
+        let result = executor.execute_streaming(&command, &cancel, &mut |event| {
+            let event = match event {
+                CommandEvent::Stdout(line) => Event::default()
+                    .event("stdout")
+                    .data(redact_text(&line, &patterns)),
+                CommandEvent::Stderr(line) => Event::default()
+                    .event("stderr")
+                    .data(redact_text(&line, &patterns)),
+            };
+            let _ = sender.send(event);
+        });
+        if let Err(err) = record_history(
+            &ctx,
+            task_command,
+            request.environment,
+            result.as_ref().copied(),
+        ) {
+            tracing::warn!(error = %err, "could not record streamed task in history");
+        }
+        let last = match result {
+            Ok(status) => Event::default()
+                .event("done")
+                .data(json!({ "status": status }).to_string()),
+            Err(err) => Event::default().event("error").data(err.to_string()),
+        };
+        let _ = sender.send(last);
+    });
+
+    // The guard lives as long as the response body, so a client hanging up
+    // kills the task instead of leaving it running unobserved.
+    let events = stream::unfold(
+        (receiver, disconnected),
+        |(mut receiver, disconnected)| async move {
+            receiver
+                .recv()
+                .await
+                .map(|event| (Ok(event), (receiver, disconnected)))
+        },
+    );
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Sets the flag it holds when dropped.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+fn task_request(ctx: &AppContext, payload: TaskRunRequest) -> Result<RunTaskRequest> {
     let TaskRunRequest {
         task,
         mut arguments,
         params,
         environment,
+        timeout_ms,
+        env,
     } = payload;
-    arguments.extend(
-        params
-            .into_iter()
-            .map(|(key, value)| format!("{key}:{value}")),
-    );
-    let request = RunTaskRequest {
+    if !ctx.config.introspection.console.task_allowed(&task) {
+        return Err(Error::BadRequest(format!(
+            "task `{task}` is not allowed by the console configuration"
+        )));
+    }
+    for (key, value) in &params {
+        arguments.push(encode_param(key, value)?);
+    }
+    Ok(RunTaskRequest {
         environment,
         task,
         arguments,
-    };
-    let output = service.run_task(&request)?;
-    format::json(CommandExecution::from(output))
+        timeout: timeout_ms.map(Duration::from_millis),
+        env: allowed_env(ctx, env)?,
+        capture_nonzero: true,
+    })
 }
 
 pub async fn doctor_snapshot(
@@ -179,6 +425,8 @@ pub async fn doctor_snapshot(
         config,
         graph,
         assistant,
+        timeout_ms,
+        env,
     } = payload;
     let request = RunDoctorRequest {
         environment,
@@ -186,9 +434,12 @@ pub async fn doctor_snapshot(
         config,
         graph,
         assistant,
+        timeout: timeout_ms.map(Duration::from_millis),
+        env: allowed_env(&ctx, env)?,
+        capture_nonzero: true,
     };
-    let output = service.run_doctor(&request)?;
-    format::json(DoctorSnapshotResponse::from(output))
+    let output = service.run_doctor(&request).await?;
+    format::json(DoctorSnapshotResponse::from(redacted(&ctx, output)?))
 }
 
 pub async fn job_status(
@@ -200,19 +451,206 @@ pub async fn job_status(
     let request = JobStatusRequest {
         environment: query.environment,
         job_id,
+        env: BTreeMap::new(),
     };
-    let response = service.job_status(&request)?;
+    let mut response = service.job_status(&request).await?;
+    response.result = response
+        .result
+        .map(|output| redacted(&ctx, output))
+        .transpose()?;
     format::json(JobStatusSnapshot::from(response))
 }
 
-fn resolve_service(ctx: &AppContext) -> Result<Arc<dyn CliAutomationService>> {
+/// Long-polls a job until it reaches a terminal state or the timeout elapses,
+/// returning the latest status either way.
+pub async fn job_status_wait(
+    State(ctx): State<AppContext>,
+    Path(job_id): Path<String>,
+    Query(query): Query<JobWaitQuery>,
+) -> Result<axum::response::Response> {
+    let service = resolve_service(&ctx)?;
+    let timeout = query
+        .timeout_ms
+        .map_or(MAX_JOB_WAIT, Duration::from_millis)
+        .min(MAX_JOB_WAIT);
+    let request = JobStatusWaitRequest {
+        environment: query.environment,
+        job_id,
+        timeout,
+        poll_interval: query.poll_interval_ms.map_or(
+            JobStatusWaitRequest::DEFAULT_POLL_INTERVAL,
+            Duration::from_millis,
+        ),
+        env: BTreeMap::new(),
+    };
+    let mut response = service.job_status_wait(&request).await?;
+    response.result = response
+        .result
+        .map(|output| redacted(&ctx, output))
+        .transpose()?;
+    format::json(JobStatusSnapshot::from(response))
+}
+
+pub async fn cancel_job(
+    State(ctx): State<AppContext>,
+    Json(payload): Json<JobCancelRequest>,
+) -> Result<axum::response::Response> {
+    let service = resolve_service(&ctx)?;
+    ensure_writable(&ctx)?;
+    let JobCancelRequest {
+        name,
+        environment,
+        env,
+    } = payload;
+    let request = CancelJobRequest {
+        environment,
+        name,
+        env: allowed_env(&ctx, env)?,
+    };
+    let output = service.cancel_job(&request).await?;
+    format::json(CommandExecution::from(redacted(&ctx, output)?))
+}
+
+/// Lists the commands recently run from the console, oldest first.
+pub async fn command_history(State(ctx): State<AppContext>) -> Result<axum::response::Response> {
     if !ctx.config.introspection.console.enabled {
-        return Err(Error::NotFound);
+        return Err(console_disabled());
+    }
+    let entries = ctx
+        .shared_store
+        .get_ref::<CommandHistory>()
+        .map(|history| history.entries())
+        .unwrap_or_default();
+    format::json(entries)
+}
+
+/// Appends a command's outcome, its exit status or the error that stopped
+/// it, to the [`CommandHistory`] registered at boot.
+fn record_history(
+    ctx: &AppContext,
+    command: CliCommand,
+    environment: Option<String>,
+    outcome: std::result::Result<i32, &Error>,
+) -> Result<()> {
+    let Some(history) = ctx.shared_store.get_ref::<CommandHistory>() else {
+        return Ok(());
+    };
+    let console = &ctx.config.introspection.console;
+    let command = console.automation_program().apply(command);
+    let patterns = console.redaction_patterns()?;
+    history.record(CommandHistoryEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        command: redact_text(&command.to_shell_string(), &patterns),
+        status: outcome.as_ref().ok().copied(),
+        error: outcome
+            .err()
+            .map(|err| redact_text(&err.to_string(), &patterns)),
+        environment,
+    });
+    Ok(())
+}
+
+/// `404` with `{ "error": "console_disabled" }`, returned while
+/// `introspection.console.enabled` is off.
+fn console_disabled() -> Error {
+    Error::CustomError(
+        StatusCode::NOT_FOUND,
+        ErrorDetail::new(
+            "console_disabled",
+            "the CLI console is disabled by configuration",
+        ),
+    )
+}
+
+/// Rejects mutating actions while `introspection.console.read_only` is set.
+fn ensure_writable(ctx: &AppContext) -> Result<()> {
+    if ctx.config.introspection.console.read_only {
+        return Err(Error::BadRequest("console is read-only".to_string()));
+    }
+    Ok(())
+}
+
+/// Rejects request environment variables missing from
+/// `introspection.console.allowed_env`, so callers cannot inject variables
+/// such as `LD_PRELOAD` or `RUSTC_WRAPPER` into the spawned command.
+fn allowed_env(
+    ctx: &AppContext,
+    env: BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>> {
+    let console = &ctx.config.introspection.console;
+    if let Some(key) = env.keys().find(|key| !console.env_allowed(key)) {
+        return Err(Error::BadRequest(format!(
+            "environment variable `{key}` is not allowed by the console configuration"
+        )));
+    }
+    Ok(env)
+}
+
+/// `503` with `{ "error": "service_unavailable" }`, returned when the console
+/// is enabled but its backing service was never registered.
+fn service_unavailable(service: &str) -> Error {
+    Error::CustomError(
+        StatusCode::SERVICE_UNAVAILABLE,
+        ErrorDetail::new(
+            "service_unavailable".to_string(),
+            format!("no {service} is registered in the shared store"),
+        ),
+    )
+}
+
+/// Resolves the automation service from the shared store, preferring the
+/// async implementation and falling back to a blocking one.
+fn resolve_service(ctx: &AppContext) -> Result<Arc<dyn AsyncCliAutomationService>> {
+    if !ctx.config.introspection.console.enabled {
+        return Err(console_disabled());
+    }
+    if let Some(service) = ctx
+        .shared_store
+        .get_ref::<Arc<dyn AsyncCliAutomationService>>()
+    {
+        return Ok(Arc::clone(&*service));
     }
     ctx.shared_store
         .get_ref::<Arc<dyn CliAutomationService>>()
-        .map(|service| Arc::clone(&*service))
-        .ok_or(Error::NotFound)
+        .map(|service| {
+            Arc::new(BlockingCliAutomationService::new(Arc::clone(&*service)))
+                as Arc<dyn AsyncCliAutomationService>
+        })
+        .ok_or_else(|| service_unavailable("CLI automation service"))
+}
+
+/// Masks secrets configured in `introspection.console.redactions`.
+fn redacted(ctx: &AppContext, output: CommandOutput) -> Result<CommandOutput> {
+    let patterns = ctx.config.introspection.console.redaction_patterns()?;
+    Ok(redact(output, &patterns))
+}
+
+fn resolve_streaming_executor(ctx: &AppContext) -> Result<Arc<dyn StreamingCommandExecutor>> {
+    if !ctx.config.introspection.console.enabled {
+        return Err(console_disabled());
+    }
+    ctx.shared_store
+        .get_ref::<Arc<dyn StreamingCommandExecutor>>()
+        .map(|executor| Arc::clone(&*executor))
+        .ok_or_else(|| service_unavailable("streaming command executor"))
+}
+
+/// Parses a listing command's output, surfacing a failed listing as an error
+/// rather than an empty list.
+fn listable_commands(output: &CommandOutput) -> Result<Vec<ListableCommand>> {
+    ensure_listing_succeeded(output)?;
+    Ok(parse_listable_commands(&output.stdout))
+}
+
+fn ensure_listing_succeeded(output: &CommandOutput) -> Result<()> {
+    if output.status != 0 {
+        return Err(Error::Message(format!(
+            "listing command failed with status {}: {}",
+            output.status,
+            output.stderr.trim()
+        )));
+    }
+    Ok(())
 }
 
 fn parse_listable_commands(stdout: &str) -> Vec<ListableCommand> {
@@ -243,6 +681,81 @@ fn parse_listable_command(line: &str) -> Option<ListableCommand> {
     })
 }
 
+/// Parses `cargo loco generate --help` output into generator descriptors.
+///
+/// Lines indented deeper than a generator entry form its usage block; tokens
+/// that look like arguments (`<NAME>`, `[FIELDS]...`, `--api`) are collected
+/// from it. When a `Commands:` section exists only that section is read.
+fn parse_generator_descriptors(stdout: &str) -> Vec<GeneratorDescriptor> {
+    let has_commands_section = stdout.lines().any(|line| line.trim() == "Commands:");
+    let mut in_commands = !has_commands_section;
+    let mut command_indent: Option<usize> = None;
+    let mut descriptors: Vec<GeneratorDescriptor> = Vec::new();
+
+    for line in stdout.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if trimmed.ends_with(':') && indent == 0 {
+            in_commands = !has_commands_section || trimmed == "Commands:";
+            command_indent = None;
+            continue;
+        }
+        if !in_commands {
+            continue;
+        }
+
+        if let (Some(current), Some(last)) = (command_indent, descriptors.last_mut()) {
+            if indent > current {
+                last.arguments
+                    .extend(parse_usage_arguments(trimmed, &last.command));
+                continue;
+            }
+        }
+
+        match parse_listable_command(line) {
+            Some(ListableCommand { command, summary }) if !command.ends_with(':') => {
+                command_indent = Some(indent);
+                descriptors.push(GeneratorDescriptor {
+                    command,
+                    summary,
+                    arguments: Vec::new(),
+                });
+            }
+            _ => command_indent = None,
+        }
+    }
+
+    descriptors
+}
+
+/// Reads the arguments of a generator from a `Usage:` line, one per token, or
+/// from an argument line such as `-k, --kind <KIND>  Scaffold kind`, kept
+/// whole up to its description.
+fn parse_usage_arguments(line: &str, command: &str) -> Vec<String> {
+    let is_argument = |token: &&str| token.starts_with(['<', '[', '-']);
+    match line.strip_prefix("Usage:") {
+        Some(usage) => usage
+            .split_whitespace()
+            .skip_while(|token| *token != command)
+            .skip(1)
+            .filter(is_argument)
+            .map(ToString::to_string)
+            .collect(),
+        None => {
+            let spec: Vec<&str> = line.split_whitespace().take_while(is_argument).collect();
+            if spec.is_empty() {
+                Vec::new()
+            } else {
+                vec![spec.join(" ")]
+            }
+        }
+    }
+}
+
 impl From<CommandOutput> for CommandExecution {
     fn from(output: CommandOutput) -> Self {
         let CommandOutput {
@@ -254,6 +767,7 @@ impl From<CommandOutput> for CommandExecution {
             status,
             stdout,
             stderr,
+            duration_ms: 0,
         }
     }
 }
@@ -265,16 +779,50 @@ impl From<CommandOutput> for DoctorSnapshotResponse {
             stdout,
             stderr,
         } = output;
+        let stdout = strip_ansi(&stdout);
         let stdout_value =
             serde_json::from_str(&stdout).unwrap_or_else(|_| json!({ "raw": stdout }));
         Self {
             status,
             stdout: stdout_value,
-            stderr,
+            stderr: strip_ansi(&stderr),
         }
     }
 }
 
+/// Encodes a task parameter as a `key:value` argument.
+///
+/// Strings are passed verbatim, numbers and booleans use their JSON form
+/// (`count:3`, `force:true`) and `null` yields an empty value.
+///
+/// # Errors
+///
+/// Returns [`Error::BadRequest`] for arrays and objects.
+pub fn encode_param(key: &str, value: &Value) -> Result<String> {
+    let encoded = match value {
+        Value::String(value) => value.clone(),
+        Value::Number(value) => value.to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Null => String::new(),
+        Value::Array(_) | Value::Object(_) => {
+            return Err(Error::BadRequest(format!(
+                "task parameter `{key}` must be a string, number or boolean"
+            )));
+        }
+    };
+    Ok(format!("{key}:{encoded}"))
+}
+
+static RE_ANSI_ESCAPE: OnceLock<Regex> = OnceLock::new();
+
+/// Removes ANSI colour and cursor sequences such as `\x1b[32m`.
+fn strip_ansi(text: &str) -> String {
+    RE_ANSI_ESCAPE
+        .get_or_init(|| Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]").unwrap())
+        .replace_all(text, "")
+        .into_owned()
+}
+
 impl From<JobStatusResponse> for JobStatusSnapshot {
     fn from(response: JobStatusResponse) -> Self {
         Self {
@@ -291,6 +839,47 @@ impl From<JobStatusResponse> for JobStatusSnapshot {
 mod tests {
     use super::*;
 
+    #[test]
+    fn encode_param_formats_scalars() {
+        assert_eq!(encode_param("count", &json!(3)).unwrap(), "count:3");
+        assert_eq!(encode_param("ratio", &json!(0.5)).unwrap(), "ratio:0.5");
+        assert_eq!(encode_param("force", &json!(true)).unwrap(), "force:true");
+        assert_eq!(encode_param("name", &json!("a b")).unwrap(), "name:a b");
+    }
+
+    #[test]
+    fn encode_param_rejects_nested_values() {
+        let err = encode_param("ids", &json!([1, 2])).unwrap_err();
+        assert!(matches!(err, Error::BadRequest(message) if message.contains("`ids`")));
+        assert!(encode_param("filter", &json!({"a": 1})).is_err());
+    }
+
+    #[test]
+    fn doctor_snapshot_strips_ansi_before_parsing() {
+        let response = DoctorSnapshotResponse::from(CommandOutput::new(
+            0,
+            "\x1b[1m\x1b[32m{\"ok\":true}\x1b[0m\n",
+            "\x1b[33mwarning\x1b[0m: slow",
+        ));
+
+        assert_eq!(response.stdout, json!({"ok": true}));
+        assert_eq!(response.stderr, "warning: slow");
+    }
+
+    #[test]
+    fn doctor_snapshot_keeps_clean_raw_fallback() {
+        let response = DoctorSnapshotResponse::from(CommandOutput::new(
+            1,
+            "\x1b[31m\u{2717}\x1b[0m DB connection: fails",
+            "",
+        ));
+
+        assert_eq!(
+            response.stdout,
+            json!({"raw": "\u{2717} DB connection: fails"})
+        );
+    }
+
     #[test]
     fn parse_listable_command_supports_single_space_separator() {
         let command = parse_listable_command("model Generates a new model");
@@ -304,6 +893,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_generator_descriptors_reads_usage_blocks() {
+        let help = "Generate a new code using templates
+
+Usage: demo-cli generate [OPTIONS] <COMMAND>
+
+Commands:
+  model       Generates a new model file
+      Usage: demo-cli generate model [OPTIONS] <NAME> [FIELDS]...
+  controller  Generates a controller
+      <NAME>       Name of the controller
+      [ACTIONS]... Actions to generate
+      -k, --kind <KIND>  Scaffold kind
+  task        Generates a task
+  help        Print this message or the help of the given subcommand(s)
+
+Options:
+  -h, --help  Print help
+";
+
+        assert_eq!(
+            parse_generator_descriptors(help),
+            vec![
+                GeneratorDescriptor {
+                    command: "model".into(),
+                    summary: "Generates a new model file".into(),
+                    arguments: vec!["[OPTIONS]".into(), "<NAME>".into(), "[FIELDS]...".into()],
+                },
+                GeneratorDescriptor {
+                    command: "controller".into(),
+                    summary: "Generates a controller".into(),
+                    arguments: vec![
+                        "<NAME>".into(),
+                        "[ACTIONS]...".into(),
+                        "-k, --kind <KIND>".into()
+                    ],
+                },
+                GeneratorDescriptor {
+                    command: "task".into(),
+                    summary: "Generates a task".into(),
+                    arguments: vec![],
+                },
+                GeneratorDescriptor {
+                    command: "help".into(),
+                    summary: "Print this message or the help of the given subcommand(s)".into(),
+                    arguments: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_generator_descriptors_without_commands_header() {
+        let descriptors = parse_generator_descriptors("model    Generates a new model\n");
+
+        assert_eq!(
+            descriptors,
+            vec![GeneratorDescriptor {
+                command: "model".into(),
+                summary: "Generates a new model".into(),
+                arguments: vec![],
+            }]
+        );
+    }
+
     #[test]
     fn parse_listable_command_supports_tab_separator() {
         let command = parse_listable_command("migration\tGenerates a migration");
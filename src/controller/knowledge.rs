@@ -1,11 +1,17 @@
-use axum::{extract::State, routing::post};
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    routing::{get, post},
+};
+use futures_util::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    ai::{KnowledgeOrchestrator, ReasoningCommand},
+    ai::{KnowledgeAssistant, KnowledgeOrchestrator, KnowledgeOrchestratorError, ReasoningCommand},
     app::AppContext,
     controller::{format, Json, Routes},
-    ontology::value_objects::Iri,
+    ontology::{lint::OntologyFinding, service::OntologyServiceError, value_objects::Iri},
     Error, Result,
 };
 
@@ -34,12 +40,41 @@ pub enum ReasoningStep {
         start: String,
         end: String,
     },
+    LiteralValues {
+        property: String,
+        individual: String,
+    },
 }
 
 #[derive(Serialize)]
 pub struct KnowledgeResponseBody {
+    /// Correlation id matching the server-side log lines for this request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
     pub message: String,
     pub reasoning: Vec<ReasoningOutcomeView>,
+    /// Why the prompt could not be answered. Only set for batch items.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<KnowledgeStatsView>,
+}
+
+#[derive(Serialize)]
+pub struct KnowledgeStatsView {
+    pub reasoning_ms: u128,
+    pub assistant_ms: u128,
+    pub iri_count: usize,
+}
+
+impl From<crate::ai::KnowledgeStats> for KnowledgeStatsView {
+    fn from(value: crate::ai::KnowledgeStats) -> Self {
+        Self {
+            reasoning_ms: value.reasoning.as_millis(),
+            assistant_ms: value.assistant.as_millis(),
+            iri_count: value.iri_count,
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -55,6 +90,8 @@ impl From<&crate::ai::ReasoningOutcome> for ReasoningOutcomeView {
             crate::ai::ReasoningOutcome::Descendants { .. } => "descendants",
             crate::ai::ReasoningOutcome::RelatedIndividuals { .. } => "related-individuals",
             crate::ai::ReasoningOutcome::ShortestPath { .. } => "shortest-path",
+            crate::ai::ReasoningOutcome::LiteralValues { .. } => "literal-values",
+            crate::ai::ReasoningOutcome::Skipped { .. } => "skipped",
         }
         .to_string();
 
@@ -65,6 +102,25 @@ impl From<&crate::ai::ReasoningOutcome> for ReasoningOutcomeView {
     }
 }
 
+#[derive(Serialize)]
+pub struct OntologyFindingView {
+    pub severity: &'static str,
+    pub check: &'static str,
+    pub subject: String,
+    pub message: String,
+}
+
+impl From<OntologyFinding> for OntologyFindingView {
+    fn from(value: OntologyFinding) -> Self {
+        Self {
+            severity: value.severity.as_str(),
+            check: value.check,
+            subject: value.subject.to_string(),
+            message: value.message,
+        }
+    }
+}
+
 impl KnowledgeResponseBody {
     fn from_synthesis(synthesis: crate::ai::KnowledgeSynthesis) -> Self {
         let reasoning = synthesis
@@ -73,8 +129,21 @@ impl KnowledgeResponseBody {
             .map(ReasoningOutcomeView::from)
             .collect();
         Self {
+            request_id: Some(synthesis.request_id),
             message: synthesis.message,
             reasoning,
+            error: None,
+            stats: Some(synthesis.stats.into()),
+        }
+    }
+
+    fn from_error(error: &Error) -> Self {
+        Self {
+            request_id: None,
+            message: String::new(),
+            reasoning: Vec::new(),
+            error: Some(error.to_string()),
+            stats: None,
         }
     }
 }
@@ -83,49 +152,99 @@ fn parse_iri(value: &str, field: &str) -> Result<Iri> {
     Iri::new(value).map_err(|err| Error::BadRequest(format!("invalid {field} IRI: {err}")))
 }
 
+/// Translates the requested reasoning steps into commands.
+///
+/// Every step is validated before failing, so the returned
+/// [`Error::BadRequest`] lists each invalid field and value at once. Repeated
+/// steps are collapsed, keeping the first occurrence.
 fn build_plan(steps: &[ReasoningStep]) -> Result<Vec<ReasoningCommand>> {
+    let mut errors = Vec::new();
     let mut plan = Vec::with_capacity(steps.len());
-    for step in steps {
-        match step {
+    for (index, step) in steps.iter().enumerate() {
+        let mut parse = |value: &str, field: &str| {
+            Iri::new(value)
+                .map_err(|err| errors.push(format!("reasoning[{index}].{field}: {err}")))
+                .ok()
+        };
+        let command = match step {
             ReasoningStep::Ancestors { class } => {
-                plan.push(ReasoningCommand::Ancestors {
-                    class: parse_iri(class, "class")?,
-                });
+                parse(class, "class").map(|class| ReasoningCommand::Ancestors { class })
             }
             ReasoningStep::Descendants { class } => {
-                plan.push(ReasoningCommand::Descendants {
-                    class: parse_iri(class, "class")?,
-                });
+                parse(class, "class").map(|class| ReasoningCommand::Descendants { class })
             }
             ReasoningStep::RelatedIndividuals {
                 property,
                 individual,
-            } => {
-                plan.push(ReasoningCommand::RelatedIndividuals {
-                    property: parse_iri(property, "property")?,
-                    individual: parse_iri(individual, "individual")?,
-                });
-            }
+            } => match (parse(property, "property"), parse(individual, "individual")) {
+                (Some(property), Some(individual)) => Some(ReasoningCommand::RelatedIndividuals {
+                    property,
+                    individual,
+                }),
+                _ => None,
+            },
             ReasoningStep::ShortestPath { start, end } => {
-                plan.push(ReasoningCommand::ShortestPath {
-                    start: parse_iri(start, "start")?,
-                    end: parse_iri(end, "end")?,
-                });
+                match (parse(start, "start"), parse(end, "end")) {
+                    (Some(start), Some(end)) => Some(ReasoningCommand::ShortestPath { start, end }),
+                    _ => None,
+                }
             }
+            ReasoningStep::LiteralValues {
+                property,
+                individual,
+            } => match (parse(property, "property"), parse(individual, "individual")) {
+                (Some(property), Some(individual)) => Some(ReasoningCommand::LiteralValues {
+                    property,
+                    individual,
+                }),
+                _ => None,
+            },
+        };
+        plan.extend(command);
+    }
+
+    if errors.is_empty() {
+        let removed = ReasoningCommand::dedup_plan(&mut plan);
+        if removed > 0 {
+            tracing::debug!(removed, "knowledge_plan_deduplicated");
         }
+        Ok(plan)
+    } else {
+        Err(Error::BadRequest(format!(
+            "invalid reasoning IRIs: {}",
+            errors.join("; ")
+        )))
     }
-    Ok(plan)
 }
 
-pub async fn invoke(
-    State(ctx): State<AppContext>,
-    Json(payload): Json<KnowledgePrompt>,
-) -> Result<axum::response::Response> {
-    let assistant =
-        ctx.knowledge_assistant.as_ref().cloned().ok_or_else(|| {
-            Error::BadRequest("knowledge assistant is not configured".to_string())
-        })?;
+/// Maps orchestration failures onto HTTP errors: an unknown ontology is a 404,
+/// unknown ontology members are client errors, and anything else stays a 5xx.
+fn orchestration_error(err: KnowledgeOrchestratorError) -> Error {
+    match err {
+        KnowledgeOrchestratorError::Reasoner(OntologyServiceError::Missing { .. }) => {
+            Error::NotFound
+        }
+        KnowledgeOrchestratorError::Reasoner(
+            err @ (OntologyServiceError::MissingClass { .. }
+            | OntologyServiceError::MissingProperty { .. }
+            | OntologyServiceError::MissingIndividual { .. }),
+        ) => Error::BadRequest(err.to_string()),
+        err => Error::wrap(err),
+    }
+}
 
+fn configured_assistant(ctx: &AppContext) -> Result<Arc<dyn KnowledgeAssistant>> {
+    ctx.knowledge_assistant
+        .as_ref()
+        .cloned()
+        .ok_or_else(|| Error::BadRequest("knowledge assistant is not configured".to_string()))
+}
+
+async fn answer(
+    ctx: &AppContext,
+    assistant: Arc<dyn KnowledgeAssistant>,
+    payload: KnowledgePrompt,
+) -> Result<KnowledgeResponseBody> {
     let ontology = parse_iri(&payload.ontology, "ontology")?;
     let plan = build_plan(&payload.reasoning)?;
     let reasoner = ctx.ontology.reasoner();
@@ -133,13 +252,93 @@ pub async fn invoke(
     let synthesis = orchestrator
         .run(ontology, payload.prompt, plan)
         .await
-        .map_err(Error::wrap)?;
+        .map_err(orchestration_error)?;
+
+    Ok(KnowledgeResponseBody::from_synthesis(synthesis))
+}
+
+pub async fn invoke(
+    State(ctx): State<AppContext>,
+    Json(payload): Json<KnowledgePrompt>,
+) -> Result<axum::response::Response> {
+    let assistant = configured_assistant(&ctx)?;
+    format::json(answer(&ctx, assistant, payload).await?)
+}
+
+/// Answers several prompts concurrently, bounded by `ai.batch_concurrency`.
+///
+/// Responses keep the order of the prompts. A prompt that fails is reported
+/// through its `error` field without affecting the others. Batches holding
+/// more than `ai.batch_max_prompts` prompts are rejected as a whole.
+pub async fn invoke_batch(
+    State(ctx): State<AppContext>,
+    Json(payload): Json<Vec<KnowledgePrompt>>,
+) -> Result<axum::response::Response> {
+    let max_prompts = ctx.config.ai.batch_max_prompts;
+    if payload.len() > max_prompts {
+        return Err(Error::BadRequest(format!(
+            "batch holds {} prompts, at most {max_prompts} are allowed",
+            payload.len()
+        )));
+    }
+    let assistant = configured_assistant(&ctx)?;
+    let concurrency = ctx.config.ai.batch_concurrency.max(1);
+
+    let responses: Vec<KnowledgeResponseBody> = stream::iter(payload)
+        .map(|prompt| {
+            let ctx = &ctx;
+            let assistant = Arc::clone(&assistant);
+            async move {
+                answer(ctx, assistant, prompt)
+                    .await
+                    .unwrap_or_else(|err| KnowledgeResponseBody::from_error(&err))
+            }
+        })
+        .buffered(concurrency)
+        .collect()
+        .await;
+
+    format::json(responses)
+}
 
-    format::json(KnowledgeResponseBody::from_synthesis(synthesis))
+/// Runs the hygiene checks against an ontology. The IRI is taken from the
+/// path, percent-encoded.
+pub async fn lint_ontology(
+    State(ctx): State<AppContext>,
+    Path(iri): Path<String>,
+) -> Result<axum::response::Response> {
+    let iri = parse_iri(&iri, "ontology")?;
+    let findings = ctx.ontology.lint(&iri).await.map_err(|err| match err {
+        OntologyServiceError::Missing { .. } => Error::NotFound,
+        err => Error::wrap(err),
+    })?;
+    format::json(
+        findings
+            .into_iter()
+            .map(OntologyFindingView::from)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Reloads the configured ontology seeds. Only available in debug builds.
+#[cfg(debug_assertions)]
+pub async fn reload_ontologies(State(ctx): State<AppContext>) -> Result<axum::response::Response> {
+    let loaded = ctx.ontology.reload_seeds().await.map_err(Error::wrap)?;
+    format::json(serde_json::json!({ "loaded": loaded }))
 }
 
 pub fn routes() -> Routes {
-    Routes::new().add("/ai/knowledge", post(invoke))
+    #[allow(unused_mut)]
+    let mut routes = Routes::new()
+        .add("/ai/knowledge", post(invoke))
+        .add("/ai/knowledge/batch", post(invoke_batch))
+        .add("/ai/ontologies/{iri}/lint", get(lint_ontology));
+
+    #[cfg(debug_assertions)]
+    {
+        routes = routes.add("/ai/ontologies/reload", post(reload_ontologies));
+    }
+    routes
 }
 
 #[cfg(test)]
@@ -155,70 +354,232 @@ mod tests {
         KnowledgeAssistant,
     };
     use crate::config::ReasonerSettings;
-    use crate::ontology::service::{OntologyService, ReasonerHandle, RepositoryHandle};
+    use crate::ontology::service::{OntologyService, ReasonerHandle};
     use crate::tests_cfg;
 
-    #[derive(Default)]
-    struct NullRepository;
+    #[test]
+    fn build_plan_collapses_duplicate_steps() {
+        let class = "https://example.org/child".to_string();
+        let steps = vec![
+            ReasoningStep::Ancestors {
+                class: class.clone(),
+            },
+            ReasoningStep::Descendants {
+                class: class.clone(),
+            },
+            ReasoningStep::Ancestors {
+                class: class.clone(),
+            },
+            ReasoningStep::Descendants { class },
+        ];
+
+        let plan = build_plan(&steps).expect("valid plan");
+
+        let class = Iri::new("https://example.org/child").unwrap();
+        assert_eq!(
+            plan,
+            vec![
+                ReasoningCommand::Ancestors {
+                    class: class.clone()
+                },
+                ReasoningCommand::Descendants { class },
+            ]
+        );
+    }
+
+    #[test]
+    fn build_plan_reports_every_invalid_iri() {
+        let steps = vec![
+            ReasoningStep::Ancestors {
+                class: "https://example.org/child".to_string(),
+            },
+            ReasoningStep::ShortestPath {
+                start: "not an iri".to_string(),
+                end: "also bad".to_string(),
+            },
+        ];
+
+        let Err(Error::BadRequest(message)) = build_plan(&steps) else {
+            panic!("expected a bad request");
+        };
+
+        assert!(message.contains("reasoning[1].start: invalid IRI: not an iri"));
+        assert!(message.contains("reasoning[1].end: invalid IRI: also bad"));
+    }
+
+    struct MissingOntologyReasoner;
 
     #[async_trait::async_trait]
-    impl crate::ontology::repositories::OntologyRepository for NullRepository {
-        type Error = crate::ontology::service::OntologyServiceError;
+    impl crate::ontology::repositories::ReasoningQuery for MissingOntologyReasoner {
+        type Error = OntologyServiceError;
 
-        async fn insert(
+        async fn ancestors_of(
             &self,
-            _ontology: crate::ontology::entities::Ontology,
-        ) -> Result<(), Self::Error> {
-            Ok(())
+            ontology: &Iri,
+            _class: &Iri,
+        ) -> Result<Vec<Iri>, Self::Error> {
+            Err(OntologyServiceError::Missing {
+                ontology: ontology.clone(),
+            })
         }
 
-        async fn update(
+        async fn descendants_of(
             &self,
-            _ontology: crate::ontology::entities::Ontology,
-        ) -> Result<(), Self::Error> {
-            Ok(())
+            ontology: &Iri,
+            _class: &Iri,
+        ) -> Result<Vec<Iri>, Self::Error> {
+            Err(OntologyServiceError::Missing {
+                ontology: ontology.clone(),
+            })
         }
 
-        async fn get(
+        async fn related_individuals(
             &self,
-            _iri: &Iri,
-        ) -> Result<Option<crate::ontology::repositories::OntologySnapshot>, Self::Error> {
-            Ok(None)
+            ontology: &Iri,
+            _via_property: &Iri,
+            _individual: &Iri,
+        ) -> Result<Vec<Iri>, Self::Error> {
+            Err(OntologyServiceError::Missing {
+                ontology: ontology.clone(),
+            })
         }
 
-        async fn delete(&self, _iri: &Iri) -> Result<(), Self::Error> {
-            Ok(())
-        }
-
-        async fn list(
+        async fn literal_values(
             &self,
-        ) -> Result<Vec<crate::ontology::repositories::OntologySummary>, Self::Error> {
-            Ok(vec![])
+            ontology: &Iri,
+            _via_property: &Iri,
+            _individual: &Iri,
+        ) -> Result<Vec<String>, Self::Error> {
+            Err(OntologyServiceError::Missing {
+                ontology: ontology.clone(),
+            })
         }
 
-        async fn attach_class(
+        async fn shortest_path(
             &self,
-            _ontology: &Iri,
-            _class: crate::ontology::entities::Class,
-        ) -> Result<(), Self::Error> {
-            Ok(())
+            ontology: &Iri,
+            _start: &Iri,
+            _end: &Iri,
+        ) -> Result<Option<Vec<Iri>>, Self::Error> {
+            Err(OntologyServiceError::Missing {
+                ontology: ontology.clone(),
+            })
         }
 
-        async fn attach_property(
+        async fn shortest_path_labeled(
             &self,
-            _ontology: &Iri,
-            _property: crate::ontology::entities::Property,
-        ) -> Result<(), Self::Error> {
-            Ok(())
+            ontology: &Iri,
+            _start: &Iri,
+            _end: &Iri,
+        ) -> Result<Option<crate::ontology::repositories::LabeledPath>, Self::Error> {
+            Err(OntologyServiceError::Missing {
+                ontology: ontology.clone(),
+            })
         }
+    }
 
-        async fn attach_individual(
-            &self,
-            _ontology: &Iri,
-            _individual: crate::ontology::entities::Individual,
-        ) -> Result<(), Self::Error> {
-            Ok(())
-        }
+    #[tokio::test]
+    async fn controller_returns_not_found_for_unknown_ontology() {
+        let repository = OntologyService::in_memory(Vec::new()).repository();
+        let ontology_service = std::sync::Arc::new(OntologyService::new(
+            repository,
+            std::sync::Arc::new(MissingOntologyReasoner) as std::sync::Arc<ReasonerHandle>,
+            ReasonerSettings::default(),
+        ));
+        let assistant: std::sync::Arc<dyn KnowledgeAssistant> =
+            std::sync::Arc::new(MockAssistant::default());
+
+        let mut ctx = tests_cfg::app::get_app_context().await;
+        ctx.ontology = ontology_service;
+        ctx.knowledge_assistant = Some(assistant);
+
+        let body = Json(KnowledgePrompt {
+            ontology: "https://example.org/absent".to_string(),
+            prompt: "Explain".to_string(),
+            reasoning: vec![ReasoningStep::Ancestors {
+                class: "https://example.org/child".to_string(),
+            }],
+        });
+
+        let response = invoke(State(ctx), body).await.into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn batch_reports_failures_per_prompt() {
+        let reasoner = std::sync::Arc::new(MockReasoner::default());
+        let repository = OntologyService::in_memory(Vec::new()).repository();
+        let ontology_service = std::sync::Arc::new(OntologyService::new(
+            repository,
+            reasoner.clone() as std::sync::Arc<ReasonerHandle>,
+            ReasonerSettings::default(),
+        ));
+        let assistant: std::sync::Arc<dyn KnowledgeAssistant> =
+            std::sync::Arc::new(MockAssistant {
+                response: crate::ai::KnowledgeResponse {
+                    message: "response".to_string(),
+                },
+                ..MockAssistant::default()
+            });
+
+        let mut ctx = tests_cfg::app::get_app_context().await;
+        ctx.ontology = ontology_service;
+        ctx.knowledge_assistant = Some(assistant);
+
+        let body = Json(vec![
+            KnowledgePrompt {
+                ontology: "https://example.org/ontology".to_string(),
+                prompt: "Explain".to_string(),
+                reasoning: vec![ReasoningStep::Ancestors {
+                    class: "https://example.org/child".to_string(),
+                }],
+            },
+            KnowledgePrompt {
+                ontology: "https://example.org/ontology".to_string(),
+                prompt: "Explain".to_string(),
+                reasoning: vec![ReasoningStep::Descendants {
+                    class: "not an iri".to_string(),
+                }],
+            },
+        ]);
+
+        let response = invoke_batch(State(ctx), body)
+            .await
+            .expect("controller success")
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let items = value.as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["message"], json!("response"));
+        assert!(items[0].get("error").is_none());
+        assert!(items[1]["error"]
+            .as_str()
+            .unwrap()
+            .contains("reasoning[0].class"));
+        assert_eq!(reasoner.calls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn batch_rejects_more_prompts_than_allowed() {
+        let mut ctx = tests_cfg::app::get_app_context().await;
+        ctx.config.ai.batch_max_prompts = 1;
+        let prompt = || KnowledgePrompt {
+            ontology: "https://example.org/ontology".to_string(),
+            prompt: "Explain".to_string(),
+            reasoning: Vec::new(),
+        };
+
+        let err = invoke_batch(State(ctx), Json(vec![prompt(), prompt()]))
+            .await
+            .expect_err("oversized batch is rejected");
+
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
@@ -227,8 +588,7 @@ mod tests {
             ancestors: vec![Iri::new("https://example.org/parent").unwrap()],
             ..MockReasoner::default()
         });
-        let repository: std::sync::Arc<RepositoryHandle> =
-            std::sync::Arc::new(NullRepository::default());
+        let repository = OntologyService::in_memory(Vec::new()).repository();
         let ontology_service = std::sync::Arc::new(OntologyService::new(
             repository,
             reasoner.clone() as std::sync::Arc<ReasonerHandle>,
@@ -267,8 +627,47 @@ mod tests {
         let value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
         assert_eq!(value["message"], json!("response"));
         assert_eq!(value["reasoning"].as_array().unwrap().len(), 1);
+        assert_eq!(value["stats"]["iri_count"], json!(1));
+        assert!(value["request_id"]
+            .as_str()
+            .is_some_and(|id| !id.is_empty()));
         let calls = reasoner.calls.lock().unwrap();
         assert_eq!(calls.len(), 1);
         assert!(assistant.last_request.lock().unwrap().is_some());
     }
+
+    #[tokio::test]
+    async fn lint_endpoint_serializes_findings() {
+        let mut ontology =
+            crate::ontology::Ontology::new(Iri::new("https://example.org/onto").unwrap());
+        ontology
+            .add_class(crate::ontology::Class::new(
+                Iri::new("https://example.org/Lonely").unwrap(),
+            ))
+            .unwrap();
+        let mut ctx = tests_cfg::app::get_app_context().await;
+        ctx.ontology = std::sync::Arc::new(OntologyService::in_memory(vec![ontology]));
+
+        let response = lint_ontology(
+            State(ctx.clone()),
+            Path("https://example.org/onto".to_string()),
+        )
+        .await
+        .expect("controller success")
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(value[0]["severity"], json!("info"));
+        assert_eq!(value[0]["check"], json!("orphan_class"));
+        assert_eq!(value[0]["subject"], json!("https://example.org/Lonely"));
+
+        let missing = lint_ontology(State(ctx), Path("https://example.org/absent".to_string()))
+            .await
+            .into_response();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
 }
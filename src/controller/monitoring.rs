@@ -4,32 +4,54 @@
 
 #[cfg(feature = "introspection_console")]
 use super::cli_console;
+#[cfg(feature = "introspection_assistant")]
+use super::ErrorDetail;
 use super::{format, routes::Routes};
 
 #[cfg(debug_assertions)]
-use crate::introspection::graph::mutation::{
-    GraphMutationService, NodeCreationRequest, ScaffoldGenerator,
+use crate::{
+    doctor::SharedDoctorService,
+    introspection::assistant::findings_from_checks,
+    introspection::graph::mutation::{
+        GraphMutationService, NodeCreationPayload, ScaffoldGenerator,
+    },
+    introspection::graph::service::DoctorHealth,
 };
 
 #[cfg(feature = "introspection_assistant")]
 use crate::introspection::assistant::{
-    self, IntrospectionAssistant, RuleBasedAssistantClient, SharedStoreConversationStore,
+    self, AssistantClient, AssistantRateLimiter, ConversationStore, IntrospectionAssistant,
+    RuleBasedAssistantClient, SharedAssistantClient, SharedStoreConversationStore,
 };
 use crate::{
     app::AppContext,
     config,
     errors::Error,
-    introspection::graph::service::{GraphIntrospectionSeed, GraphQueryService},
+    introspection::graph::{
+        domain::{GraphEdge, GraphNode},
+        service::{GraphIntrospectionSeed, GraphQueryService, GraphSnapshot, GRAPH_SCHEMA_VERSION},
+    },
+    ontology::OntologySummary,
     Result,
 };
-use axum::{extract::State, response::Response, routing::get};
+#[cfg(feature = "introspection_assistant")]
+use axum::routing::delete;
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::Response,
+    routing::get,
+};
 #[cfg(any(debug_assertions, feature = "introspection_assistant"))]
 use axum::{routing::post, Json};
-#[cfg(feature = "introspection_assistant")]
-use serde::Deserialize;
-use serde::Serialize;
-#[cfg(debug_assertions)]
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Display},
+    future::Future,
+    sync::Arc,
+    time::Duration,
+};
 
 /// Represents the health status of the application.
 #[derive(Serialize)]
@@ -37,12 +59,92 @@ pub struct Health {
     pub ok: bool,
 }
 
+/// Readiness of the application along with the status of each dependency.
+#[derive(Debug, Serialize)]
+pub struct ReadinessReport {
+    pub ok: bool,
+    pub checks: ReadinessChecks,
+}
+
+/// Per-dependency readiness. Dependencies that are not configured are omitted.
+#[derive(Debug, Default, Serialize)]
+pub struct ReadinessChecks {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<bool>,
+}
+
+impl ReadinessChecks {
+    /// Returns `true` when every present check passed.
+    #[must_use]
+    pub fn all_ok(&self) -> bool {
+        [self.db, self.queue, self.cache]
+            .into_iter()
+            .flatten()
+            .all(|ok| ok)
+    }
+}
+
+impl From<ReadinessChecks> for ReadinessReport {
+    fn from(checks: ReadinessChecks) -> Self {
+        Self {
+            ok: checks.all_ok(),
+            checks,
+        }
+    }
+}
+
+/// Awaits a dependency ping for at most `timeout`, logging any failure.
+/// Returns whether the dependency is ready.
+async fn probe<E: Display + Debug>(
+    dependency: &str,
+    timeout: Duration,
+    ping: impl Future<Output = std::result::Result<(), E>>,
+) -> bool {
+    match tokio::time::timeout(timeout, ping).await {
+        Ok(Ok(())) => true,
+        Ok(Err(error)) => {
+            tracing::error!(
+                err.msg = %error,
+                err.detail = ?error,
+                "readiness_{dependency}_ping_error"
+            );
+            false
+        }
+        Err(_) => {
+            tracing::error!(
+                timeout_ms = timeout.as_millis(),
+                "readiness_{dependency}_ping_timeout"
+            );
+            false
+        }
+    }
+}
+
+/// Renders a probe response marked `Cache-Control: no-store` so
+/// intermediaries never serve a stale status.
+fn uncached_json<T: Serialize>(item: T) -> Result<Response> {
+    format::render()
+        .header(header::CACHE_CONTROL, "no-store")
+        .json(item)
+}
+
+/// Keeps the status and headers of a probe response but drops its body, for
+/// answering `HEAD` requests.
+fn without_body(response: Result<Response>) -> Result<Response> {
+    let (parts, _) = response?.into_parts();
+    Ok(Response::from_parts(parts, Body::empty()))
+}
+
 /// Check application ping endpoint
 ///
 /// # Errors
 /// This function always returns `Ok` with a JSON response indicating the
 pub async fn ping() -> Result<Response> {
-    format::json(Health { ok: true })
+    uncached_json(Health { ok: true })
 }
 
 /// Check application ping endpoint
@@ -50,77 +152,250 @@ pub async fn ping() -> Result<Response> {
 /// # Errors
 /// This function always returns `Ok` with a JSON response indicating the
 pub async fn health() -> Result<Response> {
-    format::json(Health { ok: true })
+    uncached_json(Health { ok: true })
 }
 
 /// Check the readiness of the application by sending a ping request to
 /// Redis or the DB (depending on feature flags) to ensure connection liveness.
+/// Each ping is bounded by `introspection.readiness.timeout_ms`; a ping that
+/// takes longer counts as a failed check.
 ///
 /// # Errors
-/// All errors are logged, and the readiness status is returned as a JSON response.
+/// All errors are logged, and a [`ReadinessReport`] is returned as a JSON
+/// response.
 pub async fn readiness(State(ctx): State<AppContext>) -> Result<Response> {
-    let mut is_ok: bool = true;
+    #[allow(unused_mut)]
+    let mut checks = ReadinessChecks::default();
+    let timeout = ctx.config.introspection.readiness.timeout();
 
     #[cfg(feature = "with-db")]
-    if let Err(error) = &ctx.db.ping().await {
-        tracing::error!(err.msg = %error, err.detail = ?error, "readiness_db_ping_error");
-        is_ok = false;
+    {
+        checks.db = Some(probe("db", timeout, ctx.db.ping()).await);
     }
 
     if let Some(queue) = &ctx.queue_provider {
-        if let Err(error) = queue.ping().await {
-            tracing::error!(err.msg = %error, err.detail = ?error, "readiness_queue_ping_error");
-            is_ok = false;
-        }
+        checks.queue = Some(probe("queue", timeout, queue.ping()).await);
     }
 
     #[cfg(any(feature = "cache_inmem", feature = "cache_redis"))]
     {
-        match ctx.config.cache {
+        let configured = match ctx.config.cache {
             #[cfg(feature = "cache_inmem")]
-            config::CacheConfig::InMem(_) => {
-                if let Err(error) = &ctx.cache.driver.ping().await {
-                    tracing::error!(err.msg = %error, err.detail = ?error, "readiness_cache_ping_error");
-                    is_ok = false;
-                }
-            }
+            config::CacheConfig::InMem(_) => true,
             #[cfg(feature = "cache_redis")]
-            config::CacheConfig::Redis(_) => {
-                if let Err(error) = &ctx.cache.driver.ping().await {
-                    tracing::error!(err.msg = %error, err.detail = ?error, "readiness_cache_ping_error");
-                    is_ok = false;
-                }
-            }
-            config::CacheConfig::Null => (),
+            config::CacheConfig::Redis(_) => true,
+            config::CacheConfig::Null => false,
+        };
+        if configured {
+            checks.cache = Some(probe("cache", timeout, ctx.cache.driver.ping()).await);
         }
     }
 
-    format::json(Health { ok: is_ok })
+    uncached_json(ReadinessReport::from(checks))
+}
+
+fn graph_snapshot(ctx: &AppContext) -> Result<GraphSnapshot> {
+    let seed = ctx
+        .shared_store
+        .get_ref::<GraphIntrospectionSeed>()
+        .ok_or_else(|| Error::Message("application graph metadata unavailable".to_string()))?;
+    Ok(seed.into_service(ctx).snapshot())
+}
+
+/// Query parameters accepted by the graph endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct GraphQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+    /// Indent the JSON body for reading in a browser.
+    #[serde(default)]
+    pub pretty: bool,
+    /// Only report scheduler jobs carrying this tag.
+    pub tag: Option<String>,
 }
 
 /// Returns the application graph snapshot used for introspection adapters.
-pub async fn graph(State(ctx): State<AppContext>) -> Result<Response> {
-    let snapshot = {
-        let seed = ctx
-            .shared_store
-            .get_ref::<GraphIntrospectionSeed>()
-            .ok_or_else(|| Error::Message("application graph metadata unavailable".to_string()))?;
-        seed.into_service(&ctx).snapshot()
+///
+/// `?offset=` and `?limit=` page through the routes; the response then carries
+/// `routes_total`. `?tag=` keeps only the scheduler jobs carrying that tag.
+/// `?pretty=true` indents the JSON. Responses carry an `ETag` so pollers sending
+/// `If-None-Match` get `304 Not Modified` while the topology is unchanged.
+pub async fn graph(
+    State(ctx): State<AppContext>,
+    Query(query): Query<GraphQuery>,
+    headers: HeaderMap,
+) -> Result<Response> {
+    let mut snapshot = graph_snapshot(&ctx)?;
+    if query.offset.is_some() || query.limit.is_some() {
+        snapshot = snapshot.paginate_routes(query.offset.unwrap_or_default(), query.limit);
+    }
+    if let Some(tag) = &query.tag {
+        snapshot = snapshot.with_scheduler_tag(tag);
+    }
+
+    let body = if query.pretty {
+        serde_json::to_vec_pretty(&snapshot)?
+    } else {
+        serde_json::to_vec(&snapshot)?
     };
+    let etag = format!("\"{:016x}\"", fnv1a(&body));
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .any(|candidate| candidate == "*" || candidate == etag)
+        });
+    if not_modified {
+        return format::render()
+            .status(StatusCode::NOT_MODIFIED)
+            .etag(&etag)?
+            .empty();
+    }
+
+    Ok(format::render()
+        .etag(&etag)?
+        .response()
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))?)
+}
+
+/// 64-bit FNV-1a, stable across processes and toolchains unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
 
-    format::json(snapshot)
+/// Returns the graph snapshot counts as Prometheus gauges.
+pub async fn graph_metrics(State(ctx): State<AppContext>) -> Result<Response> {
+    let metrics = graph_snapshot(&ctx)?.to_prometheus();
+    Ok(format::render()
+        .response()
+        .header(header::CONTENT_TYPE, "text/plain; version=0.0.4")
+        .body(Body::from(metrics))?)
+}
+
+/// One line of the `GET /__loco/graph/stream` response.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum GraphStreamLine {
+    Meta { schema_version: u32 },
+    Node(GraphNode),
+    Edge(GraphEdge),
+}
+
+/// Streams the application graph as JSON Lines so large graphs can be
+/// consumed incrementally: a `{"type":"meta","schema_version":N}` header,
+/// then one `node` line per node and one `edge` line per edge.
+pub async fn graph_stream(State(ctx): State<AppContext>) -> Result<Response> {
+    let graph = ctx
+        .shared_store
+        .get_ref::<GraphIntrospectionSeed>()
+        .ok_or_else(|| Error::Message("application graph metadata unavailable".to_string()))?
+        .into_service(&ctx)
+        .build_graph();
+
+    let lines = std::iter::once(GraphStreamLine::Meta {
+        schema_version: GRAPH_SCHEMA_VERSION,
+    })
+    .chain(graph.nodes.into_iter().map(GraphStreamLine::Node))
+    .chain(graph.edges.into_iter().map(GraphStreamLine::Edge))
+    .map(|line| {
+        serde_json::to_vec(&line).map(|mut bytes| {
+            bytes.push(b'\n');
+            bytes
+        })
+    });
+
+    Ok(format::render()
+        .response()
+        .header(header::CONTENT_TYPE, "application/x-ndjson")
+        .body(Body::from_stream(futures_util::stream::iter(lines)))?)
+}
+
+/// Runs the doctor checks in-process and returns them as findings. Only
+/// routed in debug builds, since every call opens database and queue
+/// connections.
+#[cfg(debug_assertions)]
+pub async fn doctor(State(ctx): State<AppContext>) -> Result<Response> {
+    let service = ctx
+        .shared_store
+        .get_ref::<SharedDoctorService>()
+        .map(|service| Arc::clone(&*service))
+        .ok_or_else(|| Error::Message("doctor service unavailable".to_string()))?;
+    let checks = service.run(&ctx).await?;
+    ctx.shared_store.insert(DoctorHealth::from_checks(&checks));
+    format::json(findings_from_checks(&checks))
+}
+
+/// Ontology counts as reported by [`overview`].
+#[derive(Debug, Serialize)]
+pub struct OntologyOverview {
+    pub iri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub class_count: usize,
+    pub property_count: usize,
+    pub individual_count: usize,
+}
+
+impl From<OntologySummary> for OntologyOverview {
+    fn from(summary: OntologySummary) -> Self {
+        Self {
+            iri: summary.iri.to_string(),
+            label: summary.label,
+            class_count: summary.class_count,
+            property_count: summary.property_count,
+            individual_count: summary.individual_count,
+        }
+    }
+}
+
+/// Everything introspection tools need in a single response.
+#[derive(Debug, Serialize)]
+pub struct ApplicationOverview {
+    /// `null` when graph metadata was never published.
+    pub graph: Option<GraphSnapshot>,
+    /// `null` when the ontology repository could not be listed.
+    pub ontologies: Option<Vec<OntologyOverview>>,
+    /// Whether `POST /__loco/assistant` is compiled in.
+    pub assistant_available: bool,
+    /// Whether the CLI console routes are compiled in and enabled.
+    pub console_enabled: bool,
+}
+
+/// Combines the graph snapshot, ontology summaries and the availability of
+/// the assistant and console, saving tools several round trips.
+pub async fn overview(State(ctx): State<AppContext>) -> Result<Response> {
+    let ontologies = match ctx.ontology.repository().list().await {
+        Ok(summaries) => Some(summaries.into_iter().map(Into::into).collect()),
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to list ontologies for the overview");
+            None
+        }
+    };
+    format::json(ApplicationOverview {
+        graph: graph_snapshot(&ctx).ok(),
+        ontologies,
+        assistant_available: cfg!(feature = "introspection_assistant"),
+        console_enabled: cfg!(feature = "introspection_console")
+            && ctx.config.introspection.console.enabled,
+    })
 }
 
 #[cfg(debug_assertions)]
 pub async fn create_graph_node(
     State(ctx): State<AppContext>,
-    Json(request): Json<NodeCreationRequest>,
+    Json(payload): Json<NodeCreationPayload>,
 ) -> Result<Response> {
     let service = ctx
         .shared_store
         .get_ref::<GraphMutationService<Arc<dyn ScaffoldGenerator>>>()
         .ok_or_else(|| Error::Message("scaffold generator unavailable".to_string()))?;
-    let generation = service.create_node(request)?;
+    let generation = service.submit(payload)?;
     format::json(generation)
 }
 
@@ -129,6 +404,12 @@ pub async fn create_graph_node(
 pub struct AssistantRequestBody {
     #[serde(default)]
     pub doctor_findings: Vec<assistant::DoctorFinding>,
+    /// Drops findings less severe than this status before advising.
+    #[serde(default)]
+    pub min_status: Option<assistant::DoctorStatus>,
+    /// Returns the graph snapshot alongside the advice.
+    #[serde(default)]
+    pub include_graph: bool,
 }
 
 #[cfg(feature = "introspection_assistant")]
@@ -136,38 +417,114 @@ pub async fn assistant(
     State(ctx): State<AppContext>,
     Json(payload): Json<AssistantRequestBody>,
 ) -> Result<Response> {
-    let conversation_store = SharedStoreConversationStore::new(ctx.shared_store.clone());
-    let client = RuleBasedAssistantClient::default();
-    let advice = {
-        let seed = ctx
-            .shared_store
-            .get_ref::<GraphIntrospectionSeed>()
-            .ok_or_else(|| Error::Message("application graph metadata unavailable".to_string()))?;
-        let app_name = seed.app_name.clone();
-        let graph_service = seed.into_service(&ctx);
-        let adapter = IntrospectionAssistant::new(
-            app_name.as_str(),
-            &graph_service,
-            &client,
-            &conversation_store,
-        );
-
-        adapter
-            .advise(&payload.doctor_findings)
-            .await
-            .map_err(|error| Error::Message(error.to_string()))?
+    if !acquire_assistant_token(&ctx) {
+        return Err(Error::CustomError(
+            StatusCode::TOO_MANY_REQUESTS,
+            ErrorDetail::new(
+                "too_many_requests",
+                "assistant rate limit exceeded, retry later",
+            ),
+        ));
+    }
+    let AssistantRequestBody {
+        mut doctor_findings,
+        min_status,
+        include_graph,
+    } = payload;
+    if let Some(min_status) = min_status {
+        doctor_findings.retain(|finding| finding.status >= min_status);
+    }
+    let remote = ctx
+        .shared_store
+        .get_ref::<SharedAssistantClient>()
+        .map(|client| Arc::clone(&*client));
+    let advice = match remote {
+        Some(client) => advise_with(&ctx, client.as_ref(), &doctor_findings, include_graph).await?,
+        None => {
+            advise_with(
+                &ctx,
+                &RuleBasedAssistantClient,
+                &doctor_findings,
+                include_graph,
+            )
+            .await?
+        }
     };
 
     format::json(advice)
 }
 
+/// Runs the assistant pipeline with the given client.
+#[cfg(feature = "introspection_assistant")]
+async fn advise_with<C: AssistantClient + ?Sized>(
+    ctx: &AppContext,
+    client: &C,
+    doctor_findings: &[assistant::DoctorFinding],
+    include_graph: bool,
+) -> Result<assistant::AssistantAdvice> {
+    let conversation_store = SharedStoreConversationStore::new(ctx.shared_store.clone());
+    let seed = ctx
+        .shared_store
+        .get_ref::<GraphIntrospectionSeed>()
+        .ok_or_else(|| Error::Message("application graph metadata unavailable".to_string()))?;
+    let app_name = seed.app_name.clone();
+    let graph_service = seed.into_service(ctx);
+    let adapter = IntrospectionAssistant::new(
+        app_name.as_str(),
+        &graph_service,
+        client,
+        &conversation_store,
+    )
+    .include_graph(include_graph);
+
+    adapter
+        .advise(doctor_findings)
+        .await
+        .map_err(|error| Error::Message(error.to_string()))
+}
+
+/// Resets the assistant conversation history.
+#[cfg(feature = "introspection_assistant")]
+pub async fn clear_assistant_history(State(ctx): State<AppContext>) -> Result<Response> {
+    SharedStoreConversationStore::new(ctx.shared_store.clone()).clear();
+    format::empty()
+}
+
+/// Takes a token from the process-wide assistant limiter, creating it from
+/// `introspection.assistant.max_per_minute` on first use.
+#[cfg(feature = "introspection_assistant")]
+fn acquire_assistant_token(ctx: &AppContext) -> bool {
+    if !ctx.shared_store.contains::<AssistantRateLimiter>() {
+        ctx.shared_store.insert(AssistantRateLimiter::per_minute(
+            ctx.config.introspection.assistant.max_per_minute,
+        ));
+    }
+    ctx.shared_store
+        .get_ref::<AssistantRateLimiter>()
+        .map_or(true, |limiter| limiter.try_acquire())
+}
+
 /// Defines and returns the readiness-related routes.
 pub fn routes() -> Routes {
     let mut routes = Routes::new()
-        .add("/_readiness", get(readiness))
-        .add("/_ping", get(ping))
-        .add("/_health", get(health))
-        .add("/__loco/graph", get(graph));
+        .add(
+            "/_readiness",
+            get(readiness).head(|state: State<AppContext>| async move {
+                without_body(readiness(state).await)
+            }),
+        )
+        .add(
+            "/_ping",
+            get(ping).head(|| async { without_body(ping().await) }),
+        )
+        .add(
+            "/_health",
+            get(health).head(|| async { without_body(health().await) }),
+        )
+        .add("/__loco/graph", get(graph))
+        .add("/__loco/graph/metrics", get(graph_metrics))
+        .add("/__loco/graph/stream", get(graph_stream))
+        .add("/__loco/overview", get(overview));
 
     #[cfg(feature = "introspection_console")]
     {
@@ -176,12 +533,16 @@ pub fn routes() -> Routes {
 
     #[cfg(debug_assertions)]
     {
-        routes = routes.add("/__loco/graph/nodes", post(create_graph_node));
+        routes = routes
+            .add("/__loco/graph/nodes", post(create_graph_node))
+            .add("/__loco/doctor", get(doctor));
     }
 
     #[cfg(feature = "introspection_assistant")]
     {
-        routes = routes.add("/__loco/assistant", post(assistant));
+        routes = routes
+            .add("/__loco/assistant", post(assistant))
+            .add("/__loco/assistant/history", delete(clear_assistant_history));
     }
     routes
 }
@@ -192,8 +553,9 @@ mod tests {
     use crate::tests_cfg::redis::setup_redis_container;
     use axum::routing::get;
     use loco_rs::tests_cfg::db::fail_connection;
-    use loco_rs::{bgworker, cache, config, controller::monitoring, tests_cfg};
+    use loco_rs::{bgworker, cache, config, controller::monitoring, doctor, tests_cfg};
     use serde_json::Value;
+    use std::collections::BTreeMap;
     use tower::ServiceExt;
 
     #[tokio::test]
@@ -224,6 +586,125 @@ mod tests {
         assert_eq!(res_json["ok"], true);
     }
 
+    #[tokio::test]
+    async fn ping_head_has_no_body() {
+        let ctx = tests_cfg::app::get_app_context().await;
+
+        let router = monitoring::routes()
+            .handlers
+            .into_iter()
+            .fold(axum::Router::new(), |router, handler| {
+                router.route(&handler.uri, handler.method)
+            })
+            .with_state(ctx);
+
+        let req = axum::http::Request::builder()
+            .uri("/_ping")
+            .method("HEAD")
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let response = router.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers().get(axum::http::header::CACHE_CONTROL),
+            Some(&axum::http::HeaderValue::from_static("no-store"))
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn overview_reports_a_disabled_console() {
+        let mut ctx = tests_cfg::app::get_app_context().await;
+        ctx.config.introspection.console.enabled = false;
+
+        let router = axum::Router::new()
+            .route("/__loco/overview", get(monitoring::overview))
+            .with_state(ctx);
+        let req = axum::http::Request::builder()
+            .uri("/__loco/overview")
+            .method("GET")
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let response = router.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let res_json: Value = serde_json::from_slice(&body).expect("Valid JSON response");
+        assert_eq!(res_json["console_enabled"], false);
+        assert_eq!(res_json["graph"], Value::Null);
+        assert_eq!(res_json["ontologies"], serde_json::json!([]));
+        assert_eq!(
+            res_json["assistant_available"],
+            cfg!(feature = "introspection_assistant")
+        );
+    }
+
+    #[tokio::test]
+    async fn doctor_serializes_findings_from_service() {
+        struct FakeDoctor;
+
+        #[async_trait::async_trait]
+        impl doctor::DoctorService for FakeDoctor {
+            async fn run(
+                &self,
+                _ctx: &loco_rs::app::AppContext,
+            ) -> loco_rs::Result<BTreeMap<doctor::Resource, doctor::Check>> {
+                Ok(BTreeMap::from([(
+                    doctor::Resource::Database,
+                    doctor::Check {
+                        status: doctor::CheckStatus::NotOk,
+                        message: "DB connection: fails".to_string(),
+                        description: Some("connection refused".to_string()),
+                    },
+                )]))
+            }
+        }
+
+        let ctx = tests_cfg::app::get_app_context().await;
+        let service: doctor::SharedDoctorService = std::sync::Arc::new(FakeDoctor);
+        ctx.shared_store.insert(service);
+
+        let router = axum::Router::new()
+            .route("/__loco/doctor", get(monitoring::doctor))
+            .with_state(ctx.clone());
+        let req = axum::http::Request::builder()
+            .uri("/__loco/doctor")
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let response = router.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let findings: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            findings,
+            serde_json::json!([{
+                "resource": "Database",
+                "status": "Failing",
+                "message": "DB connection: fails",
+                "detail": "connection refused"
+            }])
+        );
+
+        use loco_rs::introspection::graph::service::{DoctorHealth, HealthSource};
+        let health = ctx
+            .shared_store
+            .get_ref::<DoctorHealth>()
+            .expect("doctor health published");
+        assert!(!health.is_healthy());
+    }
+
     #[tokio::test]
     async fn health_works() {
         let ctx = tests_cfg::app::get_app_context().await;
@@ -338,6 +819,7 @@ mod tests {
             .unwrap();
         let res_json: Value = serde_json::from_slice(&body).expect("Valid JSON response");
         assert_eq!(res_json["ok"], false);
+        assert_eq!(res_json["checks"]["db"], false);
     }
 
     #[cfg(feature = "cache_inmem")]
@@ -454,6 +936,68 @@ mod tests {
             .unwrap();
         let res_json: Value = serde_json::from_slice(&body).expect("Valid JSON response");
         assert_eq!(res_json["ok"], false);
+        assert_eq!(res_json["checks"]["cache"], false);
+    }
+
+    #[cfg(feature = "cache_inmem")]
+    #[tokio::test]
+    async fn readiness_times_out_slow_dependency() {
+        struct SlowCacheDriver;
+
+        #[async_trait::async_trait]
+        impl cache::drivers::CacheDriver for SlowCacheDriver {
+            async fn ping(&self) -> cache::CacheResult<()> {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                Ok(())
+            }
+            async fn contains_key(&self, _key: &str) -> cache::CacheResult<bool> {
+                Ok(false)
+            }
+            async fn get(&self, _key: &str) -> cache::CacheResult<Option<String>> {
+                Ok(None)
+            }
+            async fn insert(&self, _key: &str, _value: &str) -> cache::CacheResult<()> {
+                Ok(())
+            }
+            async fn insert_with_expiry(
+                &self,
+                _key: &str,
+                _value: &str,
+                _duration: std::time::Duration,
+            ) -> cache::CacheResult<()> {
+                Ok(())
+            }
+            async fn remove(&self, _key: &str) -> cache::CacheResult<()> {
+                Ok(())
+            }
+            async fn clear(&self) -> cache::CacheResult<()> {
+                Ok(())
+            }
+        }
+
+        let mut ctx = tests_cfg::app::get_app_context().await;
+        ctx.config.introspection.readiness.timeout_ms = 20;
+        ctx.cache = cache::Cache::new(Box::new(SlowCacheDriver)).into();
+
+        let router = axum::Router::new()
+            .route("/_readiness", get(monitoring::readiness))
+            .with_state(ctx);
+
+        let req = axum::http::Request::builder()
+            .uri("/_readiness")
+            .method("GET")
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let response = router.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let res_json: Value = serde_json::from_slice(&body).expect("Valid JSON response");
+        assert_eq!(res_json["ok"], false);
+        assert_eq!(res_json["checks"]["cache"], false);
     }
 
     #[tokio::test]
@@ -537,4 +1081,107 @@ mod tests {
         let res_json: Value = serde_json::from_slice(&body).expect("Valid JSON response");
         assert_eq!(res_json["ok"], false);
     }
+
+    #[cfg(feature = "introspection_assistant")]
+    #[tokio::test]
+    async fn assistant_returns_too_many_requests_when_bucket_is_exhausted() {
+        use loco_rs::introspection::graph::service::GraphIntrospectionSeed;
+
+        let mut ctx = tests_cfg::app::get_app_context().await;
+        ctx.config.introspection.assistant.max_per_minute = 1;
+        ctx.shared_store
+            .insert(GraphIntrospectionSeed::new("demo", Vec::new()));
+
+        let router = axum::Router::new()
+            .route(
+                "/__loco/assistant",
+                axum::routing::post(monitoring::assistant),
+            )
+            .with_state(ctx);
+        let request = || {
+            axum::http::Request::builder()
+                .uri("/__loco/assistant")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(axum::body::Body::from("{}"))
+                .unwrap()
+        };
+
+        let response = router.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let response = router.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), 429);
+    }
+
+    #[cfg(feature = "introspection_assistant")]
+    #[tokio::test]
+    async fn assistant_uses_client_registered_in_shared_store() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        use async_trait::async_trait;
+        use loco_rs::introspection::{
+            assistant::{
+                AssistantClient, AssistantCompletion, AssistantError, AssistantRequest,
+                SharedAssistantClient,
+            },
+            graph::service::GraphIntrospectionSeed,
+        };
+
+        struct RecordingClient {
+            calls: Arc<AtomicUsize>,
+        }
+
+        #[async_trait]
+        impl AssistantClient for RecordingClient {
+            type Error = AssistantError;
+
+            async fn complete(
+                &self,
+                _request: AssistantRequest,
+            ) -> Result<AssistantCompletion, Self::Error> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Ok(AssistantCompletion {
+                    reply: "remote reply".to_string(),
+                    suggestions: Vec::new(),
+                    usage: None,
+                })
+            }
+        }
+
+        let ctx = tests_cfg::app::get_app_context().await;
+        ctx.shared_store
+            .insert(GraphIntrospectionSeed::new("demo", Vec::new()));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let client: SharedAssistantClient = Arc::new(RecordingClient {
+            calls: Arc::clone(&calls),
+        });
+        ctx.shared_store.insert(client);
+
+        let router = axum::Router::new()
+            .route(
+                "/__loco/assistant",
+                axum::routing::post(monitoring::assistant),
+            )
+            .with_state(ctx);
+        let request = axum::http::Request::builder()
+            .uri("/__loco/assistant")
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from("{}"))
+            .unwrap();
+
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let res_json: Value = serde_json::from_slice(&body).expect("Valid JSON response");
+        assert_eq!(res_json["response"], "remote reply");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
 }
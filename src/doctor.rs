@@ -11,6 +11,7 @@
 //! When you run `cargo loco doctor`, any initializers that implement the `check` method
 //! will have their health checks executed and displayed in the output.
 
+use async_trait::async_trait;
 use colored::Colorize;
 use regex::Regex;
 use semver::Version;
@@ -18,10 +19,11 @@ use std::fmt::Write;
 use std::{
     collections::{BTreeMap, HashMap},
     process::Command,
-    sync::OnceLock,
+    sync::{Arc, OnceLock},
 };
 
 use crate::{
+    app::{AppContext, Initializer},
     bgworker,
     cargo_config::CargoConfig,
     config::{self, Config},
@@ -177,6 +179,15 @@ impl std::fmt::Display for Check {
 pub async fn run_all<H: crate::app::Hooks>(
     app_context: &crate::app::AppContext,
     production: bool,
+) -> Result<BTreeMap<Resource, Check>> {
+    let initializers = H::initializers(app_context).await.unwrap_or_default();
+    run_checks(app_context, &initializers, production).await
+}
+
+async fn run_checks(
+    app_context: &AppContext,
+    initializers: &[Box<dyn Initializer>],
+    production: bool,
 ) -> Result<BTreeMap<Resource, Check>> {
     let mut checks = BTreeMap::from(
         #[cfg(feature = "with-db")]
@@ -193,13 +204,11 @@ pub async fn run_all<H: crate::app::Hooks>(
     }
 
     // Add initializer checks
-    if let Ok(initializers) = H::initializers(app_context).await {
-        for initializer in initializers {
-            if let Ok(Some(mut check)) = initializer.check(app_context).await {
-                // Format the message to include "Initializer [name]: " prefix
-                check.message = format!("Initializer {}: {}", initializer.name(), check.message);
-                checks.insert(Resource::Initializer(initializer.name()), check);
-            }
+    for initializer in initializers {
+        if let Ok(Some(mut check)) = initializer.check(app_context).await {
+            // Format the message to include "Initializer [name]: " prefix
+            check.message = format!("Initializer {}: {}", initializer.name(), check.message);
+            checks.insert(Resource::Initializer(initializer.name()), check);
         }
     }
 
@@ -215,6 +224,43 @@ pub async fn run_all<H: crate::app::Hooks>(
     Ok(checks)
 }
 
+/// Runs doctor checks in-process, without shelling out to `cargo loco doctor`.
+#[async_trait]
+pub trait DoctorService: Send + Sync {
+    /// Runs the checks against the running application.
+    ///
+    /// # Errors
+    ///
+    /// When a check cannot be carried out.
+    async fn run(&self, ctx: &AppContext) -> Result<BTreeMap<Resource, Check>>;
+}
+
+/// Doctor service looked up from the shared store by the `/__loco/doctor` route.
+pub type SharedDoctorService = Arc<dyn DoctorService>;
+
+/// [`DoctorService`] running the [`run_all`] checks against initializers the
+/// app already built, so no [`crate::app::Hooks`] method is called again.
+///
+/// Checks run in production mode, so the dependency, `SeaORM` CLI and
+/// crates.io checks that spawn processes or reach the network are skipped.
+pub struct InitializersDoctorService {
+    initializers: Arc<[Box<dyn Initializer>]>,
+}
+
+impl InitializersDoctorService {
+    #[must_use]
+    pub fn new(initializers: Arc<[Box<dyn Initializer>]>) -> Self {
+        Self { initializers }
+    }
+}
+
+#[async_trait]
+impl DoctorService for InitializersDoctorService {
+    async fn run(&self, ctx: &AppContext) -> Result<BTreeMap<Resource, Check>> {
+        run_checks(ctx, &self.initializers, true).await
+    }
+}
+
 /// Checks "blessed" / major dependencies in a Loco app Cargo.toml, and
 /// recommend to update.
 /// Only if a dep exists, we check it against a min version
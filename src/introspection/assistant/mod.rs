@@ -1,4 +1,12 @@
-use std::{collections::BTreeMap, convert::Infallible, fmt::Write as _, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    fmt::Write as _,
+    fs, io,
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+    time::Instant,
+};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -7,7 +15,7 @@ use crate::{
     app::SharedStore,
     doctor::{Check, CheckStatus, Resource},
     introspection::graph::{
-        domain::RouteDescriptor,
+        domain::{node_id, RouteDescriptor},
         service::{GraphDependencies, GraphQueryService, GraphSnapshot},
     },
 };
@@ -36,6 +44,15 @@ impl From<&CheckStatus> for DoctorStatus {
 }
 
 impl DoctorStatus {
+    /// Rank used for threshold filtering: `Passing < Warning < Failing`.
+    const fn severity(self) -> u8 {
+        match self {
+            Self::Passing => 0,
+            Self::Warning => 1,
+            Self::Failing => 2,
+        }
+    }
+
     fn label(self) -> &'static str {
         match self {
             Self::Passing => "passing",
@@ -45,6 +62,18 @@ impl DoctorStatus {
     }
 }
 
+impl PartialOrd for DoctorStatus {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DoctorStatus {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.severity().cmp(&other.severity())
+    }
+}
+
 /// Serializable doctor finding used when invoking the assistant.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DoctorFinding {
@@ -87,15 +116,53 @@ pub fn findings_from_checks(checks: &BTreeMap<Resource, Check>) -> Vec<DoctorFin
         .collect()
 }
 
+/// Like [`findings_from_checks`], keeping only findings at least as severe as
+/// `min` (`Passing < Warning < Failing`).
+#[must_use]
+pub fn findings_from_checks_filtered(
+    checks: &BTreeMap<Resource, Check>,
+    min: DoctorStatus,
+) -> Vec<DoctorFinding> {
+    findings_from_checks(checks)
+        .into_iter()
+        .filter(|finding| finding.status >= min)
+        .collect()
+}
+
+/// Scores findings from 0 (everything failing) to 100 (everything passing).
+///
+/// Each finding carries a penalty of `1` when failing, `1/2` when warning and
+/// `0` when passing; the score is `100 * (1 - penalties / findings)`, rounded
+/// down. No findings scores 100.
+#[must_use]
+pub fn health_score(findings: &[DoctorFinding]) -> u8 {
+    if findings.is_empty() {
+        return 100;
+    }
+    // Penalties are counted in halves to stay in integer arithmetic.
+    let half_penalties: usize = findings
+        .iter()
+        .map(|finding| match finding.status {
+            DoctorStatus::Passing => 0,
+            DoctorStatus::Warning => 1,
+            DoctorStatus::Failing => 2,
+        })
+        .sum();
+    let max_half_penalties = findings.len() * 2;
+    let score = 100 * (max_half_penalties - half_penalties) / max_half_penalties;
+    u8::try_from(score).unwrap_or(100)
+}
+
 /// Role of a conversation turn.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ConversationRole {
     User,
     Assistant,
 }
 
 /// Represents a single entry in the assistant conversation history.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConversationTurn {
     pub role: ConversationRole,
     pub content: String,
@@ -149,6 +216,22 @@ pub struct AssistantSuggestion {
 pub struct AssistantAdvice {
     pub response: String,
     pub suggestions: Vec<AssistantSuggestion>,
+    /// Overall health of the submitted findings, see [`health_score`].
+    pub score: u8,
+    /// Graph snapshot the assistant reasoned about, when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph: Option<GraphSnapshot>,
+    /// Tokens consumed by the provider, when it reports them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
+/// Token counts reported by a provider for a single completion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
 }
 
 /// Provider response payload.
@@ -156,10 +239,12 @@ pub struct AssistantAdvice {
 pub struct AssistantCompletion {
     pub reply: String,
     pub suggestions: Vec<AssistantSuggestion>,
+    /// Token usage for cost tracking. Local clients leave it `None`.
+    pub usage: Option<TokenUsage>,
 }
 
 /// Conversation state stored across invocations.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AssistantState {
     pub history: Vec<ConversationTurn>,
 }
@@ -168,18 +253,52 @@ pub struct AssistantState {
 pub trait ConversationStore: Send + Sync {
     fn load(&self) -> AssistantState;
     fn save(&self, state: AssistantState);
+    /// Discards the stored conversation history.
+    fn clear(&self);
+}
+
+impl AssistantState {
+    /// Drops the oldest turns so at most `max_turns` remain, never starting
+    /// the history with an assistant reply.
+    pub fn trim(&mut self, max_turns: usize) {
+        let excess = self.history.len().saturating_sub(max_turns);
+        self.history.drain(..excess);
+        while self
+            .history
+            .first()
+            .is_some_and(|turn| turn.role == ConversationRole::Assistant)
+        {
+            self.history.remove(0);
+        }
+    }
 }
 
 /// `SharedStore` backed conversation repository.
+///
+/// Keeps at most `max_turns` turns (user prompts and assistant replies),
+/// trimming the oldest on every save.
 #[derive(Clone)]
 pub struct SharedStoreConversationStore {
     shared: Arc<SharedStore>,
+    max_turns: usize,
 }
 
 impl SharedStoreConversationStore {
+    /// Default history cap: the last ten prompt/reply exchanges.
+    pub const DEFAULT_MAX_TURNS: usize = 20;
+
     #[must_use]
     pub fn new(shared: Arc<SharedStore>) -> Self {
-        Self { shared }
+        Self {
+            shared,
+            max_turns: Self::DEFAULT_MAX_TURNS,
+        }
+    }
+
+    #[must_use]
+    pub fn with_max_turns(mut self, max_turns: usize) -> Self {
+        self.max_turns = max_turns;
+        self
     }
 }
 
@@ -190,9 +309,155 @@ impl ConversationStore for SharedStoreConversationStore {
             .unwrap_or_else(AssistantState::default)
     }
 
-    fn save(&self, state: AssistantState) {
+    fn save(&self, mut state: AssistantState) {
+        state.trim(self.max_turns);
         self.shared.insert(state);
     }
+
+    fn clear(&self) {
+        let _ = self.shared.remove::<AssistantState>();
+    }
+}
+
+/// JSON file backed conversation repository that survives restarts.
+///
+/// Saves write a sibling temporary file and rename it over the target, so a
+/// crash never leaves a half-written history. A missing or unreadable file
+/// loads as an empty conversation.
+#[derive(Debug)]
+pub struct FileConversationStore {
+    path: PathBuf,
+    max_turns: usize,
+    lock: Mutex<()>,
+}
+
+impl FileConversationStore {
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_turns: SharedStoreConversationStore::DEFAULT_MAX_TURNS,
+            lock: Mutex::new(()),
+        }
+    }
+
+    #[must_use]
+    pub fn with_max_turns(mut self, max_turns: usize) -> Self {
+        self.max_turns = max_turns;
+        self
+    }
+
+    fn read(&self) -> io::Result<AssistantState> {
+        let content = fs::read_to_string(&self.path)?;
+        serde_json::from_str(&content).map_err(io::Error::from)
+    }
+
+    fn write(&self, state: &AssistantState) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(state)?)?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+impl ConversationStore for FileConversationStore {
+    fn load(&self) -> AssistantState {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        match self.read() {
+            Ok(state) => state,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => AssistantState::default(),
+            Err(err) => {
+                tracing::warn!(
+                    path = %self.path.display(),
+                    error = %err,
+                    "could not read assistant conversation, starting fresh"
+                );
+                AssistantState::default()
+            }
+        }
+    }
+
+    fn save(&self, mut state: AssistantState) {
+        state.trim(self.max_turns);
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Err(err) = self.write(&state) {
+            tracing::error!(
+                path = %self.path.display(),
+                error = %err,
+                "could not persist assistant conversation"
+            );
+        }
+    }
+
+    fn clear(&self) {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        match fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => tracing::error!(
+                path = %self.path.display(),
+                error = %err,
+                "could not clear assistant conversation"
+            ),
+            _ => {}
+        }
+    }
+}
+
+/// Process-wide token bucket guarding the assistant endpoint.
+///
+/// The bucket holds up to `max_per_minute` tokens and refills continuously, so
+/// bursts up to the limit are accepted. A limit of `0` disables throttling.
+#[derive(Debug)]
+pub struct AssistantRateLimiter {
+    max_per_minute: u32,
+    bucket: Mutex<TokenBucket>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl AssistantRateLimiter {
+    #[must_use]
+    pub fn per_minute(max_per_minute: u32) -> Self {
+        Self {
+            max_per_minute,
+            bucket: Mutex::new(TokenBucket {
+                tokens: f64::from(max_per_minute),
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    #[must_use]
+    pub fn max_per_minute(&self) -> u32 {
+        self.max_per_minute
+    }
+
+    /// Takes a token, returning `false` when the bucket is empty.
+    pub fn try_acquire(&self) -> bool {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> bool {
+        if self.max_per_minute == 0 {
+            return true;
+        }
+        let capacity = f64::from(self.max_per_minute);
+        let mut bucket = self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = capacity.min(bucket.tokens + elapsed.as_secs_f64() * capacity / 60.0);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
 }
 
 /// Errors produced by the assistant pipeline.
@@ -211,18 +476,24 @@ pub trait AssistantClient: Send + Sync {
         -> Result<AssistantCompletion, Self::Error>;
 }
 
+/// Assistant client registered in the [`SharedStore`] to replace the
+/// rule-based default, e.g. a remote provider.
+pub type SharedAssistantClient = Arc<dyn AssistantClient<Error = AssistantError>>;
+
 /// Adapter orchestrating prompt creation, conversation management and provider interaction.
-pub struct IntrospectionAssistant<'a, Q, C, S> {
+pub struct IntrospectionAssistant<'a, Q, C: ?Sized, S> {
     app_name: &'a str,
     graph: &'a Q,
     client: &'a C,
     store: &'a S,
+    include_graph: bool,
+    prompt_budget: PromptBudget,
 }
 
 impl<'a, Q, C, S> IntrospectionAssistant<'a, Q, C, S>
 where
     Q: GraphQueryService + Send + Sync,
-    C: AssistantClient,
+    C: AssistantClient + ?Sized,
     S: ConversationStore,
 {
     #[must_use]
@@ -232,17 +503,49 @@ where
             graph,
             client,
             store,
+            include_graph: false,
+            prompt_budget: PromptBudget::default(),
         }
     }
 
+    /// Limits how much of the graph is written into the prompt.
+    #[must_use]
+    pub fn with_prompt_budget(mut self, prompt_budget: PromptBudget) -> Self {
+        self.prompt_budget = prompt_budget;
+        self
+    }
+
+    /// Attaches the graph snapshot sent to the client to the returned advice.
+    #[must_use]
+    pub fn include_graph(mut self, include_graph: bool) -> Self {
+        self.include_graph = include_graph;
+        self
+    }
+
     /// Requests advice from the configured assistant provider.
     pub async fn advise(
         &self,
         doctor_findings: &[DoctorFinding],
+    ) -> Result<AssistantAdvice, AssistantError> {
+        self.advise_scoped(doctor_findings, GraphScope::all()).await
+    }
+
+    /// Requests advice with only the graph sections selected by `scope`
+    /// written into the prompt.
+    pub async fn advise_scoped(
+        &self,
+        doctor_findings: &[DoctorFinding],
+        scope: GraphScope,
     ) -> Result<AssistantAdvice, AssistantError> {
         let snapshot = self.graph.snapshot();
         let mut state = self.store.load();
-        let prompt_text = build_prompt(self.app_name, &snapshot, doctor_findings);
+        let prompt_text = build_prompt(
+            self.app_name,
+            &snapshot,
+            doctor_findings,
+            &self.prompt_budget,
+            scope,
+        );
 
         let request = AssistantRequest {
             app_name: self.app_name.to_string(),
@@ -270,11 +573,82 @@ where
         Ok(AssistantAdvice {
             response: completion.reply,
             suggestions: completion.suggestions,
+            score: health_score(doctor_findings),
+            graph: self.include_graph.then_some(snapshot),
+            usage: completion.usage,
         })
     }
 }
 
-fn build_prompt(app_name: &str, snapshot: &GraphSnapshot, findings: &[DoctorFinding]) -> String {
+/// Caps how many entries of each graph section are written into the prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PromptBudget {
+    pub max_routes: usize,
+    pub max_workers: usize,
+    pub max_jobs: usize,
+    pub max_tasks: usize,
+}
+
+impl Default for PromptBudget {
+    fn default() -> Self {
+        Self {
+            max_routes: 200,
+            max_workers: 100,
+            max_jobs: 100,
+            max_tasks: 100,
+        }
+    }
+}
+
+/// Selects which graph sections are written into the prompt. Unselected
+/// sections are left out entirely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct GraphScope {
+    pub routes: bool,
+    pub workers: bool,
+    pub scheduler_jobs: bool,
+    pub tasks: bool,
+}
+
+impl GraphScope {
+    /// Selects every section.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self {
+            routes: true,
+            workers: true,
+            scheduler_jobs: true,
+            tasks: true,
+        }
+    }
+
+    /// Selects no section, to be combined with struct update syntax, e.g.
+    /// `GraphScope { scheduler_jobs: true, ..GraphScope::none() }`.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            routes: false,
+            workers: false,
+            scheduler_jobs: false,
+            tasks: false,
+        }
+    }
+}
+
+impl Default for GraphScope {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+fn build_prompt(
+    app_name: &str,
+    snapshot: &GraphSnapshot,
+    findings: &[DoctorFinding],
+    budget: &PromptBudget,
+    scope: GraphScope,
+) -> String {
     let mut prompt = String::new();
     writeln!(prompt, "Application: {app_name}").unwrap();
     writeln!(
@@ -285,10 +659,18 @@ fn build_prompt(app_name: &str, snapshot: &GraphSnapshot, findings: &[DoctorFind
     .unwrap();
     prompt.push('\n');
 
-    append_routes(&mut prompt, &snapshot.routes);
-    append_background_workers(&mut prompt, &snapshot.dependencies);
-    append_scheduler_jobs(&mut prompt, &snapshot.dependencies);
-    append_tasks(&mut prompt, &snapshot.dependencies);
+    if scope.routes {
+        append_routes(&mut prompt, &snapshot.routes, budget.max_routes);
+    }
+    if scope.workers {
+        append_background_workers(&mut prompt, &snapshot.dependencies, budget.max_workers);
+    }
+    if scope.scheduler_jobs {
+        append_scheduler_jobs(&mut prompt, &snapshot.dependencies, budget.max_jobs);
+    }
+    if scope.tasks {
+        append_tasks(&mut prompt, &snapshot.dependencies, budget.max_tasks);
+    }
     append_findings(&mut prompt, findings);
 
     prompt.push_str(
@@ -297,7 +679,14 @@ fn build_prompt(app_name: &str, snapshot: &GraphSnapshot, findings: &[DoctorFind
     prompt
 }
 
-fn append_routes(buffer: &mut String, routes: &[RouteDescriptor]) {
+/// Notes how many entries of a section were left out of the prompt.
+fn append_overflow(buffer: &mut String, total: usize, limit: usize) {
+    if total > limit {
+        writeln!(buffer, "- ... and {} more", total - limit).unwrap();
+    }
+}
+
+fn append_routes(buffer: &mut String, routes: &[RouteDescriptor], limit: usize) {
     buffer.push_str("Routes:\n");
     if routes.is_empty() {
         buffer.push_str("- none defined\n");
@@ -305,7 +694,7 @@ fn append_routes(buffer: &mut String, routes: &[RouteDescriptor]) {
         return;
     }
 
-    for route in routes {
+    for route in routes.iter().take(limit) {
         let mut methods = route.methods.clone();
         methods.sort();
         methods.dedup();
@@ -314,12 +703,18 @@ fn append_routes(buffer: &mut String, routes: &[RouteDescriptor]) {
         } else {
             methods.join(", ")
         };
-        writeln!(buffer, "- route:{} (methods: {joined})", route.path).unwrap();
+        writeln!(
+            buffer,
+            "- {} (methods: {joined})",
+            node_id::route(&route.path)
+        )
+        .unwrap();
     }
+    append_overflow(buffer, routes.len(), limit);
     buffer.push('\n');
 }
 
-fn append_background_workers(buffer: &mut String, dependencies: &GraphDependencies) {
+fn append_background_workers(buffer: &mut String, dependencies: &GraphDependencies, limit: usize) {
     buffer.push_str("Background workers:\n");
     if dependencies.background_workers.is_empty() {
         buffer.push_str("- none registered\n");
@@ -327,17 +722,18 @@ fn append_background_workers(buffer: &mut String, dependencies: &GraphDependenci
         return;
     }
 
-    for worker in &dependencies.background_workers {
+    for worker in dependencies.background_workers.iter().take(limit) {
         let queue = worker
             .queue
             .clone()
             .unwrap_or_else(|| "unspecified".to_string());
-        writeln!(buffer, "- worker:{} (queue: {queue})", worker.name).unwrap();
+        writeln!(buffer, "- {} (queue: {queue})", worker.node_id()).unwrap();
     }
+    append_overflow(buffer, dependencies.background_workers.len(), limit);
     buffer.push('\n');
 }
 
-fn append_scheduler_jobs(buffer: &mut String, dependencies: &GraphDependencies) {
+fn append_scheduler_jobs(buffer: &mut String, dependencies: &GraphDependencies, limit: usize) {
     buffer.push_str("Scheduler jobs:\n");
     if dependencies.scheduler_jobs.is_empty() {
         buffer.push_str("- none configured\n");
@@ -345,7 +741,7 @@ fn append_scheduler_jobs(buffer: &mut String, dependencies: &GraphDependencies)
         return;
     }
 
-    for job in &dependencies.scheduler_jobs {
+    for job in dependencies.scheduler_jobs.iter().take(limit) {
         let tags = if job.tags.is_empty() {
             "none".to_string()
         } else {
@@ -353,15 +749,18 @@ fn append_scheduler_jobs(buffer: &mut String, dependencies: &GraphDependencies)
         };
         writeln!(
             buffer,
-            "- scheduler:{} (schedule: {}, command: {}, tags: {tags})",
-            job.name, job.schedule, job.command
+            "- {} (schedule: {}, command: {}, tags: {tags})",
+            node_id::scheduler(&job.name),
+            job.schedule,
+            job.command
         )
         .unwrap();
     }
+    append_overflow(buffer, dependencies.scheduler_jobs.len(), limit);
     buffer.push('\n');
 }
 
-fn append_tasks(buffer: &mut String, dependencies: &GraphDependencies) {
+fn append_tasks(buffer: &mut String, dependencies: &GraphDependencies, limit: usize) {
     buffer.push_str("Tasks:\n");
     if dependencies.tasks.is_empty() {
         buffer.push_str("- none registered\n");
@@ -369,16 +768,17 @@ fn append_tasks(buffer: &mut String, dependencies: &GraphDependencies) {
         return;
     }
 
-    for task in &dependencies.tasks {
+    for task in dependencies.tasks.iter().take(limit) {
         match &task.detail {
             Some(detail) => {
-                writeln!(buffer, "- task:{} (detail: {detail})", task.name).unwrap();
+                writeln!(buffer, "- {} (detail: {detail})", node_id::task(&task.name)).unwrap();
             }
             None => {
-                writeln!(buffer, "- task:{}", task.name).unwrap();
+                writeln!(buffer, "- {}", node_id::task(&task.name)).unwrap();
             }
         }
     }
+    append_overflow(buffer, dependencies.tasks.len(), limit);
     buffer.push('\n');
 }
 
@@ -415,6 +815,25 @@ fn append_findings(buffer: &mut String, findings: &[DoctorFinding]) {
     }
 }
 
+/// Maps a finding to the graph node it implicates.
+///
+/// Queue findings point at the first background worker (preferring one with
+/// a known queue); everything else falls back to the `app:` root node.
+#[must_use]
+pub fn resolve_node_id(finding: &DoctorFinding, graph: &GraphSnapshot, app_name: &str) -> String {
+    let workers = &graph.dependencies.background_workers;
+    if finding.resource == describe_resource(&Resource::Queue) {
+        let worker = workers
+            .iter()
+            .find(|worker| worker.queue.is_some())
+            .or_else(|| workers.first());
+        if let Some(worker) = worker {
+            return worker.node_id();
+        }
+    }
+    node_id::app(app_name)
+}
+
 /// Simple rule-based assistant used as the default implementation when no remote provider is configured.
 #[derive(Debug, Default)]
 pub struct RuleBasedAssistantClient;
@@ -427,12 +846,23 @@ impl AssistantClient for RuleBasedAssistantClient {
         &self,
         request: AssistantRequest,
     ) -> Result<AssistantCompletion, Self::Error> {
-        let mut suggestions: Vec<AssistantSuggestion> = request
+        let mut findings: Vec<&DoctorFinding> = request
             .doctor_findings
             .iter()
             .filter(|finding| finding.status != DoctorStatus::Passing)
+            .collect();
+        // Most severe first, then alphabetical, independent of caller ordering.
+        findings.sort_by(|left, right| {
+            right
+                .status
+                .cmp(&left.status)
+                .then_with(|| left.resource.cmp(&right.resource))
+        });
+
+        let mut suggestions: Vec<AssistantSuggestion> = findings
+            .into_iter()
             .map(|finding| AssistantSuggestion {
-                node_id: format!("app:{}", request.app_name),
+                node_id: resolve_node_id(finding, &request.graph, &request.app_name),
                 summary: format!("Investigate {}", finding.resource),
                 rationale: Some(match &finding.detail {
                     Some(detail) => format!("{} - {detail}", finding.message),
@@ -444,13 +874,13 @@ impl AssistantClient for RuleBasedAssistantClient {
         if suggestions.is_empty() {
             if let Some(route) = request.graph.routes.first() {
                 suggestions.push(AssistantSuggestion {
-                    node_id: format!("route:{}", route.path),
+                    node_id: node_id::route(&route.path),
                     summary: format!("Review {} route for optimisation opportunities", route.path),
                     rationale: Some("No doctor warnings were reported; consider confirming the route behaviour.".to_string()),
                 });
             } else {
                 suggestions.push(AssistantSuggestion {
-                    node_id: format!("app:{}", request.app_name),
+                    node_id: node_id::app(&request.app_name),
                     summary: "System appears healthy. Continue monitoring.".to_string(),
                     rationale: Some("No doctor warnings were reported.".to_string()),
                 });
@@ -460,6 +890,7 @@ impl AssistantClient for RuleBasedAssistantClient {
         Ok(AssistantCompletion {
             reply: "Generated suggestions from local rules.".to_string(),
             suggestions,
+            usage: None,
         })
     }
 }
@@ -468,8 +899,11 @@ impl AssistantClient for RuleBasedAssistantClient {
 mod tests {
     use super::*;
     use crate::introspection::graph::{
-        domain::{BackgroundWorkerDescriptor, SchedulerJobDescriptor, TaskDescriptor},
-        service::GraphHealth,
+        domain::{
+            BackgroundWorkerDescriptor, BackgroundWorkerRepository, GraphBuilder, RoutesRepository,
+            SchedulerJobDescriptor, SchedulerRepository, TaskDescriptor, TaskRepository,
+        },
+        service::{GraphHealth, GRAPH_SCHEMA_VERSION},
     };
     use std::sync::Mutex;
 
@@ -484,6 +918,33 @@ mod tests {
         }
     }
 
+    /// Serves a snapshot back to [`GraphBuilder`] to get the real node ids.
+    struct SnapshotRepositories<'a>(&'a GraphSnapshot);
+
+    impl RoutesRepository for SnapshotRepositories<'_> {
+        fn routes(&self) -> Vec<RouteDescriptor> {
+            self.0.routes.clone()
+        }
+    }
+
+    impl BackgroundWorkerRepository for SnapshotRepositories<'_> {
+        fn workers(&self) -> Vec<BackgroundWorkerDescriptor> {
+            self.0.dependencies.background_workers.clone()
+        }
+    }
+
+    impl SchedulerRepository for SnapshotRepositories<'_> {
+        fn jobs(&self) -> Vec<SchedulerJobDescriptor> {
+            self.0.dependencies.scheduler_jobs.clone()
+        }
+    }
+
+    impl TaskRepository for SnapshotRepositories<'_> {
+        fn tasks(&self) -> Vec<TaskDescriptor> {
+            self.0.dependencies.tasks.clone()
+        }
+    }
+
     #[derive(Clone)]
     struct RecordingClient {
         last: Arc<Mutex<Option<AssistantRequest>>>,
@@ -522,10 +983,14 @@ mod tests {
 
     fn sample_snapshot() -> GraphSnapshot {
         GraphSnapshot {
+            schema_version: GRAPH_SCHEMA_VERSION,
             routes: vec![RouteDescriptor {
                 path: "/health".to_string(),
                 methods: vec!["GET".to_string()],
+                handler: None,
+                params: Vec::new(),
             }],
+            routes_total: None,
             dependencies: GraphDependencies {
                 background_workers: vec![BackgroundWorkerDescriptor {
                     name: "mailer".to_string(),
@@ -538,13 +1003,18 @@ mod tests {
                     run_on_start: false,
                     shell: true,
                     tags: vec!["maintenance".to_string()],
+                    next_run: None,
                 }],
                 tasks: vec![TaskDescriptor {
                     name: "cleanup".to_string(),
                     detail: Some("remove temp files".to_string()),
+                    enqueues: Vec::new(),
                 }],
             },
-            health: GraphHealth { ok: true },
+            health: GraphHealth {
+                ok: true,
+                duplicate_routes: 0,
+            },
         }
     }
 
@@ -557,6 +1027,300 @@ mod tests {
         }
     }
 
+    fn finding_with_status(status: DoctorStatus) -> DoctorFinding {
+        DoctorFinding {
+            status,
+            ..failing_finding()
+        }
+    }
+
+    #[test]
+    fn build_prompt_truncates_sections_over_budget() {
+        let mut snapshot = sample_snapshot();
+        snapshot.routes = (0..5)
+            .map(|index| RouteDescriptor {
+                path: format!("/items/{index}"),
+                methods: vec!["GET".to_string()],
+                handler: None,
+                params: Vec::new(),
+            })
+            .collect();
+        let budget = PromptBudget {
+            max_routes: 2,
+            max_workers: 0,
+            ..PromptBudget::default()
+        };
+
+        let prompt = build_prompt("demo", &snapshot, &[], &budget, GraphScope::all());
+
+        assert!(prompt.contains("route:/items/1"));
+        assert!(!prompt.contains("route:/items/2"));
+        assert!(prompt.contains("- ... and 3 more"));
+        assert!(!prompt.contains("worker:mailer"));
+        assert!(prompt.contains("- ... and 1 more"));
+        assert!(prompt.contains("task:cleanup"));
+    }
+
+    #[test]
+    fn health_score_is_full_when_everything_passes() {
+        assert_eq!(health_score(&[]), 100);
+        assert_eq!(
+            health_score(&[
+                finding_with_status(DoctorStatus::Passing),
+                finding_with_status(DoctorStatus::Passing),
+            ]),
+            100
+        );
+    }
+
+    #[test]
+    fn health_score_is_zero_when_everything_fails() {
+        assert_eq!(
+            health_score(&[
+                finding_with_status(DoctorStatus::Failing),
+                finding_with_status(DoctorStatus::Failing),
+            ]),
+            0
+        );
+    }
+
+    #[test]
+    fn health_score_weights_failures_above_warnings() {
+        let mixed = [
+            finding_with_status(DoctorStatus::Passing),
+            finding_with_status(DoctorStatus::Warning),
+            finding_with_status(DoctorStatus::Failing),
+            finding_with_status(DoctorStatus::Passing),
+        ];
+        assert_eq!(health_score(&mixed), 62);
+
+        let warnings = [
+            finding_with_status(DoctorStatus::Passing),
+            finding_with_status(DoctorStatus::Warning),
+        ];
+        let failures = [
+            finding_with_status(DoctorStatus::Passing),
+            finding_with_status(DoctorStatus::Failing),
+        ];
+        assert_eq!(health_score(&warnings), 75);
+        assert_eq!(health_score(&failures), 50);
+    }
+
+    #[test]
+    fn file_conversation_store_round_trips_history() {
+        let tree = tree_fs::TreeBuilder::default()
+            .create()
+            .expect("temp directory");
+        let path = tree.root.join("assistant").join("history.json");
+        let state = AssistantState {
+            history: vec![
+                ConversationTurn::user("why is the queue failing?"),
+                ConversationTurn::assistant("redis is unreachable"),
+            ],
+        };
+
+        let store = FileConversationStore::new(&path);
+        assert_eq!(store.load(), AssistantState::default());
+        store.save(state.clone());
+
+        let reloaded = FileConversationStore::new(&path);
+        assert_eq!(reloaded.load(), state);
+
+        reloaded.clear();
+        assert_eq!(reloaded.load(), AssistantState::default());
+    }
+
+    #[test]
+    fn queue_findings_resolve_to_worker_nodes() {
+        let snapshot = sample_snapshot();
+
+        assert_eq!(
+            resolve_node_id(&failing_finding(), &snapshot, "demo"),
+            "worker:mailer@redis"
+        );
+
+        let database = DoctorFinding {
+            resource: "Database".to_string(),
+            ..failing_finding()
+        };
+        assert_eq!(resolve_node_id(&database, &snapshot, "demo"), "app:demo");
+
+        let mut without_workers = sample_snapshot();
+        without_workers.dependencies.background_workers.clear();
+        assert_eq!(
+            resolve_node_id(&failing_finding(), &without_workers, "demo"),
+            "app:demo"
+        );
+    }
+
+    #[tokio::test]
+    async fn rule_based_suggestions_reference_real_graph_nodes() {
+        let mut without_routes = sample_snapshot();
+        without_routes.routes.clear();
+        let cases = [
+            (sample_snapshot(), vec![failing_finding()]),
+            (sample_snapshot(), Vec::new()),
+            (without_routes, Vec::new()),
+        ];
+
+        for (snapshot, doctor_findings) in cases {
+            let repositories = SnapshotRepositories(&snapshot);
+            let graph = GraphBuilder::new(
+                "demo",
+                &repositories,
+                &repositories,
+                &repositories,
+                &repositories,
+            )
+            .build();
+            let request = AssistantRequest {
+                app_name: "demo".to_string(),
+                prompt: AssistantPrompt {
+                    system: String::new(),
+                    history: Vec::new(),
+                    user: String::new(),
+                },
+                graph: snapshot.clone(),
+                doctor_findings,
+            };
+
+            let completion = RuleBasedAssistantClient
+                .complete(request)
+                .await
+                .expect("rule based completion");
+
+            for suggestion in completion.suggestions {
+                assert!(
+                    graph.nodes.iter().any(|node| node.id == suggestion.node_id),
+                    "{} is not a graph node",
+                    suggestion.node_id
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn rule_based_suggestions_are_ordered_by_severity_then_resource() {
+        let finding = |resource: &str, status| DoctorFinding {
+            resource: resource.to_string(),
+            status,
+            message: format!("{resource} check"),
+            detail: None,
+        };
+        let request = AssistantRequest {
+            app_name: "demo".to_string(),
+            prompt: AssistantPrompt {
+                system: String::new(),
+                history: Vec::new(),
+                user: String::new(),
+            },
+            graph: sample_snapshot(),
+            doctor_findings: vec![
+                finding("Queue", DoctorStatus::Warning),
+                finding("Database", DoctorStatus::Failing),
+                finding("Dependencies", DoctorStatus::Passing),
+                finding("Assets", DoctorStatus::Warning),
+                finding("Cache", DoctorStatus::Failing),
+            ],
+        };
+
+        let completion = RuleBasedAssistantClient
+            .complete(request)
+            .await
+            .expect("rule-based client is infallible");
+
+        let summaries: Vec<&str> = completion
+            .suggestions
+            .iter()
+            .map(|suggestion| suggestion.summary.as_str())
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "Investigate Cache",
+                "Investigate Database",
+                "Investigate Assets",
+                "Investigate Queue",
+            ]
+        );
+    }
+
+    #[test]
+    fn shared_store_conversation_keeps_most_recent_pairs() {
+        let store =
+            SharedStoreConversationStore::new(Arc::new(SharedStore::default())).with_max_turns(3);
+        let history = (1..=4)
+            .flat_map(|n| {
+                [
+                    ConversationTurn::user(format!("question {n}")),
+                    ConversationTurn::assistant(format!("answer {n}")),
+                ]
+            })
+            .collect();
+
+        store.save(AssistantState { history });
+
+        let history = store.load().history;
+        assert_eq!(
+            history,
+            vec![
+                ConversationTurn::user("question 4"),
+                ConversationTurn::assistant("answer 4"),
+            ]
+        );
+
+        store.clear();
+        assert!(store.load().history.is_empty());
+    }
+
+    #[test]
+    fn filtered_findings_respect_severity_threshold() {
+        let check = |status, message: &str| Check {
+            status,
+            message: message.to_string(),
+            description: None,
+        };
+        let checks = BTreeMap::from([
+            (Resource::Database, check(CheckStatus::NotOk, "db down")),
+            (
+                Resource::Queue,
+                check(CheckStatus::NotConfigure, "no queue"),
+            ),
+            (Resource::Deps, check(CheckStatus::Ok, "deps ok")),
+        ]);
+
+        let failing = findings_from_checks_filtered(&checks, DoctorStatus::Failing);
+        assert_eq!(failing.len(), 1);
+        assert_eq!(failing[0].resource, "Database");
+
+        let warning = findings_from_checks_filtered(&checks, DoctorStatus::Warning);
+        assert_eq!(warning.len(), 2);
+        assert_eq!(
+            findings_from_checks_filtered(&checks, DoctorStatus::Passing),
+            findings_from_checks(&checks)
+        );
+    }
+
+    #[test]
+    fn rate_limiter_rejects_requests_once_bucket_is_empty() {
+        let limiter = AssistantRateLimiter::per_minute(2);
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire_at(start));
+        assert!(limiter.try_acquire_at(start));
+        assert!(!limiter.try_acquire_at(start));
+
+        assert!(limiter.try_acquire_at(start + std::time::Duration::from_secs(30)));
+        assert!(!limiter.try_acquire_at(start + std::time::Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn rate_limiter_with_zero_limit_is_unbounded() {
+        let limiter = AssistantRateLimiter::per_minute(0);
+
+        assert!((0..1_000).all(|_| limiter.try_acquire()));
+    }
+
     #[tokio::test]
     async fn formats_prompt_and_returns_suggestions() {
         let snapshot = sample_snapshot();
@@ -572,6 +1336,7 @@ mod tests {
                 summary: "Verify health endpoint".to_string(),
                 rationale: Some("Ensure monitoring matches requirements.".to_string()),
             }],
+            usage: None,
         };
         let client = RecordingClient::new(completion.clone());
         let assistant = IntrospectionAssistant::new("demo", &graph, &client, &store);
@@ -585,6 +1350,7 @@ mod tests {
         assert!(captured.prompt.user.contains("route:/health"));
         assert!(captured.prompt.user.contains("Doctor findings"));
         assert_eq!(advice.suggestions, completion.suggestions);
+        assert_eq!(advice.graph, None);
 
         let state = shared_store
             .get::<AssistantState>()
@@ -592,6 +1358,107 @@ mod tests {
         assert_eq!(state.history.len(), 2);
     }
 
+    #[tokio::test]
+    async fn scoped_advice_leaves_unselected_sections_out_of_the_prompt() {
+        let graph = StubGraphService {
+            snapshot: sample_snapshot(),
+        };
+        let store = SharedStoreConversationStore::new(Arc::new(SharedStore::default()));
+        let client = RecordingClient::new(AssistantCompletion {
+            reply: "Mock reply".to_string(),
+            suggestions: vec![],
+            usage: None,
+        });
+
+        IntrospectionAssistant::new("demo", &graph, &client, &store)
+            .advise_scoped(
+                &[],
+                GraphScope {
+                    scheduler_jobs: true,
+                    ..GraphScope::none()
+                },
+            )
+            .await
+            .expect("assistant advice");
+
+        let prompt = client.captured().prompt.user;
+        assert!(prompt.contains("scheduler:daily"));
+        assert!(!prompt.contains("Routes:"));
+        assert!(!prompt.contains("route:/health"));
+        assert!(!prompt.contains("Background workers:"));
+        assert!(!prompt.contains("Tasks:"));
+    }
+
+    #[tokio::test]
+    async fn rule_based_client_reports_no_token_usage() {
+        let graph = StubGraphService {
+            snapshot: sample_snapshot(),
+        };
+        let store = SharedStoreConversationStore::new(Arc::new(SharedStore::default()));
+
+        let advice = IntrospectionAssistant::new("demo", &graph, &RuleBasedAssistantClient, &store)
+            .advise(&[failing_finding()])
+            .await
+            .expect("assistant advice");
+
+        assert_eq!(advice.usage, None);
+        assert!(serde_json::to_value(&advice)
+            .expect("serialize advice")
+            .get("usage")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn token_usage_reported_by_the_client_reaches_the_advice() {
+        let graph = StubGraphService {
+            snapshot: sample_snapshot(),
+        };
+        let store = SharedStoreConversationStore::new(Arc::new(SharedStore::default()));
+        let usage = TokenUsage {
+            prompt_tokens: 120,
+            completion_tokens: 30,
+            total_tokens: 150,
+        };
+        let client = RecordingClient::new(AssistantCompletion {
+            reply: "Mock reply".to_string(),
+            suggestions: vec![],
+            usage: Some(usage),
+        });
+
+        let advice = IntrospectionAssistant::new("demo", &graph, &client, &store)
+            .advise(&[])
+            .await
+            .expect("assistant advice");
+
+        assert_eq!(advice.usage, Some(usage));
+        assert_eq!(
+            serde_json::to_value(&advice).expect("serialize advice")["usage"]["total_tokens"],
+            150
+        );
+    }
+
+    #[tokio::test]
+    async fn attaches_graph_snapshot_when_requested() {
+        let snapshot = sample_snapshot();
+        let graph = StubGraphService {
+            snapshot: snapshot.clone(),
+        };
+        let store = SharedStoreConversationStore::new(Arc::new(SharedStore::default()));
+        let client = RecordingClient::new(AssistantCompletion {
+            reply: "Mock reply".to_string(),
+            suggestions: vec![],
+            usage: None,
+        });
+
+        let advice = IntrospectionAssistant::new("demo", &graph, &client, &store)
+            .include_graph(true)
+            .advise(&[])
+            .await
+            .expect("assistant advice");
+
+        assert_eq!(advice.graph, Some(snapshot));
+    }
+
     #[tokio::test]
     async fn reuses_conversation_history_between_calls() {
         let graph = StubGraphService {
@@ -603,6 +1470,7 @@ mod tests {
         let first = RecordingClient::new(AssistantCompletion {
             reply: "First".to_string(),
             suggestions: vec![],
+            usage: None,
         });
         let assistant = IntrospectionAssistant::new("demo", &graph, &first, &store);
         assistant
@@ -613,6 +1481,7 @@ mod tests {
         let second = RecordingClient::new(AssistantCompletion {
             reply: "Second".to_string(),
             suggestions: vec![],
+            usage: None,
         });
         let assistant = IntrospectionAssistant::new("demo", &graph, &second, &store);
         assistant.advise(&[]).await.expect("second call succeeds");
@@ -1,26 +1,81 @@
-use std::sync::Arc;
+use std::{
+    io::{BufRead, BufReader, Read},
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::cli::automation::CargoAutomationCommandBuilder;
 use crate::introspection::cli::{
-    CliAutomationService, CliCommand, CommandExecutor, CommandOutput, EnqueueJobRequest,
-    JobStatusRequest, JobStatusResponse, ListGeneratorsRequest, ListJobsRequest, ListTasksRequest,
-    RunDoctorRequest, RunGeneratorRequest, RunTaskRequest,
+    AsyncCliAutomationService, AsyncCommandExecutor, AutomationProgram, CancelJobRequest,
+    CliAutomationService, CliCommand, CommandEvent, CommandExecutor, CommandOutput,
+    DbStatusRequest, EnqueueJobRequest, JobState, JobStatusRequest, JobStatusResponse,
+    JobStatusWaitRequest, ListGeneratorsRequest, ListJobsRequest, ListTasksRequest,
+    ListWorkersRequest, MigrateDownRequest, MigrateRequest, RunDoctorRequest, RunGeneratorRequest,
+    RunTaskRequest, SeedRequest, StreamingCommandExecutor,
 };
 use crate::{Error, Result};
+use async_trait::async_trait;
 use serde::Deserialize;
 
-pub struct CargoCliAutomationService<E: CommandExecutor> {
+/// Runs `cargo loco` commands through either a blocking [`CommandExecutor`]
+/// or an [`AsyncCommandExecutor`].
+pub struct CargoCliAutomationService<E> {
     executor: Arc<E>,
+    working_dir: Option<PathBuf>,
+    program: AutomationProgram,
 }
 
-impl<E: CommandExecutor> CargoCliAutomationService<E> {
+impl<E> CargoCliAutomationService<E> {
     #[must_use]
     pub fn new(executor: Arc<E>) -> Self {
-        Self { executor }
+        Self {
+            executor,
+            working_dir: None,
+            program: AutomationProgram::default(),
+        }
+    }
+
+    /// Runs commands through `program` instead of `cargo loco` (see
+    /// `introspection.console.program`).
+    #[must_use]
+    pub fn with_program(mut self, program: AutomationProgram) -> Self {
+        self.program = program;
+        self
+    }
+
+    /// Runs every command from `working_dir` (see
+    /// `introspection.console.working_dir`).
+    #[must_use]
+    pub fn with_working_dir(mut self, working_dir: Option<PathBuf>) -> Self {
+        self.working_dir = working_dir;
+        self
+    }
+
+    fn stamp(&self, command: CliCommand) -> CliCommand {
+        let command = self.program.apply(command);
+        match &self.working_dir {
+            Some(working_dir) => command.with_working_dir(Some(working_dir.clone())),
+            None => command,
+        }
     }
+}
 
+impl<E: CommandExecutor> CargoCliAutomationService<E> {
     fn execute(&self, command: CliCommand) -> Result<CommandOutput> {
-        self.executor.execute(&command)
+        self.executor.execute(&self.stamp(command))
+    }
+}
+
+impl<E: AsyncCommandExecutor> CargoCliAutomationService<E> {
+    async fn execute_async(&self, command: CliCommand) -> Result<CommandOutput> {
+        self.executor.execute(&self.stamp(command)).await
     }
 }
 
@@ -51,6 +106,7 @@ impl<E: CommandExecutor> CliAutomationService for CargoCliAutomationService<E> {
     }
 
     fn enqueue_job(&self, request: &EnqueueJobRequest) -> Result<CommandOutput> {
+        validate_payload(request)?;
         let command = CargoAutomationCommandBuilder::enqueue_job(request);
         self.execute(command)
     }
@@ -58,13 +114,142 @@ impl<E: CommandExecutor> CliAutomationService for CargoCliAutomationService<E> {
     fn job_status(&self, request: &JobStatusRequest) -> Result<JobStatusResponse> {
         let command = CargoAutomationCommandBuilder::job_status(request);
         let output = self.execute(command)?;
-        parse_job_status(&output.stdout)
+        parse_job_status(&output)
     }
 
     fn run_doctor(&self, request: &RunDoctorRequest) -> Result<CommandOutput> {
         let command = CargoAutomationCommandBuilder::run_doctor(request);
         self.execute(command)
     }
+
+    fn migrate(&self, request: &MigrateRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::migrate(request);
+        self.execute(command)
+    }
+
+    fn migrate_down(&self, request: &MigrateDownRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::migrate_down(request);
+        self.execute(command)
+    }
+
+    fn db_status(&self, request: &DbStatusRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::db_status(request);
+        self.execute(command)
+    }
+
+    fn seed(&self, request: &SeedRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::seed(request);
+        self.execute(command)
+    }
+
+    fn list_workers(&self, request: &ListWorkersRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::list_workers(request);
+        self.execute(command)
+    }
+
+    fn cancel_job(&self, request: &CancelJobRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::cancel_job(request);
+        self.execute(command)
+    }
+
+    fn job_status_wait(&self, request: &JobStatusWaitRequest) -> Result<JobStatusResponse> {
+        let status_request = request.status_request();
+        let deadline = Instant::now() + request.timeout;
+        loop {
+            let response = CliAutomationService::job_status(self, &status_request)?;
+            if response.is_terminal() || Instant::now() >= deadline {
+                return Ok(response);
+            }
+            thread::sleep(request.effective_poll_interval());
+        }
+    }
+}
+
+#[async_trait]
+impl<E: AsyncCommandExecutor> AsyncCliAutomationService for CargoCliAutomationService<E> {
+    async fn list_generators(&self, request: &ListGeneratorsRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::list_generators(request);
+        self.execute_async(command).await
+    }
+
+    async fn run_generator(&self, request: &RunGeneratorRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::run_generator(request);
+        self.execute_async(command).await
+    }
+
+    async fn list_tasks(&self, request: &ListTasksRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::list_tasks(request);
+        self.execute_async(command).await
+    }
+
+    async fn run_task(&self, request: &RunTaskRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::run_task(request);
+        self.execute_async(command).await
+    }
+
+    async fn list_jobs(&self, request: &ListJobsRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::list_jobs(request);
+        self.execute_async(command).await
+    }
+
+    async fn enqueue_job(&self, request: &EnqueueJobRequest) -> Result<CommandOutput> {
+        validate_payload(request)?;
+        let command = CargoAutomationCommandBuilder::enqueue_job(request);
+        self.execute_async(command).await
+    }
+
+    async fn job_status(&self, request: &JobStatusRequest) -> Result<JobStatusResponse> {
+        let command = CargoAutomationCommandBuilder::job_status(request);
+        let output = self.execute_async(command).await?;
+        parse_job_status(&output)
+    }
+
+    async fn run_doctor(&self, request: &RunDoctorRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::run_doctor(request);
+        self.execute_async(command).await
+    }
+
+    async fn migrate(&self, request: &MigrateRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::migrate(request);
+        self.execute_async(command).await
+    }
+
+    async fn migrate_down(&self, request: &MigrateDownRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::migrate_down(request);
+        self.execute_async(command).await
+    }
+
+    async fn db_status(&self, request: &DbStatusRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::db_status(request);
+        self.execute_async(command).await
+    }
+
+    async fn seed(&self, request: &SeedRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::seed(request);
+        self.execute_async(command).await
+    }
+
+    async fn list_workers(&self, request: &ListWorkersRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::list_workers(request);
+        self.execute_async(command).await
+    }
+
+    async fn cancel_job(&self, request: &CancelJobRequest) -> Result<CommandOutput> {
+        let command = CargoAutomationCommandBuilder::cancel_job(request);
+        self.execute_async(command).await
+    }
+
+    async fn job_status_wait(&self, request: &JobStatusWaitRequest) -> Result<JobStatusResponse> {
+        let status_request = request.status_request();
+        let deadline = Instant::now() + request.timeout;
+        loop {
+            let response = AsyncCliAutomationService::job_status(self, &status_request).await?;
+            if response.is_terminal() || Instant::now() >= deadline {
+                return Ok(response);
+            }
+            tokio::time::sleep(request.effective_poll_interval()).await;
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -88,12 +273,31 @@ struct JobResultPayload {
     stderr: String,
 }
 
-fn parse_job_status(stdout: &str) -> Result<JobStatusResponse> {
-    let payload: JobStatusPayload = serde_json::from_str(stdout)
+/// Rejects a job payload that is not JSON before the CLI is spawned, so the
+/// caller gets a `400` instead of a failure deep inside `cargo loco`.
+fn validate_payload(request: &EnqueueJobRequest) -> Result<()> {
+    match request.payload.as_deref().map(str::trim) {
+        Some(payload) if !payload.is_empty() => serde_json::from_str::<serde_json::Value>(payload)
+            .map(|_| ())
+            .map_err(|err| Error::BadRequest(format!("job payload is not valid JSON: {err}"))),
+        _ => Ok(()),
+    }
+}
+
+fn parse_job_status(output: &CommandOutput) -> Result<JobStatusResponse> {
+    if output.status != 0 {
+        return Err(Error::Message(format!(
+            "job status command failed with status {}: {}",
+            output.status,
+            output.stderr.trim()
+        )));
+    }
+
+    let payload: JobStatusPayload = serde_json::from_str(&output.stdout)
         .map_err(|err| Error::Message(format!("failed to parse job status response: {err}")))?;
     Ok(JobStatusResponse {
         id: payload.id,
-        state: payload.state,
+        state: JobState::from(payload.state),
         result: payload
             .result
             .map(|result| CommandOutput::new(result.status, result.stdout, result.stderr)),
@@ -102,32 +306,207 @@ fn parse_job_status(stdout: &str) -> Result<JobStatusResponse> {
     })
 }
 
+/// Runs commands with [`std::process::Command`].
+///
+/// A non-zero exit status is reported as an error unless the command sets
+/// [`CliCommand::capture_nonzero`], in which case the captured status, stdout
+/// and stderr are returned.
 #[derive(Default)]
 pub struct StdCommandExecutor;
 
+impl StdCommandExecutor {
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    fn output_with_timeout(mut process: Command, timeout: Duration) -> Result<Output> {
+        let mut child = process
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdout = child.stdout.take().map(spawn_reader);
+        let stderr = child.stderr.take().map(spawn_reader);
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Error::Message("command timed out".to_string()));
+            }
+            thread::sleep(Self::POLL_INTERVAL);
+        };
+
+        Ok(Output {
+            status,
+            stdout: join_reader(stdout),
+            stderr: join_reader(stderr),
+        })
+    }
+}
+
 impl CommandExecutor for StdCommandExecutor {
     fn execute(&self, command: &CliCommand) -> Result<CommandOutput> {
-        use std::process::Command;
+        let mut process = Command::new(&command.program);
+        process.args(&command.args).envs(&command.env);
+        if let Some(working_dir) = resolve_working_dir(command)? {
+            process.current_dir(working_dir);
+        }
+
+        let output = match command.timeout {
+            Some(timeout) => Self::output_with_timeout(process, timeout)?,
+            None => process.output()?,
+        };
+        into_command_output(command, &output)
+    }
+}
 
-        let output = Command::new(&command.program)
+impl StreamingCommandExecutor for StdCommandExecutor {
+    fn execute_streaming(
+        &self,
+        command: &CliCommand,
+        cancel: &AtomicBool,
+        on_event: &mut dyn FnMut(CommandEvent),
+    ) -> Result<i32> {
+        let mut process = Command::new(&command.program);
+        process
             .args(&command.args)
-            .output()?;
-        let status_code = output.status.code().unwrap_or(-1);
-        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
-        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
-
-        if !output.status.success() {
-            return Err(Error::Message(format!(
-                "command `{}` failed with status {status_code}: {}",
-                command,
-                stderr.trim()
-            )));
+            .envs(&command.env)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(working_dir) = resolve_working_dir(command)? {
+            process.current_dir(working_dir);
         }
 
-        Ok(CommandOutput::new(status_code, stdout, stderr))
+        let mut child = process.spawn()?;
+        let (sender, receiver) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            spawn_line_reader(stdout, sender.clone(), CommandEvent::Stdout);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_line_reader(stderr, sender.clone(), CommandEvent::Stderr);
+        }
+        drop(sender);
+
+        let deadline = command.timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            match receiver.recv_timeout(Self::POLL_INTERVAL) {
+                Ok(event) => on_event(event),
+                Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Error::Message("command timed out".to_string()));
+            }
+            if cancel.load(Ordering::Relaxed) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Error::Message("command cancelled".to_string()));
+            }
+        }
+
+        Ok(child.wait()?.code().unwrap_or(-1))
     }
 }
 
+/// Forwards a child pipe line by line until it closes.
+fn spawn_line_reader<R: Read + Send + 'static>(
+    reader: R,
+    sender: mpsc::Sender<CommandEvent>,
+    wrap: fn(String) -> CommandEvent,
+) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        let mut line = Vec::new();
+        while matches!(reader.read_until(b'\n', &mut line), Ok(read) if read > 0) {
+            let text = String::from_utf8_lossy(&line)
+                .trim_end_matches(['\n', '\r'])
+                .to_string();
+            if sender.send(wrap(text)).is_err() {
+                break;
+            }
+            line.clear();
+        }
+    });
+}
+
+/// Drains a child pipe on a separate thread so a chatty process cannot block
+/// on a full pipe while we wait for it.
+fn spawn_reader<R: Read + Send + 'static>(mut reader: R) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = reader.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+fn join_reader(handle: Option<thread::JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    handle
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default()
+}
+
+/// Runs commands with [`tokio::process::Command`] so callers can `.await`
+/// them without stalling the runtime.
+///
+/// Honours [`CliCommand::capture_nonzero`] the same way as
+/// [`StdCommandExecutor`].
+#[derive(Default)]
+pub struct TokioCommandExecutor;
+
+#[async_trait]
+impl AsyncCommandExecutor for TokioCommandExecutor {
+    async fn execute(&self, command: &CliCommand) -> Result<CommandOutput> {
+        let mut process = tokio::process::Command::new(&command.program);
+        process
+            .args(&command.args)
+            .envs(&command.env)
+            .kill_on_drop(true);
+        if let Some(working_dir) = resolve_working_dir(command)? {
+            process.current_dir(working_dir);
+        }
+
+        let output = match command.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, process.output())
+                .await
+                .map_err(|_| Error::Message("command timed out".to_string()))??,
+            None => process.output().await?,
+        };
+        into_command_output(command, &output)
+    }
+}
+
+/// Validates the command's working directory up front so a typo yields a clear
+/// message instead of a generic "No such file or directory".
+fn resolve_working_dir(command: &CliCommand) -> Result<Option<&PathBuf>> {
+    match &command.working_dir {
+        Some(working_dir) if !working_dir.is_dir() => Err(Error::Message(format!(
+            "working directory `{}` for command `{command}` does not exist",
+            working_dir.display()
+        ))),
+        working_dir => Ok(working_dir.as_ref()),
+    }
+}
+
+fn into_command_output(command: &CliCommand, output: &Output) -> Result<CommandOutput> {
+    let status_code = output.status.code().unwrap_or(-1);
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+
+    if !command.capture_nonzero && !output.status.success() {
+        return Err(Error::Message(format!(
+            "command `{}` failed with status {status_code}: {}",
+            command,
+            stderr.trim()
+        )));
+    }
+
+    Ok(CommandOutput::new(status_code, stdout, stderr))
+}
+
 impl CargoCliAutomationService<StdCommandExecutor> {
     #[must_use]
     pub fn system() -> Self {
@@ -140,3 +519,10 @@ impl Default for CargoCliAutomationService<StdCommandExecutor> {
         Self::system()
     }
 }
+
+impl CargoCliAutomationService<TokioCommandExecutor> {
+    #[must_use]
+    pub fn tokio() -> Self {
+        Self::new(Arc::new(TokioCommandExecutor))
+    }
+}
@@ -1,4 +1,15 @@
-use std::fmt;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use regex::Regex;
+use serde::{Serialize, Serializer};
 
 use crate::Result;
 
@@ -9,6 +20,14 @@ pub mod adapters;
 pub struct CliCommand {
     pub program: String,
     pub args: Vec<String>,
+    /// Maximum time the command may run before it is killed.
+    pub timeout: Option<Duration>,
+    /// Extra environment variables, merged over the inherited environment.
+    pub env: BTreeMap<String, String>,
+    /// Directory to run the command from instead of the process cwd.
+    pub working_dir: Option<PathBuf>,
+    /// Report a non-zero exit as a [`CommandOutput`] instead of an error.
+    pub capture_nonzero: bool,
 }
 
 impl CliCommand {
@@ -17,17 +36,157 @@ impl CliCommand {
         Self {
             program: program.into(),
             args: args.into(),
+            timeout: None,
+            env: BTreeMap::new(),
+            working_dir: None,
+            capture_nonzero: false,
         }
     }
+
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Adds environment variables on top of the inherited process environment.
+    #[must_use]
+    pub fn with_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env.extend(env);
+        self
+    }
+
+    #[must_use]
+    pub fn with_working_dir(mut self, working_dir: Option<PathBuf>) -> Self {
+        self.working_dir = working_dir;
+        self
+    }
+
+    #[must_use]
+    pub fn with_capture_nonzero(mut self, capture_nonzero: bool) -> Self {
+        self.capture_nonzero = capture_nonzero;
+        self
+    }
+
+    /// Renders the command as a line that can be pasted into a POSIX shell,
+    /// single-quoting any argument containing whitespace or metacharacters.
+    #[must_use]
+    pub fn to_shell_string(&self) -> String {
+        std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(|part| shell_quote(part))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn shell_quote(value: &str) -> Cow<'_, str> {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c);
+    if !value.is_empty() && value.chars().all(is_safe) {
+        Cow::Borrowed(value)
+    } else {
+        Cow::Owned(format!("'{}'", value.replace('\'', r"'\''")))
+    }
 }
 
 impl fmt::Display for CliCommand {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.program)?;
-        for arg in &self.args {
-            write!(f, " {}", arg)?;
+        f.write_str(&self.to_shell_string())
+    }
+}
+
+/// Program that launches automation commands.
+///
+/// Defaults to `cargo loco <command>`. A prebuilt app binary or wrapper script
+/// usually takes the command directly, as `<program> <command>`, in which case
+/// the `loco` subcommand is dropped.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutomationProgram {
+    pub program: String,
+    /// Keep the leading `loco` subcommand, as `cargo loco` needs.
+    pub loco_subcommand: bool,
+    /// Passed to `cargo run` as `--manifest-path <path>`.
+    pub manifest_path: Option<PathBuf>,
+    /// Passed to `cargo run` as `-p <package>`, to pick a workspace member.
+    pub package: Option<String>,
+}
+
+impl Default for AutomationProgram {
+    fn default() -> Self {
+        Self::cargo()
+    }
+}
+
+impl AutomationProgram {
+    /// `cargo loco <command>`.
+    #[must_use]
+    pub fn cargo() -> Self {
+        Self {
+            program: "cargo".to_string(),
+            loco_subcommand: true,
+            manifest_path: None,
+            package: None,
+        }
+    }
+
+    /// `<program> <command>`, for binaries that expose the loco CLI directly.
+    #[must_use]
+    pub fn binary(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            loco_subcommand: false,
+            manifest_path: None,
+            package: None,
+        }
+    }
+
+    /// Targets the crate at `manifest_path`, e.g. `apps/backend/Cargo.toml`.
+    #[must_use]
+    pub fn with_manifest_path(mut self, manifest_path: Option<PathBuf>) -> Self {
+        self.manifest_path = manifest_path;
+        self
+    }
+
+    /// Targets a workspace member by package name.
+    #[must_use]
+    pub fn with_package(mut self, package: Option<String>) -> Self {
+        self.package = package;
+        self
+    }
+
+    /// Rewrites a command built for `cargo loco` to run through this program.
+    ///
+    /// Manifest and package selection only apply while the `loco` subcommand
+    /// is kept. Cargo does not accept them ahead of an alias, so the `loco`
+    /// alias (`run --`) is expanded to
+    /// `cargo run --manifest-path <path> -p <package> -- <command>`.
+    #[must_use]
+    pub fn apply(&self, mut command: CliCommand) -> CliCommand {
+        command.program.clone_from(&self.program);
+        if !command.args.first().is_some_and(|arg| arg == "loco") {
+            return command;
+        }
+        if !self.loco_subcommand {
+            command.args.remove(0);
+            return command;
+        }
+
+        if self.manifest_path.is_none() && self.package.is_none() {
+            return command;
+        }
+
+        let mut selection = vec!["run".to_string()];
+        if let Some(manifest_path) = &self.manifest_path {
+            selection.push("--manifest-path".to_string());
+            selection.push(manifest_path.display().to_string());
         }
-        Ok(())
+        if let Some(package) = &self.package {
+            selection.push("-p".to_string());
+            selection.push(package.clone());
+        }
+        selection.push("--".to_string());
+        command.args.splice(0..1, selection);
+        command
     }
 }
 
@@ -50,11 +209,145 @@ impl CommandOutput {
     }
 }
 
+/// Placeholder written over redacted secrets.
+pub const REDACTED: &str = "***";
+
+/// Masks secrets in captured stdout and stderr.
+///
+/// When a pattern has a capture group only the first group is masked, so
+/// `password=([^\s&;]+)` keeps the `password=` prefix; otherwise the whole
+/// match is replaced with [`REDACTED`].
+#[must_use]
+pub fn redact(output: CommandOutput, patterns: &[Regex]) -> CommandOutput {
+    CommandOutput {
+        status: output.status,
+        stdout: redact_text(&output.stdout, patterns),
+        stderr: redact_text(&output.stderr, patterns),
+    }
+}
+
+/// Applies [`redact`] to a single piece of text.
+#[must_use]
+pub fn redact_text(text: &str, patterns: &[Regex]) -> String {
+    patterns.iter().fold(text.to_string(), |text, pattern| {
+        pattern
+            .replace_all(&text, |captures: &regex::Captures<'_>| {
+                let Some(secret) = captures.get(1) else {
+                    return REDACTED.to_string();
+                };
+                let whole = &captures[0];
+                let offset = captures.get(0).map_or(0, |whole| whole.start());
+                format!(
+                    "{}{REDACTED}{}",
+                    &whole[..secret.start() - offset],
+                    &whole[secret.end() - offset..]
+                )
+            })
+            .into_owned()
+    })
+}
+
+/// A command run from the console, as reported by `/__loco/cli/history`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct CommandHistoryEntry {
+    /// RFC 3339 time at which the command finished.
+    pub timestamp: String,
+    /// Shell rendering of the command, with secrets redacted.
+    pub command: String,
+    /// Exit status, absent when the command could not be run to completion.
+    pub status: Option<i32>,
+    /// Why the command failed or timed out, with secrets redacted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub environment: Option<String>,
+}
+
+/// Bounded, most-recent-last log of console commands kept in the shared
+/// store. Once full, recording an entry evicts the oldest one.
+#[derive(Debug)]
+pub struct CommandHistory {
+    capacity: usize,
+    entries: Mutex<VecDeque<CommandHistoryEntry>>,
+}
+
+impl CommandHistory {
+    /// Default number of entries kept.
+    pub const DEFAULT_CAPACITY: usize = 100;
+
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn record(&self, entry: CommandHistoryEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Recorded entries, oldest first.
+    #[must_use]
+    pub fn entries(&self) -> Vec<CommandHistoryEntry> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for CommandHistory {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
 /// Executes operating system commands.
+///
+/// Implementations must kill the process and return
+/// `Error::Message("command timed out")` once [`CliCommand::timeout`] elapses.
 pub trait CommandExecutor: Send + Sync {
     fn execute(&self, command: &CliCommand) -> Result<CommandOutput>;
 }
 
+/// Executes operating system commands without blocking the async runtime.
+#[async_trait]
+pub trait AsyncCommandExecutor: Send + Sync {
+    async fn execute(&self, command: &CliCommand) -> Result<CommandOutput>;
+}
+
+/// Output chunk emitted while a command is still running.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CommandEvent {
+    Stdout(String),
+    Stderr(String),
+}
+
+/// Executes commands while forwarding their output as it is produced.
+///
+/// Implementations call `on_event` once per chunk (typically one line) and
+/// return the exit status; a non-zero status is not treated as an error.
+/// [`CliCommand::timeout`] is honoured the same way as by [`CommandExecutor`],
+/// and the command is killed as soon as `cancel` is set, e.g. because the
+/// client reading its output went away.
+pub trait StreamingCommandExecutor: Send + Sync {
+    fn execute_streaming(
+        &self,
+        command: &CliCommand,
+        cancel: &AtomicBool,
+        on_event: &mut dyn FnMut(CommandEvent),
+    ) -> Result<i32>;
+}
+
 /// Defines the safe subset of `cargo loco` commands exposed for automation.
 pub trait CliAutomationService: Send + Sync {
     fn list_generators(&self, request: &ListGeneratorsRequest) -> Result<CommandOutput>;
@@ -65,11 +358,145 @@ pub trait CliAutomationService: Send + Sync {
     fn enqueue_job(&self, request: &EnqueueJobRequest) -> Result<CommandOutput>;
     fn job_status(&self, request: &JobStatusRequest) -> Result<JobStatusResponse>;
     fn run_doctor(&self, request: &RunDoctorRequest) -> Result<CommandOutput>;
+    fn migrate(&self, request: &MigrateRequest) -> Result<CommandOutput>;
+    fn migrate_down(&self, request: &MigrateDownRequest) -> Result<CommandOutput>;
+    fn db_status(&self, request: &DbStatusRequest) -> Result<CommandOutput>;
+    fn seed(&self, request: &SeedRequest) -> Result<CommandOutput>;
+    fn list_workers(&self, request: &ListWorkersRequest) -> Result<CommandOutput>;
+    fn cancel_job(&self, request: &CancelJobRequest) -> Result<CommandOutput>;
+    fn job_status_wait(&self, request: &JobStatusWaitRequest) -> Result<JobStatusResponse>;
+}
+
+/// Async counterpart of [`CliAutomationService`] used by the HTTP console.
+#[async_trait]
+pub trait AsyncCliAutomationService: Send + Sync {
+    async fn list_generators(&self, request: &ListGeneratorsRequest) -> Result<CommandOutput>;
+    async fn run_generator(&self, request: &RunGeneratorRequest) -> Result<CommandOutput>;
+    async fn list_tasks(&self, request: &ListTasksRequest) -> Result<CommandOutput>;
+    async fn run_task(&self, request: &RunTaskRequest) -> Result<CommandOutput>;
+    async fn list_jobs(&self, request: &ListJobsRequest) -> Result<CommandOutput>;
+    async fn enqueue_job(&self, request: &EnqueueJobRequest) -> Result<CommandOutput>;
+    async fn job_status(&self, request: &JobStatusRequest) -> Result<JobStatusResponse>;
+    async fn run_doctor(&self, request: &RunDoctorRequest) -> Result<CommandOutput>;
+    async fn migrate(&self, request: &MigrateRequest) -> Result<CommandOutput>;
+    async fn migrate_down(&self, request: &MigrateDownRequest) -> Result<CommandOutput>;
+    async fn db_status(&self, request: &DbStatusRequest) -> Result<CommandOutput>;
+    async fn seed(&self, request: &SeedRequest) -> Result<CommandOutput>;
+    async fn list_workers(&self, request: &ListWorkersRequest) -> Result<CommandOutput>;
+    async fn cancel_job(&self, request: &CancelJobRequest) -> Result<CommandOutput>;
+    async fn job_status_wait(&self, request: &JobStatusWaitRequest) -> Result<JobStatusResponse>;
+}
+
+/// Adapts a blocking [`CliAutomationService`] to [`AsyncCliAutomationService`]
+/// by running every call on Tokio's blocking thread pool.
+#[derive(Clone)]
+pub struct BlockingCliAutomationService {
+    inner: Arc<dyn CliAutomationService>,
+}
+
+impl BlockingCliAutomationService {
+    #[must_use]
+    pub fn new(inner: Arc<dyn CliAutomationService>) -> Self {
+        Self { inner }
+    }
+
+    async fn run<T, F>(&self, call: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn CliAutomationService) -> Result<T> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        tokio::task::spawn_blocking(move || call(inner.as_ref())).await?
+    }
+}
+
+#[async_trait]
+impl AsyncCliAutomationService for BlockingCliAutomationService {
+    async fn list_generators(&self, request: &ListGeneratorsRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.list_generators(&request))
+            .await
+    }
+
+    async fn run_generator(&self, request: &RunGeneratorRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.run_generator(&request))
+            .await
+    }
+
+    async fn list_tasks(&self, request: &ListTasksRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.list_tasks(&request)).await
+    }
+
+    async fn run_task(&self, request: &RunTaskRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.run_task(&request)).await
+    }
+
+    async fn list_jobs(&self, request: &ListJobsRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.list_jobs(&request)).await
+    }
+
+    async fn enqueue_job(&self, request: &EnqueueJobRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.enqueue_job(&request)).await
+    }
+
+    async fn job_status(&self, request: &JobStatusRequest) -> Result<JobStatusResponse> {
+        let request = request.clone();
+        self.run(move |service| service.job_status(&request)).await
+    }
+
+    async fn run_doctor(&self, request: &RunDoctorRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.run_doctor(&request)).await
+    }
+
+    async fn migrate(&self, request: &MigrateRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.migrate(&request)).await
+    }
+
+    async fn migrate_down(&self, request: &MigrateDownRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.migrate_down(&request))
+            .await
+    }
+
+    async fn db_status(&self, request: &DbStatusRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.db_status(&request)).await
+    }
+
+    async fn seed(&self, request: &SeedRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.seed(&request)).await
+    }
+
+    async fn list_workers(&self, request: &ListWorkersRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.list_workers(&request))
+            .await
+    }
+
+    async fn cancel_job(&self, request: &CancelJobRequest) -> Result<CommandOutput> {
+        let request = request.clone();
+        self.run(move |service| service.cancel_job(&request)).await
+    }
+
+    async fn job_status_wait(&self, request: &JobStatusWaitRequest) -> Result<JobStatusResponse> {
+        let request = request.clone();
+        self.run(move |service| service.job_status_wait(&request))
+            .await
+    }
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct ListGeneratorsRequest {
     pub environment: Option<String>,
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -77,11 +504,17 @@ pub struct RunGeneratorRequest {
     pub environment: Option<String>,
     pub generator: String,
     pub arguments: Vec<String>,
+    pub timeout: Option<Duration>,
+    pub env: BTreeMap<String, String>,
+    /// Return a non-zero exit as output instead of an error, so the caller
+    /// sees the tool's own error text.
+    pub capture_nonzero: bool,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct ListTasksRequest {
     pub environment: Option<String>,
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -89,6 +522,11 @@ pub struct RunTaskRequest {
     pub environment: Option<String>,
     pub task: String,
     pub arguments: Vec<String>,
+    pub timeout: Option<Duration>,
+    pub env: BTreeMap<String, String>,
+    /// Return a non-zero exit as output instead of an error, so the caller
+    /// sees the tool's own error text.
+    pub capture_nonzero: bool,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -97,6 +535,7 @@ pub struct ListJobsRequest {
     pub config_path: Option<String>,
     pub name: Option<String>,
     pub tag: Option<String>,
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -108,23 +547,156 @@ pub struct EnqueueJobRequest {
     pub tags: Vec<String>,
     pub payload: Option<String>,
     pub arguments: Vec<String>,
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct JobStatusRequest {
     pub environment: Option<String>,
     pub job_id: String,
+    pub env: BTreeMap<String, String>,
+}
+
+/// Polls a job's status until it reaches a terminal state.
+///
+/// A zero `timeout` checks the status once.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobStatusWaitRequest {
+    pub environment: Option<String>,
+    pub job_id: String,
+    /// Maximum time to wait for a terminal state before returning the latest status.
+    pub timeout: Duration,
+    /// Delay between consecutive status checks, raised to
+    /// [`Self::MIN_POLL_INTERVAL`] when shorter.
+    pub poll_interval: Duration,
+    pub env: BTreeMap<String, String>,
+}
+
+impl Default for JobStatusWaitRequest {
+    fn default() -> Self {
+        Self {
+            environment: None,
+            job_id: String::new(),
+            timeout: Duration::ZERO,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            env: BTreeMap::new(),
+        }
+    }
+}
+
+impl JobStatusWaitRequest {
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+    /// Every poll spawns the CLI, so shorter intervals are not honoured.
+    pub const MIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Delay actually slept between status checks.
+    #[must_use]
+    pub fn effective_poll_interval(&self) -> Duration {
+        self.poll_interval.max(Self::MIN_POLL_INTERVAL)
+    }
+
+    /// Status request issued on every poll.
+    #[must_use]
+    pub fn status_request(&self) -> JobStatusRequest {
+        JobStatusRequest {
+            environment: self.environment.clone(),
+            job_id: self.job_id.clone(),
+            env: self.env.clone(),
+        }
+    }
+}
+
+/// Lifecycle state reported by `cargo loco jobs status`.
+///
+/// States the CLI may add later are kept verbatim in [`JobState::Unknown`], so
+/// displaying or serializing a state always yields the reported string.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JobState {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+    Unknown(String),
+}
+
+impl JobState {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+            Self::Unknown(state) => state,
+        }
+    }
+
+    /// Indicates whether the job has stopped (`completed`, `failed` or `cancelled`).
+    #[must_use]
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+impl Default for JobState {
+    fn default() -> Self {
+        Self::Unknown(String::new())
+    }
+}
+
+impl From<&str> for JobState {
+    fn from(state: &str) -> Self {
+        match state {
+            "queued" => Self::Queued,
+            "running" => Self::Running,
+            "completed" => Self::Completed,
+            "failed" => Self::Failed,
+            "cancelled" => Self::Cancelled,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for JobState {
+    fn from(state: String) -> Self {
+        match Self::from(state.as_str()) {
+            Self::Unknown(_) => Self::Unknown(state),
+            known => known,
+        }
+    }
+}
+
+impl fmt::Display for JobState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for JobState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct JobStatusResponse {
     pub id: String,
-    pub state: String,
+    pub state: JobState,
     pub result: Option<CommandOutput>,
     pub error: Option<String>,
     pub updated_at: Option<String>,
 }
 
+impl JobStatusResponse {
+    /// Indicates whether the job has stopped (see [`JobState::is_terminal`]).
+    #[must_use]
+    pub fn is_terminal(&self) -> bool {
+        self.state.is_terminal()
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct RunDoctorRequest {
     pub environment: Option<String>,
@@ -132,4 +704,59 @@ pub struct RunDoctorRequest {
     pub config: bool,
     pub graph: bool,
     pub assistant: bool,
+    pub timeout: Option<Duration>,
+    pub env: BTreeMap<String, String>,
+    /// Return a non-zero exit as output instead of an error, so the caller
+    /// sees the tool's own error text.
+    pub capture_nonzero: bool,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MigrateRequest {
+    pub environment: Option<String>,
+    pub timeout: Option<Duration>,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MigrateDownRequest {
+    pub environment: Option<String>,
+    /// Number of migrations to roll back; the CLI defaults to one.
+    pub steps: Option<u32>,
+    pub timeout: Option<Duration>,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DbStatusRequest {
+    pub environment: Option<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SeedRequest {
+    pub environment: Option<String>,
+    /// Truncates the seeded tables before loading the fixtures.
+    pub reset: bool,
+    /// Directory to load seed fixtures from instead of the default.
+    pub from: Option<String>,
+    pub timeout: Option<Duration>,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ListWorkersRequest {
+    pub environment: Option<String>,
+    /// Restricts the listing to workers consuming this queue.
+    pub queue: Option<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CancelJobRequest {
+    pub environment: Option<String>,
+    /// Job name passed to `jobs cancel --name`; every queued job with that
+    /// name is cancelled.
+    pub name: String,
+    pub env: BTreeMap<String, String>,
 }
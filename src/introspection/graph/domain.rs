@@ -1,7 +1,46 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use serde::Serialize;
 
+use crate::scheduler;
+
+/// Node identifier scheme shared by the graph builder and its consumers, so
+/// ids referenced elsewhere (e.g. assistant suggestions) match real nodes.
+pub mod node_id {
+    /// Id of the application root node.
+    #[must_use]
+    pub fn app(name: &str) -> String {
+        format!("app:{name}")
+    }
+
+    /// Id of an HTTP route node.
+    #[must_use]
+    pub fn route(path: &str) -> String {
+        format!("route:{path}")
+    }
+
+    /// Id of a background worker node, suffixed with `@<queue>` when known.
+    #[must_use]
+    pub fn worker(name: &str, queue: Option<&str>) -> String {
+        match queue {
+            Some(queue) => format!("worker:{name}@{queue}"),
+            None => format!("worker:{name}"),
+        }
+    }
+
+    /// Id of a scheduler job node.
+    #[must_use]
+    pub fn scheduler(name: &str) -> String {
+        format!("scheduler:{name}")
+    }
+
+    /// Id of a task node.
+    #[must_use]
+    pub fn task(name: &str) -> String {
+        format!("task:{name}")
+    }
+}
+
 /// Represents the full application graph composed of nodes and edges.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ApplicationGraph {
@@ -25,6 +64,10 @@ pub enum ComponentKind {
     HttpRoute {
         path: String,
         methods: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        handler: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        params: Vec<String>,
     },
     BackgroundWorker {
         name: String,
@@ -37,6 +80,8 @@ pub enum ComponentKind {
         run_on_start: bool,
         shell: bool,
         tags: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        next_run: Option<String>,
     },
     Task {
         name: String,
@@ -53,17 +98,37 @@ pub struct GraphEdge {
 }
 
 /// Describes the semantics of an edge.
+///
+/// Edges between the same nodes are ordered by discriminant, so new kinds
+/// take the next ordinal and are appended to [`EdgeKind::ALL`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[repr(u8)]
 pub enum EdgeKind {
-    Contains,
-    Triggers,
+    Contains = 0,
+    Triggers = 1,
 }
 
 impl EdgeKind {
-    fn sort_key(self) -> u8 {
+    /// Every edge kind, in sort order.
+    pub const ALL: [Self; 2] = [Self::Contains, Self::Triggers];
+
+    /// Iterates over every edge kind, in sort order.
+    pub fn all() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// Ordinal used to order edges between the same nodes.
+    #[must_use]
+    pub const fn sort_key(self) -> u8 {
+        self as u8
+    }
+
+    /// Lowercase name used by exporters, e.g. as an edge label.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
         match self {
-            Self::Contains => 0,
-            Self::Triggers => 1,
+            Self::Contains => "contains",
+            Self::Triggers => "triggers",
         }
     }
 }
@@ -73,6 +138,29 @@ impl EdgeKind {
 pub struct RouteDescriptor {
     pub path: String,
     pub methods: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handler: Option<String>,
+    /// Names of the path parameters, in path order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<String>,
+}
+
+/// Extracts the parameter names from a route path, accepting both `:name`
+/// and `{name}` segments. Wildcards such as `{*rest}` yield `rest`.
+#[must_use]
+pub fn route_params(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter_map(|segment| {
+            segment.strip_prefix(':').or_else(|| {
+                segment
+                    .strip_prefix('{')
+                    .and_then(|inner| inner.strip_suffix('}'))
+                    .map(|inner| inner.trim_start_matches('*'))
+            })
+        })
+        .filter(|name| !name.is_empty())
+        .map(ToString::to_string)
+        .collect()
 }
 
 /// Background worker description extracted from queue registries.
@@ -82,6 +170,15 @@ pub struct BackgroundWorkerDescriptor {
     pub queue: Option<String>,
 }
 
+impl BackgroundWorkerDescriptor {
+    /// Graph node id for this worker. Handlers consuming several queues get
+    /// one node per queue, suffixed with `@<queue>`.
+    #[must_use]
+    pub fn node_id(&self) -> String {
+        node_id::worker(&self.name, self.queue.as_deref())
+    }
+}
+
 /// Scheduler job description extracted from the scheduler configuration.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct SchedulerJobDescriptor {
@@ -91,6 +188,30 @@ pub struct SchedulerJobDescriptor {
     pub run_on_start: bool,
     pub shell: bool,
     pub tags: Vec<String>,
+    /// Next execution time in RFC 3339 format, when the schedule is a valid
+    /// cron expression.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_run: Option<String>,
+}
+
+impl SchedulerJobDescriptor {
+    /// Describes the scheduler job `name` with sorted, deduplicated tags.
+    /// `next_run` is left empty since it depends on the current time.
+    #[must_use]
+    pub fn from_config(name: impl Into<String>, job: &scheduler::Job) -> Self {
+        let mut tags = job.tags.clone().unwrap_or_default();
+        tags.sort();
+        tags.dedup();
+        Self {
+            name: name.into(),
+            schedule: job.cron.clone(),
+            command: job.run.clone(),
+            run_on_start: job.run_on_start,
+            shell: job.shell,
+            tags,
+            next_run: None,
+        }
+    }
 }
 
 /// Task description exposed by the task registry.
@@ -98,9 +219,15 @@ pub struct SchedulerJobDescriptor {
 pub struct TaskDescriptor {
     pub name: String,
     pub detail: Option<String>,
+    /// Background workers this task enqueues, by worker name.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enqueues: Vec<String>,
 }
 
 /// Repository abstraction for retrieving HTTP routes.
+///
+/// Methods are kept in the order the repository returns them; the builder
+/// only drops repeats.
 pub trait RoutesRepository {
     fn routes(&self) -> Vec<RouteDescriptor>;
 }
@@ -129,6 +256,7 @@ where
     T: TaskRepository + ?Sized,
 {
     app_name: &'a str,
+    root_id: Option<String>,
     routes: &'a R,
     background_workers: &'a B,
     scheduler: &'a S,
@@ -152,6 +280,7 @@ where
     ) -> Self {
         Self {
             app_name,
+            root_id: None,
             routes,
             background_workers,
             scheduler,
@@ -159,13 +288,25 @@ where
         }
     }
 
+    /// Overrides the id of the application root node, `app:{name}` by
+    /// default, e.g. to keep graphs apart when several are merged. Every
+    /// other node id is then prefixed with it, as in `{root_id}::route:/`.
+    #[must_use]
+    pub fn with_root_id(mut self, root_id: impl Into<String>) -> Self {
+        self.root_id = Some(root_id.into());
+        self
+    }
+
     /// Materialises the graph by querying every repository.
     #[allow(clippy::too_many_lines)]
     pub fn build(&self) -> ApplicationGraph {
         let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
         let mut edges: Vec<GraphEdge> = Vec::new();
 
-        let root_id = format!("app:{}", self.app_name);
+        let root_id = self
+            .root_id
+            .clone()
+            .unwrap_or_else(|| node_id::app(self.app_name));
         nodes.insert(
             root_id.clone(),
             GraphNode {
@@ -177,9 +318,19 @@ where
         );
 
         let mut task_nodes: BTreeMap<String, String> = BTreeMap::new();
+        let mut task_references: Vec<(String, Vec<String>)> = Vec::new();
         for task in self.tasks.tasks() {
-            let TaskDescriptor { name, detail } = task;
-            let node_id = format!("task:{name}");
+            let TaskDescriptor {
+                name,
+                detail,
+                enqueues,
+            } = task;
+            let node_id = node_id::task(&name);
+            let mut references = enqueues;
+            if let Some(detail) = &detail {
+                references.extend(detail_references(detail));
+            }
+            task_references.push((node_id.clone(), references));
             nodes.insert(
                 node_id.clone(),
                 GraphNode {
@@ -199,15 +350,25 @@ where
         }
 
         for route in self.routes.routes() {
-            let RouteDescriptor { path, mut methods } = route;
-            methods.sort();
-            methods.dedup();
-            let node_id = format!("route:{path}");
+            let RouteDescriptor {
+                path,
+                mut methods,
+                handler,
+                params,
+            } = route;
+            let mut seen = BTreeSet::new();
+            methods.retain(|method| seen.insert(method.clone()));
+            let node_id = node_id::route(&path);
             nodes.insert(
                 node_id.clone(),
                 GraphNode {
                     id: node_id.clone(),
-                    kind: ComponentKind::HttpRoute { path, methods },
+                    kind: ComponentKind::HttpRoute {
+                        path,
+                        methods,
+                        handler,
+                        params,
+                    },
                 },
             );
             edges.push(GraphEdge {
@@ -217,9 +378,14 @@ where
             });
         }
 
+        let mut worker_nodes: BTreeMap<String, Vec<String>> = BTreeMap::new();
         for worker in self.background_workers.workers() {
+            let node_id = worker.node_id();
+            worker_nodes
+                .entry(worker.name.clone())
+                .or_default()
+                .push(node_id.clone());
             let BackgroundWorkerDescriptor { name, queue } = worker;
-            let node_id = format!("worker:{name}");
             nodes.insert(
                 node_id.clone(),
                 GraphNode {
@@ -243,9 +409,10 @@ where
                 run_on_start,
                 shell,
                 mut tags,
+                next_run,
             } = job;
 
-            let node_id = format!("scheduler:{name}");
+            let node_id = node_id::scheduler(&name);
             tags.sort();
             tags.dedup();
 
@@ -262,6 +429,7 @@ where
                         run_on_start,
                         shell,
                         tags,
+                        next_run,
                     },
                 },
             );
@@ -282,34 +450,135 @@ where
             }
         }
 
+        for (task_node_id, references) in task_references {
+            for worker_node_id in references
+                .iter()
+                .filter_map(|name| worker_nodes.get(name))
+                .flatten()
+            {
+                edges.push(GraphEdge {
+                    from: task_node_id.clone(),
+                    to: worker_node_id.clone(),
+                    kind: EdgeKind::Triggers,
+                });
+            }
+        }
+
         edges.extend(scheduler_edges);
-        edges.sort_by(|a, b| {
-            let a_key = (&a.from, &a.to, a.kind.sort_key());
-            let b_key = (&b.from, &b.to, b.kind.sort_key());
-            a_key.cmp(&b_key)
-        });
-        edges.dedup();
-
-        ApplicationGraph {
-            nodes: nodes.into_values().collect(),
-            edges,
+        let mut nodes: Vec<GraphNode> = nodes.into_values().collect();
+        if self.root_id.is_some() {
+            let scope = |id: &mut String| {
+                if *id != root_id {
+                    *id = format!("{root_id}::{id}");
+                }
+            };
+            nodes.iter_mut().for_each(|node| scope(&mut node.id));
+            for edge in &mut edges {
+                scope(&mut edge.from);
+                scope(&mut edge.to);
+            }
         }
+        sort_edges(&mut edges);
+
+        ApplicationGraph { nodes, edges }
     }
 }
 
+/// Orders edges by source, target and kind, dropping duplicates.
+fn sort_edges(edges: &mut Vec<GraphEdge>) {
+    edges.sort_by(|a, b| {
+        let a_key = (&a.from, &a.to, a.kind.sort_key());
+        let b_key = (&b.from, &b.to, b.kind.sort_key());
+        a_key.cmp(&b_key)
+    });
+    edges.dedup();
+}
+
+/// Errors raised by [`merge_graphs`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GraphMergeError {
+    /// Two graphs define a node with the same id but different contents.
+    #[error("node `{id}` is defined differently by two graphs")]
+    ConflictingNode { id: String },
+}
+
+/// Unions several graphs, e.g. the services behind a gateway. Identical
+/// nodes and edges are kept once, nodes are ordered by id and edges like
+/// [`GraphBuilder::build`] orders them.
+///
+/// Give each graph its own root with [`GraphBuilder::with_root_id`] when the
+/// applications share a name or components; their node ids are then
+/// namespaced by root and never collide.
+///
+/// # Errors
+///
+/// Returns [`GraphMergeError::ConflictingNode`] when two graphs use the same
+/// node id for different components.
+pub fn merge_graphs(graphs: Vec<ApplicationGraph>) -> Result<ApplicationGraph, GraphMergeError> {
+    let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+    let mut edges = Vec::new();
+    for graph in graphs {
+        for node in graph.nodes {
+            match nodes.get(&node.id) {
+                Some(existing) if existing.kind != node.kind => {
+                    return Err(GraphMergeError::ConflictingNode { id: node.id });
+                }
+                Some(_) => {}
+                None => {
+                    nodes.insert(node.id.clone(), node);
+                }
+            }
+        }
+        edges.extend(graph.edges);
+    }
+    sort_edges(&mut edges);
+
+    Ok(ApplicationGraph {
+        nodes: nodes.into_values().collect(),
+        edges,
+    })
+}
+
+/// Splits a task description into identifier-like words that may name a
+/// background worker, e.g. `DownloadWorker` in "Enqueue DownloadWorker jobs".
+fn detail_references(detail: &str) -> impl Iterator<Item = String> + '_ {
+    detail
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| !word.is_empty())
+        .map(ToString::to_string)
+}
+
 fn scheduler_task_reference(command: &str, shell: bool) -> Option<String> {
     let trimmed = command.trim();
     if trimmed.is_empty() {
         return None;
     }
 
-    let candidate = if shell {
-        trimmed
-            .strip_prefix("task ")
-            .and_then(|rest| rest.split_whitespace().next())
+    if !shell {
+        return trimmed.split_whitespace().next().map(ToString::to_string);
+    }
+
+    trimmed
+        .split(['&', ';', '|'])
+        .find_map(shell_segment_task_reference)
+}
+
+/// Extracts the task name from a single shell segment such as
+/// `cargo loco task cleanup --env prod` or `task cleanup`.
+fn shell_segment_task_reference(segment: &str) -> Option<String> {
+    let tokens: Vec<&str> = segment.split_whitespace().collect();
+
+    let name_index = if tokens.first() == Some(&"task") {
+        Some(1)
     } else {
-        trimmed.split_whitespace().next()
+        tokens
+            .windows(3)
+            .position(|window| window == ["cargo", "loco", "task"])
+            .map(|index| index + 3)
     }?;
 
-    Some(candidate.to_string())
+    tokens
+        .get(name_index)
+        .filter(|name| !name.starts_with('-'))
+        .map(|name| (*name).to_string())
 }
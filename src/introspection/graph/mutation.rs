@@ -2,6 +2,7 @@
 
 use std::sync::Arc;
 
+pub use loco_gen::{FileAction, PlannedFile};
 use serde::{Deserialize, Serialize};
 
 use crate::Error;
@@ -11,6 +12,12 @@ use crate::Error;
 pub struct FieldDefinition {
     pub name: String,
     pub data_type: String,
+    /// `Some(false)` marks the column as required (`string!`).
+    #[serde(default)]
+    pub nullable: Option<bool>,
+    /// `Some(true)` requests an index on the column (`string:index`).
+    #[serde(default)]
+    pub indexed: Option<bool>,
 }
 
 impl FieldDefinition {
@@ -19,18 +26,69 @@ impl FieldDefinition {
         Self {
             name: name.into(),
             data_type: data_type.into(),
+            nullable: None,
+            indexed: None,
         }
     }
 
+    #[must_use]
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = Some(nullable);
+        self
+    }
+
+    #[must_use]
+    pub fn with_indexed(mut self, indexed: bool) -> Self {
+        self.indexed = Some(indexed);
+        self
+    }
+
+    /// Returns the `name` and the generator type with modifiers applied.
     #[must_use]
     pub fn into_pair(self) -> (String, String) {
-        (self.name, self.data_type)
+        let data_type = self.composed_type();
+        (self.name, data_type)
     }
 
     #[must_use]
     pub fn as_pair(&self) -> (String, String) {
-        (self.name.clone(), self.data_type.clone())
+        (self.name.clone(), self.composed_type())
     }
+
+    /// Renders the field as a generator argument, e.g. `title:string!:index`.
+    #[must_use]
+    pub fn as_argument(&self) -> String {
+        format!("{}:{}", self.name, self.composed_type())
+    }
+
+    fn composed_type(&self) -> String {
+        let mut data_type = self.data_type.clone();
+        if self.nullable == Some(false) && !data_type.ends_with(['!', '^']) {
+            data_type.push('!');
+        }
+        if self.indexed == Some(true) {
+            data_type.push_str(":index");
+        }
+        data_type
+    }
+}
+
+/// Builds the generator field pairs, appending a `<name>:references` pair for
+/// every referenced entity.
+#[must_use]
+pub fn generator_fields(
+    fields: Vec<FieldDefinition>,
+    references: Vec<String>,
+) -> Vec<(String, String)> {
+    fields
+        .into_iter()
+        .map(FieldDefinition::into_pair)
+        .chain(
+            references
+                .into_iter()
+                .map(|reference| (reference, "references".to_string())),
+        )
+        .collect()
 }
 
 /// Presentation style for generated scaffold components.
@@ -53,6 +111,8 @@ pub enum NodeCreationRequest {
         with_timestamps: Option<bool>,
         #[serde(default)]
         fields: Vec<FieldDefinition>,
+        #[serde(default)]
+        references: Vec<String>,
     },
     #[cfg(feature = "with-db")]
     Migration {
@@ -61,6 +121,8 @@ pub enum NodeCreationRequest {
         with_timestamps: Option<bool>,
         #[serde(default)]
         fields: Vec<FieldDefinition>,
+        #[serde(default)]
+        references: Vec<String>,
     },
     #[cfg(feature = "with-db")]
     Scaffold {
@@ -69,6 +131,8 @@ pub enum NodeCreationRequest {
         with_timestamps: Option<bool>,
         #[serde(default)]
         fields: Vec<FieldDefinition>,
+        #[serde(default)]
+        references: Vec<String>,
         interface: NodePresentation,
     },
     Controller {
@@ -93,38 +157,63 @@ pub enum NodeCreationRequest {
 }
 
 impl NodeCreationRequest {
+    fn name(&self) -> Option<&str> {
+        match self {
+            #[cfg(feature = "with-db")]
+            Self::Model { name, .. }
+            | Self::Migration { name, .. }
+            | Self::Scaffold { name, .. } => Some(name),
+            Self::Controller { name, .. }
+            | Self::Task { name }
+            | Self::Worker { name }
+            | Self::Mailer { name }
+            | Self::Data { name } => Some(name),
+            Self::Scheduler {} => None,
+        }
+    }
+
     fn into_component(self) -> Result<NodeComponent, Error> {
+        if let Some(name) = self.name() {
+            validate_name(name)?;
+        }
+
         match self {
             #[cfg(feature = "with-db")]
             Self::Model {
                 name,
                 with_timestamps,
                 fields,
+                references,
             } => Ok(NodeComponent::Model {
                 name,
                 with_timestamps: with_timestamps.unwrap_or(true),
                 fields,
+                references,
             }),
             #[cfg(feature = "with-db")]
             Self::Migration {
                 name,
                 with_timestamps,
                 fields,
+                references,
             } => Ok(NodeComponent::Migration {
                 name,
                 with_timestamps: with_timestamps.unwrap_or(true),
                 fields,
+                references,
             }),
             #[cfg(feature = "with-db")]
             Self::Scaffold {
                 name,
                 with_timestamps,
                 fields,
+                references,
                 interface,
             } => Ok(NodeComponent::Scaffold {
                 name,
                 with_timestamps: with_timestamps.unwrap_or(true),
                 fields,
+                references,
                 interface,
             }),
             Self::Controller {
@@ -145,11 +234,43 @@ impl NodeCreationRequest {
     }
 }
 
+/// Ensures a component name is a snake_case or CamelCase identifier.
+fn validate_name(name: &str) -> Result<(), Error> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::BadRequest(format!(
+            "invalid component name `{name}`: expected a snake_case or CamelCase identifier \
+             (letters, digits and underscores, not starting with a digit)"
+        )))
+    }
+}
+
+/// Request envelope carrying generation options shared by every component.
+///
+/// The component fields are flattened, so `{"component": "task", "name":
+/// "cleanup", "dry_run": true}` deserializes into a dry-run task request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeCreationPayload {
+    #[serde(flatten)]
+    pub request: NodeCreationRequest,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 /// Normalised command passed to code generators.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NodeCreationCommand {
     pub app_name: String,
     pub component: NodeComponent,
+    /// When set, generators must only report what would be generated.
+    pub dry_run: bool,
 }
 
 /// Normalised component variants independent of adapters.
@@ -160,18 +281,21 @@ pub enum NodeComponent {
         name: String,
         with_timestamps: bool,
         fields: Vec<FieldDefinition>,
+        references: Vec<String>,
     },
     #[cfg(feature = "with-db")]
     Migration {
         name: String,
         with_timestamps: bool,
         fields: Vec<FieldDefinition>,
+        references: Vec<String>,
     },
     #[cfg(feature = "with-db")]
     Scaffold {
         name: String,
         with_timestamps: bool,
         fields: Vec<FieldDefinition>,
+        references: Vec<String>,
         interface: NodePresentation,
     },
     Controller {
@@ -198,6 +322,9 @@ pub enum NodeComponent {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ScaffoldGeneration {
     pub message: String,
+    /// Files a dry run would create or change.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<PlannedFile>,
 }
 
 impl ScaffoldGeneration {
@@ -205,11 +332,21 @@ impl ScaffoldGeneration {
     pub fn new(message: impl Into<String>) -> Self {
         Self {
             message: message.into(),
+            files: Vec::new(),
         }
     }
+
+    #[must_use]
+    pub fn with_files(mut self, files: Vec<PlannedFile>) -> Self {
+        self.files = files;
+        self
+    }
 }
 
 /// Port abstracting scaffold generation.
+///
+/// Implementations must not touch the filesystem when
+/// [`NodeCreationCommand::dry_run`] is set.
 pub trait ScaffoldGenerator: Send + Sync {
     fn generate(&self, command: NodeCreationCommand) -> crate::Result<ScaffoldGeneration>;
 }
@@ -245,10 +382,19 @@ where
     }
 
     pub fn create_node(&self, request: NodeCreationRequest) -> crate::Result<ScaffoldGeneration> {
+        self.submit(NodeCreationPayload {
+            request,
+            dry_run: false,
+        })
+    }
+
+    pub fn submit(&self, payload: NodeCreationPayload) -> crate::Result<ScaffoldGeneration> {
+        let NodeCreationPayload { request, dry_run } = payload;
         let component = request.into_component()?;
         let command = NodeCreationCommand {
             app_name: self.app_name.clone(),
             component,
+            dry_run,
         };
         self.generator.generate(command)
     }
@@ -286,6 +432,7 @@ mod tests {
             name: "post".into(),
             with_timestamps: None,
             fields: vec![FieldDefinition::new("title", "string")],
+            references: vec![],
         };
 
         service
@@ -300,10 +447,192 @@ mod tests {
                 name,
                 with_timestamps,
                 fields,
-            } if name == "post" && *with_timestamps && fields == &vec![FieldDefinition::new("title", "string")]
+                references,
+            } if name == "post" && *with_timestamps && fields == &vec![FieldDefinition::new("title", "string")] && references.is_empty()
         ));
     }
 
+    #[cfg(feature = "with-db")]
+    #[test]
+    fn model_references_are_carried_into_command() {
+        let generator = Arc::new(RecordingGenerator::default());
+        let service = GraphMutationService::new("demo", Arc::clone(&generator));
+        let request: NodeCreationRequest = serde_json::from_value(serde_json::json!({
+            "component": "model",
+            "name": "post",
+            "fields": [{"name": "title", "data_type": "string"}],
+            "references": ["user", "organization"],
+        }))
+        .expect("request deserializes");
+
+        service
+            .create_node(request)
+            .expect("model generation succeeds");
+
+        let recorded = generator.take();
+        assert_eq!(
+            recorded,
+            vec![NodeCreationCommand {
+                app_name: "demo".into(),
+                component: NodeComponent::Model {
+                    name: "post".into(),
+                    with_timestamps: true,
+                    fields: vec![FieldDefinition::new("title", "string")],
+                    references: vec!["user".into(), "organization".into()],
+                },
+                dry_run: false,
+            }]
+        );
+
+        let NodeComponent::Model {
+            fields, references, ..
+        } = recorded[0].component.clone()
+        else {
+            unreachable!("model component expected");
+        };
+        assert_eq!(
+            generator_fields(fields, references),
+            vec![
+                ("title".to_string(), "string".to_string()),
+                ("user".to_string(), "references".to_string()),
+                ("organization".to_string(), "references".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn composes_indexed_non_null_field() {
+        let field = FieldDefinition::new("title", "string")
+            .with_nullable(false)
+            .with_indexed(true);
+
+        assert_eq!(field.as_argument(), "title:string!:index");
+        assert_eq!(
+            field.into_pair(),
+            ("title".to_string(), "string!:index".to_string())
+        );
+    }
+
+    #[test]
+    fn keeps_plain_field_type_untouched() {
+        let field = FieldDefinition::new("title", "string^").with_nullable(false);
+
+        assert_eq!(
+            field.as_pair(),
+            ("title".to_string(), "string^".to_string())
+        );
+    }
+
+    #[cfg(feature = "with-db")]
+    #[test]
+    fn model_command_carries_field_modifiers() {
+        let generator = Arc::new(RecordingGenerator::default());
+        let service = GraphMutationService::new("demo", Arc::clone(&generator));
+        let field: FieldDefinition = serde_json::from_value(serde_json::json!({
+            "name": "email",
+            "data_type": "string",
+            "nullable": false,
+            "indexed": true,
+        }))
+        .expect("field deserializes");
+        let request = NodeCreationRequest::Model {
+            name: "user".into(),
+            with_timestamps: None,
+            fields: vec![field],
+            references: vec![],
+        };
+
+        service
+            .create_node(request)
+            .expect("model generation succeeds");
+
+        let recorded = generator.take();
+        assert!(matches!(
+            &recorded[0].component,
+            NodeComponent::Model { fields, .. }
+                if fields.iter().map(FieldDefinition::as_argument).collect::<Vec<_>>()
+                    == vec!["email:string!:index".to_string()]
+        ));
+    }
+
+    #[test]
+    fn rejects_empty_name() {
+        let generator = Arc::new(RecordingGenerator::default());
+        let service = GraphMutationService::new("demo", Arc::clone(&generator));
+
+        let result = service.create_node(NodeCreationRequest::Worker {
+            name: String::new(),
+        });
+
+        assert!(
+            matches!(result, Err(Error::BadRequest(message)) if message.contains("invalid component name"))
+        );
+        assert!(generator.take().is_empty());
+    }
+
+    #[test]
+    fn rejects_name_with_spaces() {
+        let generator = Arc::new(RecordingGenerator::default());
+        let service = GraphMutationService::new("demo", Arc::clone(&generator));
+
+        let result = service.create_node(NodeCreationRequest::Task {
+            name: "123 bad name".into(),
+        });
+
+        assert!(
+            matches!(result, Err(Error::BadRequest(message)) if message.contains("`123 bad name`"))
+        );
+        assert!(generator.take().is_empty());
+    }
+
+    #[test]
+    fn accepts_camel_case_names() {
+        let generator = Arc::new(RecordingGenerator::default());
+        let service = GraphMutationService::new("demo", Arc::clone(&generator));
+
+        service
+            .create_node(NodeCreationRequest::Mailer {
+                name: "WelcomeMailer".into(),
+            })
+            .expect("camel case name is accepted");
+
+        assert_eq!(generator.take().len(), 1);
+    }
+
+    #[test]
+    fn dry_run_flag_is_carried_into_command() {
+        let generator = Arc::new(RecordingGenerator::default());
+        let service = GraphMutationService::new("demo", Arc::clone(&generator));
+        let payload: NodeCreationPayload = serde_json::from_value(serde_json::json!({
+            "component": "worker",
+            "name": "report",
+            "dry_run": true,
+        }))
+        .expect("payload deserializes");
+
+        service.submit(payload).expect("dry run succeeds");
+
+        let recorded = generator.take();
+        assert_eq!(recorded.len(), 1);
+        assert!(recorded[0].dry_run);
+        assert!(matches!(
+            &recorded[0].component,
+            NodeComponent::Worker { name } if name == "report"
+        ));
+    }
+
+    #[test]
+    fn create_node_is_not_a_dry_run() {
+        let generator = Arc::new(RecordingGenerator::default());
+        let service = GraphMutationService::new("demo", Arc::clone(&generator));
+
+        service
+            .create_node(NodeCreationRequest::Scheduler {})
+            .expect("scheduler generation succeeds");
+
+        assert!(!generator.take()[0].dry_run);
+    }
+
     #[test]
     fn propagates_app_name_to_command() {
         let generator = Arc::new(RecordingGenerator::default());
@@ -3,20 +3,24 @@ use std::{
     sync::Arc,
 };
 
+use chrono::{DateTime, Utc};
+use croner::Cron;
 use serde::Serialize;
 
 use crate::{
     app::AppContext,
     bgworker::Queue,
     controller::{AppRoutes, ListRoutes},
+    doctor::{Check, Resource},
+    introspection::assistant::{findings_from_checks, DoctorFinding, DoctorStatus},
     scheduler,
     task::Tasks,
 };
 
 use super::domain::{
-    ApplicationGraph, BackgroundWorkerDescriptor, BackgroundWorkerRepository, GraphBuilder,
-    RouteDescriptor, RoutesRepository, SchedulerJobDescriptor, SchedulerRepository, TaskDescriptor,
-    TaskRepository,
+    route_params, ApplicationGraph, BackgroundWorkerDescriptor, BackgroundWorkerRepository,
+    GraphBuilder, RouteDescriptor, RoutesRepository, SchedulerJobDescriptor, SchedulerRepository,
+    TaskDescriptor, TaskRepository,
 };
 
 /// Cached data used by adapters to instantiate [`ApplicationGraphService`].
@@ -24,6 +28,8 @@ use super::domain::{
 pub struct GraphIntrospectionSeed {
     pub app_name: String,
     pub routes: Vec<RouteDescriptor>,
+    /// `(path, method)` pairs registered more than once.
+    pub duplicate_routes: Vec<(String, String)>,
 }
 
 impl GraphIntrospectionSeed {
@@ -33,9 +39,17 @@ impl GraphIntrospectionSeed {
         Self {
             app_name: app_name.into(),
             routes,
+            duplicate_routes: Vec::new(),
         }
     }
 
+    /// Records duplicate route registrations found while collecting routes.
+    #[must_use]
+    pub fn with_duplicate_routes(mut self, duplicate_routes: Vec<(String, String)>) -> Self {
+        self.duplicate_routes = duplicate_routes;
+        self
+    }
+
     /// Instantiates an [`ApplicationGraphService`] bound to this seed.
     #[must_use]
     pub fn into_service<'a>(&'a self, context: &'a AppContext) -> ApplicationGraphService<'a> {
@@ -44,6 +58,7 @@ impl GraphIntrospectionSeed {
             self.routes.clone(),
             context,
         )
+        .with_duplicate_routes(self.duplicate_routes.clone())
     }
 }
 
@@ -53,16 +68,193 @@ pub trait GraphQueryService {
     fn snapshot(&self) -> GraphSnapshot;
 }
 
+/// Port consulted for the `health` section of a [`GraphSnapshot`].
+pub trait HealthSource: Send + Sync {
+    /// Returns `true` when the application is considered healthy.
+    fn is_healthy(&self) -> bool;
+}
+
+/// Latest doctor findings, published into the shared store so graph
+/// snapshots report real health.
+///
+/// `/__loco/doctor` publishes it on every call. Until something inserts it,
+/// snapshots report healthy.
+///
+/// ```rust,ignore
+/// ctx.shared_store.insert(DoctorHealth::from_checks(&checks));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DoctorHealth {
+    pub findings: Vec<DoctorFinding>,
+}
+
+impl DoctorHealth {
+    #[must_use]
+    pub fn new(findings: Vec<DoctorFinding>) -> Self {
+        Self { findings }
+    }
+
+    #[must_use]
+    pub fn from_checks(checks: &BTreeMap<Resource, Check>) -> Self {
+        Self::new(findings_from_checks(checks))
+    }
+}
+
+impl HealthSource for DoctorHealth {
+    fn is_healthy(&self) -> bool {
+        self.findings
+            .iter()
+            .all(|finding| finding.status == DoctorStatus::Passing)
+    }
+}
+
+/// Version of the [`GraphSnapshot`] JSON contract.
+///
+/// Bump this whenever fields are added, removed or change meaning so clients
+/// can detect incompatible payloads.
+pub const GRAPH_SCHEMA_VERSION: u32 = 5;
+
 /// Serializable representation of the application graph exposed to adapters.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct GraphSnapshot {
+    pub schema_version: u32,
     pub routes: Vec<RouteDescriptor>,
+    /// Number of routes before pagination. Only set when `routes` is a page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routes_total: Option<usize>,
     pub dependencies: GraphDependencies,
     pub health: GraphHealth,
 }
 
+impl GraphSnapshot {
+    /// Keeps `limit` routes starting at `offset` and records the unpaginated
+    /// count in `routes_total`. Dependencies are left untouched.
+    #[must_use]
+    pub fn paginate_routes(mut self, offset: usize, limit: Option<usize>) -> Self {
+        self.routes_total = Some(self.routes.len());
+        self.routes = self
+            .routes
+            .into_iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+        self
+    }
+
+    /// Keeps only the scheduler jobs tagged with `tag`, so their nodes and
+    /// edges are the only scheduler ones in a graph built from the snapshot.
+    #[must_use]
+    pub fn with_scheduler_tag(mut self, tag: &str) -> Self {
+        self.dependencies
+            .scheduler_jobs
+            .retain(|job| job.tags.iter().any(|candidate| candidate == tag));
+        self
+    }
+
+    /// Returns a small, fully populated snapshot documenting the JSON contract.
+    ///
+    /// ```json
+    /// {
+    ///   "schema_version": 5,
+    ///   "routes": [{ "path": "/_health", "methods": ["GET"] }],
+    ///   "dependencies": {
+    ///     "background_workers": [{ "name": "DownloadWorker", "queue": "default" }],
+    ///     "scheduler_jobs": [{
+    ///       "name": "nightly_cleanup",
+    ///       "schedule": "0 0 * * *",
+    ///       "command": "cleanup",
+    ///       "run_on_start": false,
+    ///       "shell": false,
+    ///       "tags": ["maintenance"],
+    ///       "next_run": "2024-01-02T00:00:00+00:00"
+    ///     }],
+    ///     "tasks": [{ "name": "cleanup", "detail": "Remove stale data" }]
+    ///   },
+    ///   "health": { "ok": true }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn example() -> Self {
+        Self {
+            schema_version: GRAPH_SCHEMA_VERSION,
+            routes: vec![RouteDescriptor {
+                path: "/_health".to_string(),
+                methods: vec!["GET".to_string()],
+                handler: None,
+                params: Vec::new(),
+            }],
+            routes_total: None,
+            dependencies: GraphDependencies {
+                background_workers: vec![BackgroundWorkerDescriptor {
+                    name: "DownloadWorker".to_string(),
+                    queue: Some("default".to_string()),
+                }],
+                scheduler_jobs: vec![SchedulerJobDescriptor {
+                    name: "nightly_cleanup".to_string(),
+                    schedule: "0 0 * * *".to_string(),
+                    command: "cleanup".to_string(),
+                    run_on_start: false,
+                    shell: false,
+                    tags: vec!["maintenance".to_string()],
+                    next_run: Some("2024-01-02T00:00:00+00:00".to_string()),
+                }],
+                tasks: vec![TaskDescriptor {
+                    name: "cleanup".to_string(),
+                    detail: Some("Remove stale data".to_string()),
+                    enqueues: Vec::new(),
+                }],
+            },
+            health: GraphHealth {
+                ok: true,
+                duplicate_routes: 0,
+            },
+        }
+    }
+
+    /// Renders the snapshot counts as gauges in the Prometheus text exposition
+    /// format.
+    #[must_use]
+    pub fn to_prometheus(&self) -> String {
+        let gauges = [
+            (
+                "loco_routes_total",
+                "Number of routes registered in the application.",
+                self.routes.len(),
+            ),
+            (
+                "loco_workers_total",
+                "Number of registered background workers.",
+                self.dependencies.background_workers.len(),
+            ),
+            (
+                "loco_scheduler_jobs_total",
+                "Number of configured scheduler jobs.",
+                self.dependencies.scheduler_jobs.len(),
+            ),
+            (
+                "loco_tasks_total",
+                "Number of registered tasks.",
+                self.dependencies.tasks.len(),
+            ),
+            (
+                "graph_snapshot_health",
+                "Whether the introspection graph is healthy (1) or not (0).",
+                usize::from(self.health.ok),
+            ),
+        ];
+
+        let mut output = String::new();
+        for (name, help, value) in gauges {
+            output.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}\n"
+            ));
+        }
+        output
+    }
+}
+
 /// Collection of framework dependencies registered in the application.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct GraphDependencies {
     pub background_workers: Vec<BackgroundWorkerDescriptor>,
     pub scheduler_jobs: Vec<SchedulerJobDescriptor>,
@@ -70,9 +262,49 @@ pub struct GraphDependencies {
 }
 
 /// Health status for the introspection graph.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct GraphHealth {
     pub ok: bool,
+    /// Number of `(path, method)` pairs registered more than once. Any
+    /// duplicate makes the graph unhealthy.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub duplicate_routes: usize,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+const fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// Order in which a route's methods are listed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MethodOrder {
+    /// Sorted by name, e.g. `DELETE, GET, POST`.
+    #[default]
+    Alphabetical,
+    /// `GET, POST, PUT, PATCH, DELETE`, followed by any other method
+    /// alphabetically.
+    HttpSemantic,
+}
+
+impl MethodOrder {
+    const HTTP_SEMANTIC: [&'static str; 5] = ["GET", "POST", "PUT", "PATCH", "DELETE"];
+
+    /// Sorts `methods` in place according to this ordering.
+    pub fn sort(self, methods: &mut [String]) {
+        match self {
+            Self::Alphabetical => methods.sort(),
+            Self::HttpSemantic => methods.sort_by(|a, b| {
+                let rank = |method: &str| {
+                    Self::HTTP_SEMANTIC
+                        .iter()
+                        .position(|known| *known == method)
+                        .unwrap_or(Self::HTTP_SEMANTIC.len())
+                };
+                rank(a).cmp(&rank(b)).then_with(|| a.cmp(b))
+            }),
+        }
+    }
 }
 
 /// Service that adapts framework-specific data sources to the graph domain.
@@ -83,14 +315,17 @@ pub struct ApplicationGraphService<'a> {
     scheduler_config: Option<&'a scheduler::Config>,
     context: &'a AppContext,
     task_registry: Option<&'a Tasks>,
+    clock: Option<DateTime<Utc>>,
+    health_source: Option<Arc<dyn HealthSource>>,
+    exclude_prefixes: Vec<String>,
+    duplicate_routes: Vec<(String, String)>,
+    method_order: MethodOrder,
 }
 
 impl<'a> ApplicationGraphService<'a> {
     /// Builds the service from the [`AppRoutes`] definition and application context.
     pub fn new(app_name: &'a str, routes: &'a AppRoutes, context: &'a AppContext) -> Self {
-        let collected_routes = routes.collect();
-        let descriptors = Self::collect_route_descriptors(&collected_routes);
-        Self::from_route_descriptors(app_name, descriptors, context)
+        Self::from_list_routes(app_name, routes.collect(), context)
     }
 
     /// Builds the service from already collected routes, allowing tests to stub the data.
@@ -101,6 +336,7 @@ impl<'a> ApplicationGraphService<'a> {
     ) -> Self {
         let descriptors = Self::collect_route_descriptors(&routes);
         Self::from_route_descriptors(app_name, descriptors, context)
+            .with_duplicate_routes(Self::find_duplicate_routes(&routes))
     }
 
     /// Builds the service from route descriptors, bypassing Axum specific metadata.
@@ -116,6 +352,11 @@ impl<'a> ApplicationGraphService<'a> {
             scheduler_config: context.config.scheduler.as_ref(),
             context,
             task_registry: None,
+            clock: None,
+            health_source: None,
+            exclude_prefixes: Vec::new(),
+            duplicate_routes: Vec::new(),
+            method_order: MethodOrder::default(),
         }
     }
 
@@ -140,12 +381,71 @@ impl<'a> ApplicationGraphService<'a> {
         self
     }
 
+    /// Pins the instant used to compute scheduler next-run times instead of
+    /// the current time.
+    pub fn with_clock(mut self, now: DateTime<Utc>) -> Self {
+        self.clock = Some(now);
+        self
+    }
+
+    /// Overrides where snapshot health is read from. By default the
+    /// [`DoctorHealth`] published in the shared store is used, if any.
+    pub fn with_health_source(mut self, health_source: Arc<dyn HealthSource>) -> Self {
+        self.health_source = Some(health_source);
+        self
+    }
+
+    /// Hides routes under any of the given path prefixes, such as the internal
+    /// `/__loco` endpoints. Prefixes match whole path segments, so `/api`
+    /// hides `/api` and `/api/users` but not `/apiary`. Nothing is excluded by
+    /// default.
+    pub fn with_excluded_prefixes<I, S>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclude_prefixes = prefixes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Overrides the `(path, method)` pairs reported as registered twice.
+    pub fn with_duplicate_routes(mut self, duplicate_routes: Vec<(String, String)>) -> Self {
+        self.duplicate_routes = duplicate_routes;
+        self
+    }
+
+    /// Returns the `(path, method)` pairs registered more than once, which
+    /// route descriptors silently merge.
+    #[must_use]
+    pub fn duplicate_routes(&self) -> Vec<(String, String)> {
+        self.duplicate_routes.clone()
+    }
+
+    /// Sets how each route's methods are ordered. Defaults to
+    /// [`MethodOrder::Alphabetical`].
+    pub fn with_method_order(mut self, method_order: MethodOrder) -> Self {
+        self.method_order = method_order;
+        self
+    }
+
     /// Overrides the collected route descriptors.
     pub fn with_routes(mut self, routes: Vec<RouteDescriptor>) -> Self {
         self.routes = routes;
         self
     }
 
+    /// Consults the configured [`HealthSource`], falling back to the shared
+    /// [`DoctorHealth`]. Without findings the application counts as healthy.
+    fn is_healthy(&self) -> bool {
+        if let Some(source) = &self.health_source {
+            return source.is_healthy();
+        }
+        self.context
+            .shared_store
+            .get_ref::<DoctorHealth>()
+            .map_or(true, |health| health.is_healthy())
+    }
+
     /// Materialises the [`ApplicationGraph`] using the domain builder.
     pub fn build_graph(&self) -> ApplicationGraph {
         GraphBuilder::new(self.app_name, self, self, self, self).build()
@@ -154,70 +454,124 @@ impl<'a> ApplicationGraphService<'a> {
     /// Aggregates route descriptors from collected [`ListRoutes`] data.
     #[must_use]
     pub fn collect_route_descriptors(routes: &[ListRoutes]) -> Vec<RouteDescriptor> {
-        let mut aggregated: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
-
-        for route in routes {
-            let entry = aggregated.entry(route.uri.clone()).or_default();
-            for method in &route.actions {
-                entry.insert(method.to_string());
-            }
-        }
-
-        aggregated
+        aggregate_routes(routes)
             .into_iter()
-            .map(|(path, methods)| RouteDescriptor {
+            .map(|(path, (methods, handler))| RouteDescriptor {
+                params: route_params(&path),
                 path,
-                methods: methods.into_iter().collect(),
+                methods: methods.into_keys().collect(),
+                handler,
+            })
+            .collect()
+    }
+
+    /// Lists the `(path, method)` pairs registered by more than one route.
+    #[must_use]
+    pub fn find_duplicate_routes(routes: &[ListRoutes]) -> Vec<(String, String)> {
+        aggregate_routes(routes)
+            .into_iter()
+            .flat_map(|(path, (methods, _))| {
+                methods
+                    .into_iter()
+                    .filter(|(_, count)| *count > 1)
+                    .map(move |(method, _)| (path.clone(), method))
             })
             .collect()
     }
 }
 
+/// Methods registered per path with how many routes registered each, plus
+/// the first known handler.
+type RouteAggregate = BTreeMap<String, (BTreeMap<String, usize>, Option<String>)>;
+
+fn aggregate_routes(routes: &[ListRoutes]) -> RouteAggregate {
+    let mut aggregated: RouteAggregate = BTreeMap::new();
+
+    for route in routes {
+        let (methods, handler) = aggregated.entry(route.uri.clone()).or_default();
+        let actions: BTreeSet<String> = route.actions.iter().map(ToString::to_string).collect();
+        for method in actions {
+            *methods.entry(method).or_default() += 1;
+        }
+        if handler.is_none() {
+            handler.clone_from(&route.handler);
+        }
+    }
+
+    aggregated
+}
+
 impl GraphQueryService for ApplicationGraphService<'_> {
     fn snapshot(&self) -> GraphSnapshot {
         GraphSnapshot {
+            schema_version: GRAPH_SCHEMA_VERSION,
             routes: RoutesRepository::routes(self),
+            routes_total: None,
             dependencies: GraphDependencies {
                 background_workers: BackgroundWorkerRepository::workers(self),
                 scheduler_jobs: SchedulerRepository::jobs(self),
                 tasks: TaskRepository::tasks(self),
             },
-            health: GraphHealth { ok: true },
+            health: GraphHealth {
+                ok: self.is_healthy() && self.duplicate_routes.is_empty(),
+                duplicate_routes: self.duplicate_routes.len(),
+            },
         }
     }
 }
 
 impl RoutesRepository for ApplicationGraphService<'_> {
     fn routes(&self) -> Vec<RouteDescriptor> {
-        self.routes.clone()
+        self.routes
+            .iter()
+            .filter(|route| {
+                !self
+                    .exclude_prefixes
+                    .iter()
+                    .any(|prefix| path_is_under(&route.path, prefix))
+            })
+            .cloned()
+            .map(|mut route| {
+                self.method_order.sort(&mut route.methods);
+                route
+            })
+            .collect()
     }
 }
 
+/// Whether `path` is `prefix` itself or nested below it.
+fn path_is_under(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
 impl BackgroundWorkerRepository for ApplicationGraphService<'_> {
     fn workers(&self) -> Vec<BackgroundWorkerDescriptor> {
-        let mut names = BTreeSet::new();
+        let mut pairs = BTreeSet::new();
 
         if let Some(queue) = &self.queue_provider {
             match queue.as_ref() {
                 #[cfg(feature = "bg_redis")]
-                Queue::Redis(_, registry, _, _) => {
-                    names.extend(read_registry_names(Arc::clone(registry)));
+                Queue::Redis(_, registry, opts, _) => {
+                    let queues = crate::bgworker::redis::get_queues(&opts.queues);
+                    pairs.extend(read_registry_workers(Arc::clone(registry), &queues));
                 }
                 #[cfg(feature = "bg_pg")]
                 Queue::Postgres(_, registry, _, _) => {
-                    names.extend(read_registry_names(Arc::clone(registry)));
+                    pairs.extend(read_registry_workers(Arc::clone(registry), &[]));
                 }
                 #[cfg(feature = "bg_sqlt")]
                 Queue::Sqlite(_, registry, _, _) => {
-                    names.extend(read_registry_names(Arc::clone(registry)));
+                    pairs.extend(read_registry_workers(Arc::clone(registry), &[]));
                 }
                 Queue::None => {}
             }
         }
 
-        names
+        pairs
             .into_iter()
-            .map(|name| BackgroundWorkerDescriptor { name, queue: None })
+            .map(|(name, queue)| BackgroundWorkerDescriptor { name, queue })
             .collect()
     }
 }
@@ -227,20 +581,14 @@ impl SchedulerRepository for ApplicationGraphService<'_> {
         let mut result = Vec::new();
 
         if let Some(config) = self.scheduler_config {
+            let now = self.clock.unwrap_or_else(Utc::now);
             let mut jobs: Vec<_> = config.jobs.iter().collect();
             jobs.sort_by(|(left, _), (right, _)| left.cmp(right));
 
             for (name, job) in jobs {
-                let mut tags = job.tags.clone().unwrap_or_default();
-                tags.sort();
-                tags.dedup();
                 result.push(SchedulerJobDescriptor {
-                    name: name.clone(),
-                    schedule: job.cron.clone(),
-                    command: job.run.clone(),
-                    run_on_start: job.run_on_start,
-                    shell: job.shell,
-                    tags,
+                    next_run: next_run(&job.cron, &now),
+                    ..SchedulerJobDescriptor::from_config(name.as_str(), job)
                 });
             }
         }
@@ -263,6 +611,25 @@ impl TaskRepository for ApplicationGraphService<'_> {
     }
 }
 
+/// Computes the next execution after `now` for a cron or English schedule,
+/// returning `None` when the schedule cannot be parsed.
+fn next_run(schedule: &str, now: &DateTime<Utc>) -> Option<String> {
+    let cron = scheduler::cron_syntax(schedule).ok()?;
+    // English schedules carry a trailing year field, which croner does not
+    // understand; an unrestricted year can simply be dropped.
+    let cron = match cron.rsplit_once(' ') {
+        Some((rest, "*")) if cron.split_whitespace().count() == 7 => rest,
+        _ => cron.as_str(),
+    };
+    Cron::new(cron)
+        .with_seconds_optional()
+        .parse()
+        .ok()?
+        .find_next_occurrence(now, false)
+        .ok()
+        .map(|next| next.to_rfc3339())
+}
+
 /// Collects task descriptors from a registry and keeps the output sorted for determinism.
 fn collect_tasks(tasks: &Tasks) -> Vec<TaskDescriptor> {
     let mut descriptors: Vec<_> = tasks
@@ -271,6 +638,7 @@ fn collect_tasks(tasks: &Tasks) -> Vec<TaskDescriptor> {
         .map(|info| TaskDescriptor {
             name: info.name,
             detail: Some(info.detail),
+            enqueues: Vec::new(),
         })
         .collect();
     descriptors.sort_by(|left, right| left.name.cmp(&right.name));
@@ -278,18 +646,49 @@ fn collect_tasks(tasks: &Tasks) -> Vec<TaskDescriptor> {
 }
 
 #[cfg(any(feature = "bg_redis", feature = "bg_pg", feature = "bg_sqlt"))]
-fn read_registry_names<R>(registry: Arc<tokio::sync::Mutex<R>>) -> Vec<String>
+fn read_registry_workers<R>(
+    registry: Arc<tokio::sync::Mutex<R>>,
+    queues: &[String],
+) -> Vec<(String, Option<String>)>
 where
     R: RegistryExtractor,
 {
     block_on_registry(async move {
         let guard = registry.lock().await;
-        guard.handler_names()
+        registry_workers(&*guard, queues)
     })
     .unwrap_or_default()
 }
 
-#[cfg(any(feature = "bg_redis", feature = "bg_pg", feature = "bg_sqlt"))]
+/// Pairs every registered handler with each queue it consumes. When the
+/// provider does not expose queues the handler is reported without one.
+#[cfg_attr(
+    not(any(feature = "bg_redis", feature = "bg_pg", feature = "bg_sqlt")),
+    allow(dead_code)
+)]
+fn registry_workers<R>(registry: &R, queues: &[String]) -> Vec<(String, Option<String>)>
+where
+    R: RegistryExtractor + ?Sized,
+{
+    let mut pairs = Vec::new();
+    for name in registry.handler_names() {
+        if queues.is_empty() {
+            pairs.push((name, None));
+        } else {
+            pairs.extend(
+                queues
+                    .iter()
+                    .map(|queue| (name.clone(), Some(queue.clone()))),
+            );
+        }
+    }
+    pairs
+}
+
+#[cfg_attr(
+    not(any(feature = "bg_redis", feature = "bg_pg", feature = "bg_sqlt")),
+    allow(dead_code)
+)]
 trait RegistryExtractor {
     fn handler_names(&self) -> Vec<String>;
 }
@@ -331,3 +730,51 @@ where
             .map(|rt| rt.block_on(future)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeRegistry(Vec<&'static str>);
+
+    impl RegistryExtractor for FakeRegistry {
+        fn handler_names(&self) -> Vec<String> {
+            self.0.iter().map(ToString::to_string).collect()
+        }
+    }
+
+    #[test]
+    fn registry_workers_report_one_entry_per_queue() {
+        let registry = FakeRegistry(vec!["DownloadWorker"]);
+        let queues = vec!["default".to_string(), "mailer".to_string()];
+
+        assert_eq!(
+            registry_workers(&registry, &queues),
+            vec![
+                ("DownloadWorker".to_string(), Some("default".to_string())),
+                ("DownloadWorker".to_string(), Some("mailer".to_string())),
+            ]
+        );
+        assert_eq!(
+            registry_workers(&registry, &[]),
+            vec![("DownloadWorker".to_string(), None)]
+        );
+    }
+
+    #[test]
+    fn next_run_understands_english_schedules() {
+        let now = DateTime::parse_from_rfc3339("2024-01-01T12:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            next_run("every day at 4:00 am", &now).as_deref(),
+            Some("2024-01-02T04:00:00+00:00")
+        );
+        assert_eq!(
+            next_run("0 0 * * *", &now).as_deref(),
+            Some("2024-01-02T00:00:00+00:00")
+        );
+        assert_eq!(next_run("whenever you like", &now), None);
+    }
+}
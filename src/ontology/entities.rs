@@ -1,5 +1,6 @@
 use std::collections::{BTreeMap, BTreeSet};
 
+use serde_json::json;
 use thiserror::Error;
 
 use super::value_objects::Iri;
@@ -324,6 +325,54 @@ impl Ontology {
         Ok(())
     }
 
+    /// Removes a property that no individual asserts.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OntologyError::MissingProperty`] when the property is unknown
+    /// and [`OntologyError::PropertyInUse`] when an individual still asserts it.
+    pub fn remove_property(&mut self, id: &Iri) -> Result<Property, OntologyError> {
+        if let Some(individual) = self
+            .individuals
+            .values()
+            .find(|individual| individual.properties().contains_key(id))
+        {
+            return Err(OntologyError::PropertyInUse {
+                ontology: self.id.clone(),
+                property: id.clone(),
+                individual: individual.id().clone(),
+            });
+        }
+        self.properties
+            .remove(id)
+            .ok_or_else(|| OntologyError::MissingProperty {
+                ontology: self.id.clone(),
+                property: id.clone(),
+            })
+    }
+
+    /// Removes an individual, returning the individuals whose object
+    /// assertions still point at it, or `None` when it was not present.
+    ///
+    /// Those assertions are left in place so callers can decide whether to
+    /// clean them up or reject the removal.
+    pub fn remove_individual(&mut self, id: &Iri) -> Option<BTreeSet<Iri>> {
+        self.individuals.remove(id)?;
+        let referrers = self
+            .individuals
+            .values()
+            .filter(|individual| {
+                individual
+                    .properties()
+                    .values()
+                    .flatten()
+                    .any(|assertion| *assertion == PropertyAssertion::Individual(id.clone()))
+            })
+            .map(|individual| individual.id().clone())
+            .collect();
+        Some(referrers)
+    }
+
     /// Returns the ontology identifier.
     #[must_use]
     pub fn id(&self) -> &Iri {
@@ -371,6 +420,24 @@ impl Ontology {
     pub fn individuals(&self) -> &BTreeMap<Iri, Individual> {
         &self.individuals
     }
+
+    /// Flattens every object property assertion into `(subject, property,
+    /// object)` triples, sorted and without duplicates. Literal assertions
+    /// are skipped.
+    #[must_use]
+    pub fn object_edges(&self) -> Vec<(Iri, Iri, Iri)> {
+        let mut edges = BTreeSet::new();
+        for individual in self.individuals.values() {
+            for (property, assertions) in individual.properties() {
+                for assertion in assertions {
+                    if let PropertyAssertion::Individual(target) = assertion {
+                        edges.insert((individual.id().clone(), property.clone(), target.clone()));
+                    }
+                }
+            }
+        }
+        edges.into_iter().collect()
+    }
 }
 
 /// Errors raised when manipulating an ontology aggregate.
@@ -394,6 +461,60 @@ pub enum OntologyError {
     /// Property assertion type did not match the property definition.
     #[error("property assertion does not match property `{property}` in ontology `{ontology}`")]
     InvalidPropertyAssertion { ontology: Iri, property: Iri },
+    /// Attempted to remove a property that an individual still asserts.
+    #[error("property `{property}` is asserted by `{individual}` in ontology `{ontology}`")]
+    PropertyInUse {
+        ontology: Iri,
+        property: Iri,
+        individual: Iri,
+    },
+}
+
+impl OntologyError {
+    /// Serializes the error with its fields for transport, e.g.
+    /// `{ "error": "missing_class", "ontology": "...", "class": "..." }`.
+    #[must_use]
+    pub fn to_problem_detail(&self) -> serde_json::Value {
+        match self {
+            Self::DuplicateClass(class) => json!({
+                "error": "duplicate_class",
+                "class": class.as_str(),
+            }),
+            Self::DuplicateProperty(property) => json!({
+                "error": "duplicate_property",
+                "property": property.as_str(),
+            }),
+            Self::DuplicateIndividual(individual) => json!({
+                "error": "duplicate_individual",
+                "individual": individual.as_str(),
+            }),
+            Self::MissingClass { ontology, class } => json!({
+                "error": "missing_class",
+                "ontology": ontology.as_str(),
+                "class": class.as_str(),
+            }),
+            Self::MissingProperty { ontology, property } => json!({
+                "error": "missing_property",
+                "ontology": ontology.as_str(),
+                "property": property.as_str(),
+            }),
+            Self::InvalidPropertyAssertion { ontology, property } => json!({
+                "error": "invalid_property_assertion",
+                "ontology": ontology.as_str(),
+                "property": property.as_str(),
+            }),
+            Self::PropertyInUse {
+                ontology,
+                property,
+                individual,
+            } => json!({
+                "error": "property_in_use",
+                "ontology": ontology.as_str(),
+                "property": property.as_str(),
+                "individual": individual.as_str(),
+            }),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -488,4 +609,158 @@ mod tests {
             super::OntologyError::InvalidPropertyAssertion { .. }
         ));
     }
+
+    #[test]
+    fn duplicate_class_problem_detail_names_the_class() {
+        let mut ontology = Ontology::new(iri("https://example.org/onto"));
+        ontology
+            .add_class(Class::new(iri("https://example.org/Class")))
+            .expect("class inserted");
+
+        let err = ontology
+            .add_class(Class::new(iri("https://example.org/Class")))
+            .expect_err("duplicate class");
+
+        assert_eq!(
+            err.to_problem_detail(),
+            serde_json::json!({
+                "error": "duplicate_class",
+                "class": "https://example.org/Class",
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_property_assertion_problem_detail_carries_context() {
+        let err = super::OntologyError::InvalidPropertyAssertion {
+            ontology: iri("https://example.org/onto"),
+            property: iri("https://example.org/prop"),
+        };
+
+        assert_eq!(
+            err.to_problem_detail(),
+            serde_json::json!({
+                "error": "invalid_property_assertion",
+                "ontology": "https://example.org/onto",
+                "property": "https://example.org/prop",
+            })
+        );
+    }
+
+    fn linked_ontology() -> Ontology {
+        let mut ontology = Ontology::new(iri("https://example.org/onto"));
+        ontology
+            .add_property(Property::new(
+                iri("https://example.org/knows"),
+                PropertyKind::Object,
+            ))
+            .expect("property inserted");
+        ontology
+            .add_property(Property::new(
+                iri("https://example.org/unused"),
+                PropertyKind::Data,
+            ))
+            .expect("property inserted");
+
+        let mut alice = Individual::new(iri("https://example.org/alice"));
+        alice.add_property_assertion(
+            iri("https://example.org/knows"),
+            PropertyAssertion::Individual(iri("https://example.org/bob")),
+        );
+        ontology.add_individual(alice).expect("individual inserted");
+        ontology
+            .add_individual(Individual::new(iri("https://example.org/bob")))
+            .expect("individual inserted");
+        ontology
+    }
+
+    #[test]
+    fn asserted_properties_cannot_be_removed() {
+        let mut ontology = linked_ontology();
+
+        let err = ontology
+            .remove_property(&iri("https://example.org/knows"))
+            .expect_err("property in use");
+
+        assert_eq!(
+            err,
+            super::OntologyError::PropertyInUse {
+                ontology: iri("https://example.org/onto"),
+                property: iri("https://example.org/knows"),
+                individual: iri("https://example.org/alice"),
+            }
+        );
+        assert!(ontology
+            .property(&iri("https://example.org/knows"))
+            .is_some());
+    }
+
+    #[test]
+    fn unused_properties_and_individuals_are_removed() {
+        let mut ontology = linked_ontology();
+
+        let removed = ontology
+            .remove_property(&iri("https://example.org/unused"))
+            .expect("property removed");
+        assert_eq!(removed.id(), &iri("https://example.org/unused"));
+        assert!(ontology
+            .property(&iri("https://example.org/unused"))
+            .is_none());
+
+        let referrers = ontology
+            .remove_individual(&iri("https://example.org/bob"))
+            .expect("individual removed");
+        assert_eq!(
+            referrers.into_iter().collect::<Vec<_>>(),
+            vec![iri("https://example.org/alice")]
+        );
+        assert!(ontology
+            .individual(&iri("https://example.org/bob"))
+            .is_none());
+        assert_eq!(
+            ontology.remove_individual(&iri("https://example.org/bob")),
+            None
+        );
+    }
+
+    #[test]
+    fn object_edges_are_flattened_in_order() {
+        let mut ontology = linked_ontology();
+        let mut carol = Individual::new(iri("https://example.org/carol"));
+        carol.add_property_assertion(
+            iri("https://example.org/knows"),
+            PropertyAssertion::Individual(iri("https://example.org/bob")),
+        );
+        carol.add_property_assertion(
+            iri("https://example.org/knows"),
+            PropertyAssertion::Individual(iri("https://example.org/alice")),
+        );
+        carol.add_property_assertion(
+            iri("https://example.org/unused"),
+            PropertyAssertion::Literal("ignored".to_string()),
+        );
+        ontology.add_individual(carol).expect("individual inserted");
+
+        let knows = iri("https://example.org/knows");
+        assert_eq!(
+            ontology.object_edges(),
+            vec![
+                (
+                    iri("https://example.org/alice"),
+                    knows.clone(),
+                    iri("https://example.org/bob")
+                ),
+                (
+                    iri("https://example.org/carol"),
+                    knows.clone(),
+                    iri("https://example.org/alice")
+                ),
+                (
+                    iri("https://example.org/carol"),
+                    knows,
+                    iri("https://example.org/bob")
+                ),
+            ]
+        );
+    }
 }
@@ -0,0 +1,155 @@
+//! Hygiene checks over an ontology aggregate, reported as findings with a
+//! severity in the spirit of `cargo loco doctor`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{
+    entities::{Ontology, PropertyAssertion},
+    value_objects::Iri,
+};
+
+/// How serious a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FindingSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl FindingSeverity {
+    /// Lowercase name used when reporting the finding.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// A single problem reported by [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OntologyFinding {
+    pub severity: FindingSeverity,
+    /// Name of the check that raised the finding, e.g. `range_violation`.
+    pub check: &'static str,
+    /// Class or individual the finding is about.
+    pub subject: Iri,
+    pub message: String,
+}
+
+/// Runs every check against `ontology`:
+///
+/// - `cyclic_hierarchy` (error): a class is its own ancestor.
+/// - `orphan_class` (info): a class without parents, subclasses or
+///   individuals.
+/// - `domain_violation` (warning): an individual asserts a property without
+///   being typed with one of its domains, directly or through an ancestor.
+/// - `range_violation` (warning): an object assertion points at an individual
+///   that is not typed with one of the property's ranges.
+#[must_use]
+pub fn lint(ontology: &Ontology) -> Vec<OntologyFinding> {
+    let mut findings = Vec::new();
+    check_hierarchy(ontology, &mut findings);
+    check_assertions(ontology, &mut findings);
+    findings
+}
+
+fn check_hierarchy(ontology: &Ontology, findings: &mut Vec<OntologyFinding>) {
+    let mut has_children = BTreeSet::new();
+    for class in ontology.classes().values() {
+        has_children.extend(class.parents().iter().cloned());
+    }
+    let typed: BTreeSet<&Iri> = ontology
+        .individuals()
+        .values()
+        .flat_map(|individual| individual.types())
+        .collect();
+
+    for (id, class) in ontology.classes() {
+        if ancestors(ontology, class.parents()).contains(id) {
+            findings.push(OntologyFinding {
+                severity: FindingSeverity::Error,
+                check: "cyclic_hierarchy",
+                subject: id.clone(),
+                message: format!("class `{id}` is its own ancestor"),
+            });
+        }
+        if class.parents().is_empty() && !has_children.contains(id) && !typed.contains(id) {
+            findings.push(OntologyFinding {
+                severity: FindingSeverity::Info,
+                check: "orphan_class",
+                subject: id.clone(),
+                message: format!("class `{id}` has no parents, subclasses or individuals"),
+            });
+        }
+    }
+}
+
+fn check_assertions(ontology: &Ontology, findings: &mut Vec<OntologyFinding>) {
+    let mut types: BTreeMap<&Iri, BTreeSet<Iri>> = BTreeMap::new();
+    for (id, individual) in ontology.individuals() {
+        let mut closure = ancestors(ontology, individual.types());
+        closure.extend(individual.types().iter().cloned());
+        types.insert(id, closure);
+    }
+    let untyped = BTreeSet::new();
+
+    for (id, individual) in ontology.individuals() {
+        for (property_id, assertions) in individual.properties() {
+            let Some(property) = ontology.property(property_id) else {
+                continue;
+            };
+            if !property.domains().is_empty() && types[id].is_disjoint(property.domains()) {
+                findings.push(OntologyFinding {
+                    severity: FindingSeverity::Warning,
+                    check: "domain_violation",
+                    subject: id.clone(),
+                    message: format!(
+                        "individual `{id}` asserts `{property_id}` but is not in its domain"
+                    ),
+                });
+            }
+            if property.ranges().is_empty() {
+                continue;
+            }
+            for assertion in assertions {
+                let PropertyAssertion::Individual(target) = assertion else {
+                    continue;
+                };
+                let target_types = types.get(target).unwrap_or(&untyped);
+                if target_types.is_disjoint(property.ranges()) {
+                    findings.push(OntologyFinding {
+                        severity: FindingSeverity::Warning,
+                        check: "range_violation",
+                        subject: id.clone(),
+                        message: format!(
+                            "individual `{id}` points `{property_id}` at `{target}`, which is not \
+                             in its range"
+                        ),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Every class reachable through the parents of `classes`, excluding
+/// `classes` themselves unless a cycle leads back to them.
+fn ancestors(ontology: &Ontology, classes: &BTreeSet<Iri>) -> BTreeSet<Iri> {
+    let mut visited = BTreeSet::new();
+    let mut to_visit: Vec<&Iri> = classes
+        .iter()
+        .filter_map(|class| ontology.class(class))
+        .flat_map(|class| class.parents())
+        .collect();
+    while let Some(current) = to_visit.pop() {
+        if visited.insert(current.clone()) {
+            if let Some(class) = ontology.class(current) {
+                to_visit.extend(class.parents());
+            }
+        }
+    }
+    visited
+}
@@ -6,13 +6,18 @@
 //! describe the required infrastructure behavior.
 
 pub mod entities;
+pub mod lint;
 pub mod repositories;
+pub mod seed;
 pub mod service;
 pub mod value_objects;
 
 pub use entities::{
     Class, Individual, Ontology, OntologyError, Property, PropertyAssertion, PropertyKind,
 };
-pub use repositories::{OntologyRepository, OntologySnapshot, OntologySummary, ReasoningQuery};
+pub use lint::{FindingSeverity, OntologyFinding};
+pub use repositories::{
+    OntologyRepository, OntologySnapshot, OntologyStats, OntologySummary, ReasoningQuery,
+};
 pub use service::{OntologyService, OntologyServiceError};
-pub use value_objects::{Iri, IriError};
+pub use value_objects::{Iri, IriError, IriNormalization};
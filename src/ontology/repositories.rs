@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use async_trait::async_trait;
 
 use super::entities::{Class, Individual, Ontology, Property};
@@ -43,6 +45,75 @@ impl From<&Ontology> for OntologySummary {
     }
 }
 
+/// Distribution figures for dashboards, richer than [`OntologySummary`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OntologyStats {
+    /// Number of classes without declared parents.
+    pub classes_with_no_parents: usize,
+    /// Number of classes on the longest parent chain, `0` for an ontology
+    /// without classes.
+    pub max_hierarchy_depth: usize,
+    /// Individuals asserted to be of each class, including empty classes.
+    pub individuals_per_class: BTreeMap<Iri, usize>,
+}
+
+impl From<&Ontology> for OntologyStats {
+    fn from(ontology: &Ontology) -> Self {
+        let classes = ontology.classes();
+        let mut depths = BTreeMap::new();
+        let max_hierarchy_depth = classes
+            .keys()
+            .map(|class| hierarchy_depth(class, classes, &mut depths, &mut BTreeSet::new()))
+            .max()
+            .unwrap_or(0);
+
+        let mut individuals_per_class: BTreeMap<Iri, usize> =
+            classes.keys().map(|class| (class.clone(), 0)).collect();
+        for individual in ontology.individuals().values() {
+            for class in individual.types() {
+                *individuals_per_class.entry(class.clone()).or_default() += 1;
+            }
+        }
+
+        Self {
+            classes_with_no_parents: classes
+                .values()
+                .filter(|class| class.parents().is_empty())
+                .count(),
+            max_hierarchy_depth,
+            individuals_per_class,
+        }
+    }
+}
+
+/// Depth of `class` counted in classes up to its furthest root. Depths are
+/// memoised so shared parents are only walked once, and a parent already on
+/// the current chain is ignored so cycles terminate.
+fn hierarchy_depth(
+    class: &Iri,
+    classes: &BTreeMap<Iri, Class>,
+    depths: &mut BTreeMap<Iri, usize>,
+    chain: &mut BTreeSet<Iri>,
+) -> usize {
+    if let Some(depth) = depths.get(class) {
+        return *depth;
+    }
+    if !chain.insert(class.clone()) {
+        return 0;
+    }
+    let parents_depth = classes.get(class).map_or(0, |declared| {
+        declared
+            .parents()
+            .iter()
+            .map(|parent| hierarchy_depth(parent, classes, depths, chain))
+            .max()
+            .unwrap_or(0)
+    });
+    chain.remove(class);
+    depths.insert(class.clone(), parents_depth + 1);
+    parents_depth + 1
+}
+
 /// Contract describing persistence responsibilities for ontology aggregates.
 #[async_trait]
 pub trait OntologyRepository {
@@ -89,18 +160,51 @@ pub trait OntologyRepository {
     ) -> Result<(), Self::Error>;
 }
 
+/// Path between individuals where each hop records the property it followed.
+/// The first hop is the start individual and has no property.
+pub type LabeledPath = Vec<(Option<Iri>, Iri)>;
+
 /// Abstraction describing reasoning and traversal operations on ontology graphs.
 #[async_trait]
 pub trait ReasoningQuery {
     /// Associated error type allowing infrastructure specific failures.
     type Error;
 
-    /// Returns the transitive closure of all parent classes for a given class.
+    /// Returns the transitive closure of all parent classes for a given class,
+    /// sorted by IRI.
     async fn ancestors_of(&self, ontology: &Iri, class: &Iri) -> Result<Vec<Iri>, Self::Error>;
 
-    /// Returns the transitive closure of all child classes for a given class.
+    /// Returns the transitive closure of all child classes for a given class,
+    /// sorted by IRI.
     async fn descendants_of(&self, ontology: &Iri, class: &Iri) -> Result<Vec<Iri>, Self::Error>;
 
+    /// Returns how many classes [`Self::ancestors_of`] would return.
+    async fn ancestor_count(&self, ontology: &Iri, class: &Iri) -> Result<usize, Self::Error> {
+        Ok(self.ancestors_of(ontology, class).await?.len())
+    }
+
+    /// Returns `limit` ancestors starting at `offset` from the same ordering
+    /// as [`Self::ancestors_of`], and whether more ancestors follow the page.
+    async fn ancestors_page(
+        &self,
+        ontology: &Iri,
+        class: &Iri,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Iri>, bool), Self::Error> {
+        let ancestors = self.ancestors_of(ontology, class).await?;
+        let has_more = ancestors.len() > offset.saturating_add(limit);
+        Ok((
+            ancestors.into_iter().skip(offset).take(limit).collect(),
+            has_more,
+        ))
+    }
+
+    /// Returns how many classes [`Self::descendants_of`] would return.
+    async fn descendant_count(&self, ontology: &Iri, class: &Iri) -> Result<usize, Self::Error> {
+        Ok(self.descendants_of(ontology, class).await?.len())
+    }
+
     /// Returns individuals connected to the supplied source via the provided property.
     async fn related_individuals(
         &self,
@@ -109,18 +213,41 @@ pub trait ReasoningQuery {
         individual: &Iri,
     ) -> Result<Vec<Iri>, Self::Error>;
 
+    /// Returns the literal values asserted for an individual through a data
+    /// property.
+    async fn literal_values(
+        &self,
+        ontology: &Iri,
+        via_property: &Iri,
+        individual: &Iri,
+    ) -> Result<Vec<String>, Self::Error>;
+
     /// Returns the shortest property path between two individuals, if one exists.
+    ///
+    /// The path includes both endpoints, so when `start == end` it is
+    /// `Some(vec![start])`.
     async fn shortest_path(
         &self,
         ontology: &Iri,
         start: &Iri,
         end: &Iri,
     ) -> Result<Option<Vec<Iri>>, Self::Error>;
+
+    /// Same as [`Self::shortest_path`], but also reports the property used to
+    /// reach each individual along the path.
+    async fn shortest_path_labeled(
+        &self,
+        ontology: &Iri,
+        start: &Iri,
+        end: &Iri,
+    ) -> Result<Option<LabeledPath>, Self::Error>;
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{OntologyRepository, OntologySnapshot, OntologySummary, ReasoningQuery};
+    use super::{
+        LabeledPath, OntologyRepository, OntologySnapshot, OntologySummary, ReasoningQuery,
+    };
     use crate::ontology::entities::{
         Class, Individual, Ontology, Property, PropertyAssertion, PropertyKind,
     };
@@ -307,12 +434,45 @@ mod tests {
             Ok(results.into_iter().collect())
         }
 
+        async fn literal_values(
+            &self,
+            ontology: &Iri,
+            via_property: &Iri,
+            individual: &Iri,
+        ) -> Result<Vec<String>, Self::Error> {
+            let guard = self.store.lock().unwrap();
+            let ontology = guard.get(ontology).ok_or(TestError::Missing)?;
+            let source = ontology
+                .individual(individual)
+                .ok_or_else(|| TestError::Domain(format!("individual {individual} missing")))?;
+            Ok(source
+                .properties()
+                .get(via_property)
+                .into_iter()
+                .flatten()
+                .filter_map(|assertion| match assertion {
+                    PropertyAssertion::Literal(value) => Some(value.clone()),
+                    PropertyAssertion::Individual(_) => None,
+                })
+                .collect())
+        }
+
         async fn shortest_path(
             &self,
             ontology: &Iri,
             start: &Iri,
             end: &Iri,
         ) -> Result<Option<Vec<Iri>>, Self::Error> {
+            let path = self.shortest_path_labeled(ontology, start, end).await?;
+            Ok(path.map(|hops| hops.into_iter().map(|(_, target)| target).collect()))
+        }
+
+        async fn shortest_path_labeled(
+            &self,
+            ontology: &Iri,
+            start: &Iri,
+            end: &Iri,
+        ) -> Result<Option<LabeledPath>, Self::Error> {
             let guard = self.store.lock().unwrap();
             let ontology = guard.get(ontology).ok_or(TestError::Missing)?;
             let Some(_) = ontology.individual(start) else {
@@ -322,7 +482,8 @@ mod tests {
                 return Err(TestError::Domain(format!("individual {end} missing")));
             };
 
-            let mut queue = VecDeque::from([(start.clone(), vec![start.clone()])]);
+            let mut queue: VecDeque<(Iri, LabeledPath)> =
+                VecDeque::from([(start.clone(), vec![(None, start.clone())])]);
             let mut visited = BTreeSet::from([start.clone()]);
             while let Some((current, path)) = queue.pop_front() {
                 if current == *end {
@@ -340,7 +501,7 @@ mod tests {
                             if let PropertyAssertion::Individual(target) = assertion {
                                 if visited.insert(target.clone()) {
                                     let mut next_path = path.clone();
-                                    next_path.push(target.clone());
+                                    next_path.push((Some(property_id.clone()), target.clone()));
                                     queue.push_back((target.clone(), next_path));
                                 }
                             }
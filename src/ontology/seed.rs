@@ -0,0 +1,240 @@
+//! Loading of ontology seed files configured under `ontology.seeds`.
+//!
+//! Seeds are JSON documents describing a single ontology:
+//!
+//! ```json
+//! {
+//!   "iri": "https://example.org/catalog",
+//!   "label": "Catalog",
+//!   "classes": [
+//!     { "iri": "https://example.org/Product" },
+//!     { "iri": "https://example.org/Book", "parents": ["https://example.org/Product"] }
+//!   ],
+//!   "properties": [{
+//!     "iri": "https://example.org/relatedTo",
+//!     "kind": "object",
+//!     "domain": ["https://example.org/Product"],
+//!     "range": ["https://example.org/Product"]
+//!   }],
+//!   "individuals": [{
+//!     "iri": "https://example.org/dune",
+//!     "types": ["https://example.org/Book"],
+//!     "assertions": {
+//!       "https://example.org/relatedTo": [{ "individual": "https://example.org/foundation" }]
+//!     }
+//!   }]
+//! }
+//! ```
+//!
+//! Directory seeds are walked recursively and every `.json` file is loaded in
+//! path order. Turtle (`.ttl`) files are skipped with a warning until they are
+//! supported; other files are ignored.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use super::{
+    entities::{Class, Individual, Ontology, Property, PropertyAssertion, PropertyKind},
+    service::OntologyServiceError,
+    value_objects::Iri,
+};
+
+#[derive(Deserialize)]
+struct SeedDocument {
+    iri: String,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    classes: Vec<SeedClass>,
+    #[serde(default)]
+    properties: Vec<SeedProperty>,
+    #[serde(default)]
+    individuals: Vec<SeedIndividual>,
+}
+
+#[derive(Deserialize)]
+struct SeedClass {
+    iri: String,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    parents: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SeedPropertyKind {
+    Object,
+    Data,
+}
+
+#[derive(Deserialize)]
+struct SeedProperty {
+    iri: String,
+    kind: SeedPropertyKind,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    domain: Vec<String>,
+    #[serde(default)]
+    range: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SeedAssertion {
+    Individual(String),
+    Literal(String),
+}
+
+#[derive(Deserialize)]
+struct SeedIndividual {
+    iri: String,
+    #[serde(default)]
+    types: Vec<String>,
+    #[serde(default)]
+    assertions: BTreeMap<String, Vec<SeedAssertion>>,
+}
+
+/// Returns the seed files for a configured seed path, sorted by path.
+///
+/// A file is returned as-is when it has a seed extension. Directories are
+/// walked recursively.
+///
+/// # Errors
+///
+/// Returns [`OntologyServiceError::SeedIo`] when the path cannot be read.
+pub fn seed_files(path: &Path) -> Result<Vec<PathBuf>, OntologyServiceError> {
+    let mut files = Vec::new();
+    if path.is_dir() {
+        collect_dir(path, &mut files)?;
+        files.sort();
+    } else if is_seed_file(path) {
+        files.push(path.to_path_buf());
+    }
+    Ok(files)
+}
+
+fn collect_dir(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), OntologyServiceError> {
+    let entries = fs::read_dir(dir).map_err(|source| seed_io(dir, source))?;
+    for entry in entries {
+        let path = entry.map_err(|source| seed_io(dir, source))?.path();
+        if path.is_dir() {
+            collect_dir(&path, files)?;
+        } else if is_seed_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_seed_file(path: &Path) -> bool {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => true,
+        Some("ttl") => {
+            tracing::warn!(
+                path = %path.display(),
+                "skipping Turtle ontology seed, only JSON seeds are supported"
+            );
+            false
+        }
+        _ => false,
+    }
+}
+
+/// Reads and parses a single seed file into an ontology aggregate.
+///
+/// # Errors
+///
+/// Returns [`OntologyServiceError::SeedIo`] when the file cannot be read,
+/// [`OntologyServiceError::InvalidSeed`] when it is not a valid seed document
+/// and [`OntologyServiceError::Domain`] when the described ontology is
+/// inconsistent.
+pub fn load_seed_file(path: &Path) -> Result<Ontology, OntologyServiceError> {
+    if path.extension().and_then(|extension| extension.to_str()) == Some("ttl") {
+        return Err(invalid(path, "Turtle seeds are not supported yet"));
+    }
+    let content = fs::read_to_string(path).map_err(|source| seed_io(path, source))?;
+    let document: SeedDocument =
+        serde_json::from_str(&content).map_err(|err| invalid(path, err))?;
+    build_ontology(path, document)
+}
+
+fn build_ontology(path: &Path, document: SeedDocument) -> Result<Ontology, OntologyServiceError> {
+    let parse = |value: &str| Iri::new(value).map_err(|err| invalid(path, err));
+
+    let mut ontology = Ontology::new(parse(&document.iri)?);
+    if let Some(label) = document.label {
+        ontology = ontology.with_label(label);
+    }
+
+    for seed in document.classes {
+        let mut class = Class::new(parse(&seed.iri)?);
+        if let Some(label) = seed.label {
+            class = class.with_label(label);
+        }
+        for parent in &seed.parents {
+            class.add_parent(parse(parent)?);
+        }
+        ontology.add_class(class)?;
+    }
+
+    for seed in document.properties {
+        let kind = match seed.kind {
+            SeedPropertyKind::Object => PropertyKind::Object,
+            SeedPropertyKind::Data => PropertyKind::Data,
+        };
+        let mut property = Property::new(parse(&seed.iri)?, kind);
+        if let Some(label) = seed.label {
+            property = property.with_label(label);
+        }
+        for class in &seed.domain {
+            property.add_domain(parse(class)?);
+        }
+        for class in &seed.range {
+            property.add_range(parse(class)?);
+        }
+        ontology.add_property(property)?;
+    }
+
+    for seed in document.individuals {
+        let mut individual = Individual::new(parse(&seed.iri)?);
+        for class in &seed.types {
+            individual.assert_type(parse(class)?);
+        }
+        for (property, assertions) in seed.assertions {
+            let property = parse(&property)?;
+            for assertion in assertions {
+                let assertion = match assertion {
+                    SeedAssertion::Individual(target) => {
+                        PropertyAssertion::Individual(parse(&target)?)
+                    }
+                    SeedAssertion::Literal(value) => PropertyAssertion::Literal(value),
+                };
+                individual.add_property_assertion(property.clone(), assertion);
+            }
+        }
+        ontology.add_individual(individual)?;
+    }
+
+    Ok(ontology)
+}
+
+fn seed_io(path: &Path, source: io::Error) -> OntologyServiceError {
+    OntologyServiceError::SeedIo {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+fn invalid(path: &Path, reason: impl ToString) -> OntologyServiceError {
+    OntologyServiceError::InvalidSeed {
+        path: path.to_path_buf(),
+        reason: reason.to_string(),
+    }
+}
@@ -11,7 +11,12 @@ use crate::{
     config::{OntologyBackend, OntologySettings, ReasonerBackend, ReasonerSettings},
     ontology::{
         entities::{Class, Individual, Ontology, OntologyError, Property, PropertyAssertion},
-        repositories::{OntologyRepository, OntologySnapshot, OntologySummary, ReasoningQuery},
+        lint::{self, OntologyFinding},
+        repositories::{
+            LabeledPath, OntologyRepository, OntologySnapshot, OntologyStats, OntologySummary,
+            ReasoningQuery,
+        },
+        seed,
         value_objects::Iri,
     },
 };
@@ -28,6 +33,7 @@ pub struct OntologyService {
     repository: Arc<RepositoryHandle>,
     reasoner: Arc<ReasonerHandle>,
     reasoner_settings: ReasonerSettings,
+    seeds: Vec<PathBuf>,
 }
 
 impl OntologyService {
@@ -41,6 +47,7 @@ impl OntologyService {
             repository,
             reasoner,
             reasoner_settings,
+            seeds: Vec::new(),
         }
     }
 
@@ -59,7 +66,49 @@ impl OntologyService {
             ReasonerBackend::Native => Arc::new(InMemoryReasoner::new(store, reasoner.clone())),
         };
 
-        Ok(Self::new(repository, reasoner_adapter, reasoner.clone()))
+        Ok(Self {
+            seeds: ontology.seeds.clone(),
+            ..Self::new(repository, reasoner_adapter, reasoner.clone())
+        })
+    }
+
+    /// Builds a service over the in-memory repository and reasoner, preloaded
+    /// with `ontologies`, for tests that need real reasoning without seed
+    /// files.
+    #[cfg(any(test, feature = "testing"))]
+    #[must_use]
+    pub fn in_memory(ontologies: Vec<Ontology>) -> Self {
+        let store = Arc::new(InMemoryStore::default());
+        {
+            let mut guard = store.guard();
+            for ontology in ontologies {
+                guard.insert(ontology.id().clone(), ontology);
+            }
+        }
+        let settings = ReasonerSettings::default();
+        Self::new(
+            Arc::new(InMemoryOntologyRepository::new(store.clone())),
+            Arc::new(InMemoryReasoner::new(store, settings.clone())),
+            settings,
+        )
+    }
+
+    /// Reloads the configured seed paths, replacing ontologies that share an
+    /// identifier with a seed. Returns how many ontologies were loaded.
+    ///
+    /// Seeds are parsed before anything is written, so a broken seed leaves
+    /// the repository untouched.
+    pub async fn reload_seeds(&self) -> Result<usize, OntologyServiceError> {
+        let ontologies = load_seeds(&self.seeds)?;
+        let loaded = ontologies.len();
+        for ontology in ontologies {
+            if self.repository.get(ontology.id()).await?.is_some() {
+                self.repository.update(ontology).await?;
+            } else {
+                self.repository.insert(ontology).await?;
+            }
+        }
+        Ok(loaded)
     }
 
     /// Returns a clone of the repository handle.
@@ -76,6 +125,110 @@ impl OntologyService {
     pub fn reasoner_settings(&self) -> &ReasonerSettings {
         &self.reasoner_settings
     }
+
+    /// Computes hierarchy and population figures for the ontology `iri`.
+    pub async fn stats(&self, iri: &Iri) -> Result<OntologyStats, OntologyServiceError> {
+        let snapshot = self
+            .repository
+            .get(iri)
+            .await?
+            .ok_or_else(|| OntologyServiceError::missing(iri))?;
+        Ok(OntologyStats::from(&snapshot.ontology))
+    }
+
+    /// Runs the [`lint::lint`] hygiene checks against the ontology `iri`.
+    pub async fn lint(&self, iri: &Iri) -> Result<Vec<OntologyFinding>, OntologyServiceError> {
+        let snapshot = self
+            .repository
+            .get(iri)
+            .await?
+            .ok_or_else(|| OntologyServiceError::missing(iri))?;
+        Ok(lint::lint(&snapshot.ontology))
+    }
+
+    /// Returns every ancestor class of `class`.
+    pub async fn ancestors(
+        &self,
+        ontology: &Iri,
+        class: &Iri,
+    ) -> Result<Vec<Iri>, OntologyServiceError> {
+        self.reasoner.ancestors_of(ontology, class).await
+    }
+
+    /// Returns every descendant class of `class`.
+    pub async fn descendants(
+        &self,
+        ontology: &Iri,
+        class: &Iri,
+    ) -> Result<Vec<Iri>, OntologyServiceError> {
+        self.reasoner.descendants_of(ontology, class).await
+    }
+
+    /// Returns how many ancestor classes `class` has.
+    pub async fn ancestor_count(
+        &self,
+        ontology: &Iri,
+        class: &Iri,
+    ) -> Result<usize, OntologyServiceError> {
+        self.reasoner.ancestor_count(ontology, class).await
+    }
+
+    /// Returns one page of the ancestors of `class` and whether more follow.
+    pub async fn ancestors_page(
+        &self,
+        ontology: &Iri,
+        class: &Iri,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Iri>, bool), OntologyServiceError> {
+        self.reasoner
+            .ancestors_page(ontology, class, offset, limit)
+            .await
+    }
+
+    /// Returns how many descendant classes `class` has.
+    pub async fn descendant_count(
+        &self,
+        ontology: &Iri,
+        class: &Iri,
+    ) -> Result<usize, OntologyServiceError> {
+        self.reasoner.descendant_count(ontology, class).await
+    }
+
+    /// Returns the individuals `individual` points to through `via_property`.
+    pub async fn related(
+        &self,
+        ontology: &Iri,
+        via_property: &Iri,
+        individual: &Iri,
+    ) -> Result<Vec<Iri>, OntologyServiceError> {
+        self.reasoner
+            .related_individuals(ontology, via_property, individual)
+            .await
+    }
+
+    /// Returns the literal values `individual` holds for the data property
+    /// `via_property`.
+    pub async fn literal_values(
+        &self,
+        ontology: &Iri,
+        via_property: &Iri,
+        individual: &Iri,
+    ) -> Result<Vec<String>, OntologyServiceError> {
+        self.reasoner
+            .literal_values(ontology, via_property, individual)
+            .await
+    }
+
+    /// Returns the shortest property path from `start` to `end`, if any.
+    pub async fn shortest_path(
+        &self,
+        ontology: &Iri,
+        start: &Iri,
+        end: &Iri,
+    ) -> Result<Option<Vec<Iri>>, OntologyServiceError> {
+        self.reasoner.shortest_path(ontology, start, end).await
+    }
 }
 
 /// Errors raised by ontology infrastructure components.
@@ -99,12 +252,19 @@ pub enum OntologyServiceError {
     /// Domain validation failed when mutating the ontology aggregate.
     #[error("domain error: {0}")]
     Domain(#[from] OntologyError),
+    /// The requested query relies on an inference toggle that is switched
+    /// off in `reasoner.inference`.
+    #[error("{inference} inference disabled")]
+    InferenceDisabled { inference: &'static str },
     /// Accessing a configured ontology seed path failed.
     #[error("failed to access ontology seed `{path}`: {source}")]
     SeedIo {
         path: PathBuf,
         source: std::io::Error,
     },
+    /// A seed file could not be parsed into an ontology.
+    #[error("invalid ontology seed `{path}`: {reason}")]
+    InvalidSeed { path: PathBuf, reason: String },
 }
 
 impl OntologyServiceError {
@@ -153,6 +313,15 @@ impl InMemoryStore {
             .lock()
             .expect("in-memory ontology store poisoned")
     }
+
+    /// Clones the requested ontology so callers can traverse it without
+    /// holding the store lock.
+    fn snapshot(&self, ontology: &Iri) -> Result<Ontology, OntologyServiceError> {
+        self.guard()
+            .get(ontology)
+            .cloned()
+            .ok_or_else(|| OntologyServiceError::missing(ontology))
+    }
 }
 
 #[derive(Clone)]
@@ -165,14 +334,30 @@ impl InMemoryOntologyRepository {
         Self { store }
     }
 
-    fn preload(&self, seeds: &[PathBuf]) -> Result<(), OntologyServiceError> {
-        for path in seeds {
-            validate_seed_path(path)?;
+    /// Loads every ontology found in the seed paths, replacing aggregates
+    /// that share an identifier. Returns how many ontologies were loaded.
+    fn preload(&self, seeds: &[PathBuf]) -> Result<usize, OntologyServiceError> {
+        let ontologies = load_seeds(seeds)?;
+        let loaded = ontologies.len();
+        for ontology in ontologies {
+            self.store.guard().insert(ontology.id().clone(), ontology);
         }
-        Ok(())
+        Ok(loaded)
     }
 }
 
+/// Parses every ontology found in the seed paths.
+fn load_seeds(seeds: &[PathBuf]) -> Result<Vec<Ontology>, OntologyServiceError> {
+    let mut ontologies = Vec::new();
+    for path in seeds {
+        validate_seed_path(path)?;
+        for file in seed::seed_files(path)? {
+            ontologies.push(seed::load_seed_file(&file)?);
+        }
+    }
+    Ok(ontologies)
+}
+
 fn validate_seed_path(path: &Path) -> Result<(), OntologyServiceError> {
     if path.exists() {
         if path.is_file() || path.is_dir() {
@@ -283,48 +468,53 @@ impl InMemoryReasoner {
     fn new(store: Arc<InMemoryStore>, settings: ReasonerSettings) -> Self {
         Self { store, settings }
     }
-}
-
-#[async_trait]
-impl ReasoningQuery for InMemoryReasoner {
-    type Error = OntologyServiceError;
 
-    async fn ancestors_of(&self, ontology: &Iri, class: &Iri) -> Result<Vec<Iri>, Self::Error> {
+    /// Visits every ancestor of `class` once, in breadth-first order. Callers
+    /// returning the closure sort it by IRI.
+    fn walk_ancestors(
+        &self,
+        ontology: &Iri,
+        class: &Iri,
+        mut visit: impl FnMut(&Iri),
+    ) -> Result<(), OntologyServiceError> {
         if !self.settings.inference.class_hierarchy {
-            return Ok(vec![]);
+            return Err(OntologyServiceError::InferenceDisabled {
+                inference: "class_hierarchy",
+            });
         }
-        let guard = self.store.guard();
-        let Some(ontology) = guard.get(ontology) else {
-            return Err(OntologyServiceError::missing(ontology));
-        };
+        let ontology = self.store.snapshot(ontology)?;
         let Some(start) = ontology.class(class) else {
             return Err(OntologyServiceError::missing_class(ontology.id(), class));
         };
 
         let mut visited = BTreeSet::new();
         let mut to_visit: VecDeque<Iri> = start.parents().iter().cloned().collect();
-        let mut result = Vec::new();
 
         while let Some(current) = to_visit.pop_front() {
             if visited.insert(current.clone()) {
-                result.push(current.clone());
+                visit(&current);
                 if let Some(parent) = ontology.class(&current) {
                     to_visit.extend(parent.parents().iter().cloned());
                 }
             }
         }
 
-        Ok(result)
+        Ok(())
     }
 
-    async fn descendants_of(&self, ontology: &Iri, class: &Iri) -> Result<Vec<Iri>, Self::Error> {
+    /// Visits every descendant of `class` once, in breadth-first order.
+    fn walk_descendants(
+        &self,
+        ontology: &Iri,
+        class: &Iri,
+        mut visit: impl FnMut(&Iri),
+    ) -> Result<(), OntologyServiceError> {
         if !self.settings.inference.class_hierarchy {
-            return Ok(vec![]);
+            return Err(OntologyServiceError::InferenceDisabled {
+                inference: "class_hierarchy",
+            });
         }
-        let guard = self.store.guard();
-        let Some(ontology) = guard.get(ontology) else {
-            return Err(OntologyServiceError::missing(ontology));
-        };
+        let ontology = self.store.snapshot(ontology)?;
         if ontology.class(class).is_none() {
             return Err(OntologyServiceError::missing_class(ontology.id(), class));
         }
@@ -345,19 +535,69 @@ impl ReasoningQuery for InMemoryReasoner {
             to_visit.extend(children.iter().cloned());
         }
 
-        let mut result = Vec::new();
         while let Some(current) = to_visit.pop_front() {
             if visited.insert(current.clone()) {
-                result.push(current.clone());
+                visit(&current);
                 if let Some(children) = children_map.get(&current) {
                     to_visit.extend(children.iter().cloned());
                 }
             }
         }
 
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReasoningQuery for InMemoryReasoner {
+    type Error = OntologyServiceError;
+
+    async fn ancestors_of(&self, ontology: &Iri, class: &Iri) -> Result<Vec<Iri>, Self::Error> {
+        let mut result = Vec::new();
+        self.walk_ancestors(ontology, class, |ancestor| result.push(ancestor.clone()))?;
+        result.sort();
+        Ok(result)
+    }
+
+    async fn descendants_of(&self, ontology: &Iri, class: &Iri) -> Result<Vec<Iri>, Self::Error> {
+        let mut result = Vec::new();
+        self.walk_descendants(ontology, class, |descendant| {
+            result.push(descendant.clone());
+        })?;
+        result.sort();
         Ok(result)
     }
 
+    async fn ancestor_count(&self, ontology: &Iri, class: &Iri) -> Result<usize, Self::Error> {
+        let mut count = 0;
+        self.walk_ancestors(ontology, class, |_| count += 1)?;
+        Ok(count)
+    }
+
+    async fn ancestors_page(
+        &self,
+        ontology: &Iri,
+        class: &Iri,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Iri>, bool), Self::Error> {
+        let mut ancestors = BTreeSet::new();
+        self.walk_ancestors(ontology, class, |ancestor| {
+            ancestors.insert(ancestor.clone());
+        })?;
+        let has_more = ancestors.len() > offset.saturating_add(limit);
+        Ok((
+            ancestors.into_iter().skip(offset).take(limit).collect(),
+            has_more,
+        ))
+    }
+
+    async fn descendant_count(&self, ontology: &Iri, class: &Iri) -> Result<usize, Self::Error> {
+        let mut count = 0;
+        self.walk_descendants(ontology, class, |_| count += 1)?;
+        Ok(count)
+    }
+
     async fn related_individuals(
         &self,
         ontology: &Iri,
@@ -365,12 +605,11 @@ impl ReasoningQuery for InMemoryReasoner {
         individual: &Iri,
     ) -> Result<Vec<Iri>, Self::Error> {
         if !self.settings.inference.property_assertions {
-            return Ok(vec![]);
+            return Err(OntologyServiceError::InferenceDisabled {
+                inference: "property_assertions",
+            });
         }
-        let guard = self.store.guard();
-        let Some(ontology) = guard.get(ontology) else {
-            return Err(OntologyServiceError::missing(ontology));
-        };
+        let ontology = self.store.snapshot(ontology)?;
         if ontology.property(via_property).is_none() {
             return Err(OntologyServiceError::missing_property(
                 ontology.id(),
@@ -399,19 +638,68 @@ impl ReasoningQuery for InMemoryReasoner {
         Ok(related)
     }
 
+    async fn literal_values(
+        &self,
+        ontology: &Iri,
+        via_property: &Iri,
+        individual: &Iri,
+    ) -> Result<Vec<String>, Self::Error> {
+        if !self.settings.inference.property_assertions {
+            return Err(OntologyServiceError::InferenceDisabled {
+                inference: "property_assertions",
+            });
+        }
+        let ontology = self.store.snapshot(ontology)?;
+        if ontology.property(via_property).is_none() {
+            return Err(OntologyServiceError::missing_property(
+                ontology.id(),
+                via_property,
+            ));
+        }
+        let Some(individual) = ontology.individual(individual) else {
+            return Err(OntologyServiceError::missing_individual(
+                ontology.id(),
+                individual,
+            ));
+        };
+
+        let values = individual
+            .properties()
+            .get(via_property)
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|assertion| match assertion {
+                PropertyAssertion::Literal(value) => Some(value),
+                PropertyAssertion::Individual(_) => None,
+            })
+            .collect();
+
+        Ok(values)
+    }
+
     async fn shortest_path(
         &self,
         ontology: &Iri,
         start: &Iri,
         end: &Iri,
     ) -> Result<Option<Vec<Iri>>, Self::Error> {
+        let path = self.shortest_path_labeled(ontology, start, end).await?;
+        Ok(path.map(|hops| hops.into_iter().map(|(_, target)| target).collect()))
+    }
+
+    async fn shortest_path_labeled(
+        &self,
+        ontology: &Iri,
+        start: &Iri,
+        end: &Iri,
+    ) -> Result<Option<LabeledPath>, Self::Error> {
         if !self.settings.inference.property_paths {
-            return Ok(None);
+            return Err(OntologyServiceError::InferenceDisabled {
+                inference: "property_paths",
+            });
         }
-        let guard = self.store.guard();
-        let Some(ontology) = guard.get(ontology) else {
-            return Err(OntologyServiceError::missing(ontology));
-        };
+        let ontology = self.store.snapshot(ontology)?;
         let Some(source) = ontology.individual(start) else {
             return Err(OntologyServiceError::missing_individual(
                 ontology.id(),
@@ -421,10 +709,14 @@ impl ReasoningQuery for InMemoryReasoner {
         if ontology.individual(end).is_none() {
             return Err(OntologyServiceError::missing_individual(ontology.id(), end));
         }
+        // An individual is trivially connected to itself by a zero-hop path.
+        if start == end {
+            return Ok(Some(vec![(None, start.clone())]));
+        }
 
         let mut visited = BTreeSet::from([source.id().clone()]);
-        let mut queue: VecDeque<(Iri, Vec<Iri>)> =
-            VecDeque::from([(source.id().clone(), vec![source.id().clone()])]);
+        let mut queue: VecDeque<(Iri, LabeledPath)> =
+            VecDeque::from([(source.id().clone(), vec![(None, source.id().clone())])]);
 
         while let Some((current, path)) = queue.pop_front() {
             if current == *end {
@@ -446,7 +738,7 @@ impl ReasoningQuery for InMemoryReasoner {
                         if let PropertyAssertion::Individual(next) = assertion {
                             if visited.insert(next.clone()) {
                                 let mut next_path = path.clone();
-                                next_path.push(next.clone());
+                                next_path.push((Some(property_id.clone()), next.clone()));
                                 queue.push_back((next.clone(), next_path));
                             }
                         }
@@ -458,3 +750,561 @@ impl ReasoningQuery for InMemoryReasoner {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::{entities::PropertyKind, lint::FindingSeverity};
+
+    fn iri(text: &str) -> Iri {
+        Iri::new(text).expect("valid iri")
+    }
+
+    async fn seeded_service() -> OntologyService {
+        let service = OntologyService::from_config(
+            &OntologySettings::default(),
+            &ReasonerSettings::default(),
+        )
+        .expect("ontology service");
+
+        let mut ontology = Ontology::new(iri("https://example.org/onto"));
+        let mut derived = Class::new(iri("https://example.org/Derived"));
+        derived.add_parent(iri("https://example.org/Base"));
+        ontology
+            .add_class(Class::new(iri("https://example.org/Base")))
+            .unwrap();
+        ontology.add_class(derived).unwrap();
+
+        let mut knows = Property::new(iri("https://example.org/knows"), PropertyKind::Object);
+        knows.add_domain(iri("https://example.org/Base"));
+        knows.add_range(iri("https://example.org/Base"));
+        ontology.add_property(knows).unwrap();
+
+        let mut name = Property::new(iri("https://example.org/name"), PropertyKind::Data);
+        name.add_domain(iri("https://example.org/Base"));
+        ontology.add_property(name).unwrap();
+
+        let mut alice = Individual::new(iri("https://example.org/Alice"));
+        alice.assert_type(iri("https://example.org/Base"));
+        alice.add_property_assertion(
+            iri("https://example.org/knows"),
+            PropertyAssertion::Individual(iri("https://example.org/Bob")),
+        );
+        alice.add_property_assertion(
+            iri("https://example.org/name"),
+            PropertyAssertion::Literal("Alice Liddell".to_string()),
+        );
+        ontology.add_individual(alice).unwrap();
+        let mut mentors = Property::new(iri("https://example.org/mentors"), PropertyKind::Object);
+        mentors.add_domain(iri("https://example.org/Base"));
+        mentors.add_range(iri("https://example.org/Base"));
+        ontology.add_property(mentors).unwrap();
+        let mut bob = Individual::new(iri("https://example.org/Bob"));
+        bob.assert_type(iri("https://example.org/Base"));
+        bob.add_property_assertion(
+            iri("https://example.org/mentors"),
+            PropertyAssertion::Individual(iri("https://example.org/Carol")),
+        );
+        ontology.add_individual(bob).unwrap();
+        let mut carol = Individual::new(iri("https://example.org/Carol"));
+        carol.assert_type(iri("https://example.org/Base"));
+        ontology.add_individual(carol).unwrap();
+
+        service.repository().insert(ontology).await.unwrap();
+        service
+    }
+
+    #[tokio::test]
+    async fn shortest_path_from_an_individual_to_itself_is_a_single_hop() {
+        let service = seeded_service().await;
+        let alice = iri("https://example.org/Alice");
+
+        assert_eq!(
+            service
+                .shortest_path(&iri("https://example.org/onto"), &alice, &alice)
+                .await
+                .unwrap(),
+            Some(vec![alice])
+        );
+    }
+
+    #[tokio::test]
+    async fn shortest_path_between_disconnected_individuals_is_none() {
+        let service = seeded_service().await;
+
+        assert_eq!(
+            service
+                .shortest_path(
+                    &iri("https://example.org/onto"),
+                    &iri("https://example.org/Bob"),
+                    &iri("https://example.org/Alice")
+                )
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn labeled_shortest_path_records_each_property() {
+        let service = seeded_service().await;
+        let onto = iri("https://example.org/onto");
+
+        let path = service
+            .reasoner()
+            .shortest_path_labeled(
+                &onto,
+                &iri("https://example.org/Alice"),
+                &iri("https://example.org/Carol"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            path,
+            Some(vec![
+                (None, iri("https://example.org/Alice")),
+                (
+                    Some(iri("https://example.org/knows")),
+                    iri("https://example.org/Bob")
+                ),
+                (
+                    Some(iri("https://example.org/mentors")),
+                    iri("https://example.org/Carol")
+                ),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn preload_loads_every_seed_in_a_directory() {
+        let tree = tree_fs::TreeBuilder::default()
+            .add_file(
+                "seeds/catalog.json",
+                r#"{
+                    "iri": "https://example.org/catalog",
+                    "classes": [
+                        { "iri": "https://example.org/Product" },
+                        { "iri": "https://example.org/Book", "parents": ["https://example.org/Product"] }
+                    ]
+                }"#,
+            )
+            .add_file(
+                "seeds/nested/people.json",
+                r#"{
+                    "iri": "https://example.org/people",
+                    "classes": [{ "iri": "https://example.org/Person" }],
+                    "individuals": [{
+                        "iri": "https://example.org/Alice",
+                        "types": ["https://example.org/Person"]
+                    }]
+                }"#,
+            )
+            .add_file("seeds/README.md", "not an ontology")
+            .add_file("seeds/legacy.ttl", "@prefix ex: <https://example.org/> .")
+            .create()
+            .expect("temp directory");
+
+        let settings = OntologySettings {
+            seeds: vec![tree.root.join("seeds")],
+            ..OntologySettings::default()
+        };
+        let service = OntologyService::from_config(&settings, &ReasonerSettings::default())
+            .expect("ontology service");
+
+        assert_eq!(
+            service
+                .ancestors(
+                    &iri("https://example.org/catalog"),
+                    &iri("https://example.org/Book")
+                )
+                .await
+                .unwrap(),
+            vec![iri("https://example.org/Product")]
+        );
+        let people = service
+            .repository()
+            .get(&iri("https://example.org/people"))
+            .await
+            .unwrap()
+            .expect("people ontology loaded");
+        assert!(people
+            .ontology
+            .individual(&iri("https://example.org/Alice"))
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn reload_seeds_picks_up_new_files() {
+        let tree = tree_fs::TreeBuilder::default()
+            .add_file(
+                "seeds/catalog.json",
+                r#"{ "iri": "https://example.org/catalog" }"#,
+            )
+            .create()
+            .expect("temp directory");
+        let settings = OntologySettings {
+            seeds: vec![tree.root.join("seeds")],
+            ..OntologySettings::default()
+        };
+        let service = OntologyService::from_config(&settings, &ReasonerSettings::default())
+            .expect("ontology service");
+        let people = iri("https://example.org/people");
+        assert!(service.repository().get(&people).await.unwrap().is_none());
+
+        fs::write(
+            tree.root.join("seeds").join("people.json"),
+            r#"{ "iri": "https://example.org/people" }"#,
+        )
+        .unwrap();
+
+        assert_eq!(service.reload_seeds().await.unwrap(), 2);
+        assert!(service.repository().get(&people).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn literal_values_collects_data_property_literals() {
+        let service = seeded_service().await;
+        let onto = iri("https://example.org/onto");
+
+        assert_eq!(
+            service
+                .literal_values(
+                    &onto,
+                    &iri("https://example.org/name"),
+                    &iri("https://example.org/Alice")
+                )
+                .await
+                .unwrap(),
+            vec!["Alice Liddell".to_string()]
+        );
+        assert!(service
+            .literal_values(
+                &onto,
+                &iri("https://example.org/knows"),
+                &iri("https://example.org/Alice")
+            )
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn service_wrappers_delegate_to_reasoner() {
+        let service = seeded_service().await;
+        let onto = iri("https://example.org/onto");
+
+        assert_eq!(
+            service
+                .ancestors(&onto, &iri("https://example.org/Derived"))
+                .await
+                .unwrap(),
+            vec![iri("https://example.org/Base")]
+        );
+        assert_eq!(
+            service
+                .descendants(&onto, &iri("https://example.org/Base"))
+                .await
+                .unwrap(),
+            vec![iri("https://example.org/Derived")]
+        );
+        assert_eq!(
+            service
+                .related(
+                    &onto,
+                    &iri("https://example.org/knows"),
+                    &iri("https://example.org/Alice")
+                )
+                .await
+                .unwrap(),
+            vec![iri("https://example.org/Bob")]
+        );
+        assert_eq!(
+            service
+                .shortest_path(
+                    &onto,
+                    &iri("https://example.org/Alice"),
+                    &iri("https://example.org/Bob")
+                )
+                .await
+                .unwrap(),
+            Some(vec![
+                iri("https://example.org/Alice"),
+                iri("https://example.org/Bob")
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn stats_report_depth_and_individuals_per_class() {
+        let service = OntologyService::from_config(
+            &OntologySettings::default(),
+            &ReasonerSettings::default(),
+        )
+        .expect("ontology service");
+
+        // `Person` reaches `Thing` both directly and through `Agent`.
+        let mut ontology = Ontology::new(iri("https://example.org/people"));
+        let mut agent = Class::new(iri("https://example.org/Agent"));
+        agent.add_parent(iri("https://example.org/Thing"));
+        let mut person = Class::new(iri("https://example.org/Person"));
+        person.add_parent(iri("https://example.org/Agent"));
+        person.add_parent(iri("https://example.org/Thing"));
+        let mut student = Class::new(iri("https://example.org/Student"));
+        student.add_parent(iri("https://example.org/Person"));
+        ontology
+            .add_class(Class::new(iri("https://example.org/Thing")))
+            .unwrap();
+        ontology.add_class(agent).unwrap();
+        ontology.add_class(person).unwrap();
+        ontology.add_class(student).unwrap();
+
+        let mut alice = Individual::new(iri("https://example.org/Alice"));
+        alice.assert_type(iri("https://example.org/Person"));
+        alice.assert_type(iri("https://example.org/Student"));
+        ontology.add_individual(alice).unwrap();
+        let mut bob = Individual::new(iri("https://example.org/Bob"));
+        bob.assert_type(iri("https://example.org/Person"));
+        ontology.add_individual(bob).unwrap();
+
+        service.repository().insert(ontology).await.unwrap();
+
+        let stats = service
+            .stats(&iri("https://example.org/people"))
+            .await
+            .unwrap();
+
+        assert_eq!(stats.classes_with_no_parents, 1);
+        assert_eq!(stats.max_hierarchy_depth, 4);
+        assert_eq!(
+            stats.individuals_per_class,
+            BTreeMap::from([
+                (iri("https://example.org/Agent"), 0),
+                (iri("https://example.org/Person"), 2),
+                (iri("https://example.org/Student"), 1),
+                (iri("https://example.org/Thing"), 0),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn stats_for_unknown_ontology_is_missing() {
+        let service = seeded_service().await;
+
+        let err = service
+            .stats(&iri("https://example.org/unknown"))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, OntologyServiceError::Missing { .. }));
+    }
+
+    #[tokio::test]
+    async fn hierarchy_counts_match_full_closures() {
+        let service = seeded_service().await;
+        let onto = iri("https://example.org/onto");
+
+        for class in ["https://example.org/Base", "https://example.org/Derived"] {
+            let class = iri(class);
+            assert_eq!(
+                service.ancestor_count(&onto, &class).await.unwrap(),
+                service.ancestors(&onto, &class).await.unwrap().len()
+            );
+            assert_eq!(
+                service.descendant_count(&onto, &class).await.unwrap(),
+                service.descendants(&onto, &class).await.unwrap().len()
+            );
+        }
+        assert_eq!(
+            service
+                .descendant_count(&onto, &iri("https://example.org/Base"))
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn hierarchy_counts_respect_class_hierarchy_toggle() {
+        let mut settings = ReasonerSettings::default();
+        settings.inference.class_hierarchy = false;
+        let service = OntologyService::from_config(&OntologySettings::default(), &settings)
+            .expect("ontology service");
+        let onto = iri("https://example.org/onto");
+        let class = iri("https://example.org/Base");
+
+        assert!(matches!(
+            service.ancestor_count(&onto, &class).await,
+            Err(OntologyServiceError::InferenceDisabled {
+                inference: "class_hierarchy"
+            })
+        ));
+        assert!(matches!(
+            service.descendant_count(&onto, &class).await,
+            Err(OntologyServiceError::InferenceDisabled {
+                inference: "class_hierarchy"
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn ancestors_page_walks_the_closure_in_chunks() {
+        let service = OntologyService::from_config(
+            &OntologySettings::default(),
+            &ReasonerSettings::default(),
+        )
+        .expect("ontology service");
+        let onto = iri("https://example.org/chain");
+
+        // Level0 -> Level1 -> ... -> Level5 gives Level0 five ancestors.
+        let mut ontology = Ontology::new(onto.clone());
+        for level in 0..=5 {
+            let mut class = Class::new(iri(&format!("https://example.org/Level{level}")));
+            if level < 5 {
+                class.add_parent(iri(&format!("https://example.org/Level{}", level + 1)));
+            }
+            ontology.add_class(class).unwrap();
+        }
+        service.repository().insert(ontology).await.unwrap();
+        let class = iri("https://example.org/Level0");
+
+        let mut paged = Vec::new();
+        let mut offset = 0;
+        let mut pages = 0;
+        loop {
+            let (page, has_more) = service
+                .ancestors_page(&onto, &class, offset, 2)
+                .await
+                .unwrap();
+            pages += 1;
+            offset += page.len();
+            paged.extend(page);
+            if !has_more {
+                break;
+            }
+        }
+
+        assert_eq!(pages, 3);
+        assert_eq!(paged, service.ancestors(&onto, &class).await.unwrap());
+        assert_eq!(
+            service.ancestors_page(&onto, &class, 4, 2).await.unwrap(),
+            (vec![iri("https://example.org/Level5")], false)
+        );
+    }
+
+    #[tokio::test]
+    async fn hierarchy_closures_are_sorted_by_iri() {
+        let service = OntologyService::from_config(
+            &OntologySettings::default(),
+            &ReasonerSettings::default(),
+        )
+        .expect("ontology service");
+        let onto = iri("https://example.org/zoo");
+
+        // Breadth-first discovery from either end disagrees with IRI order.
+        let mut ontology = Ontology::new(onto.clone());
+        let mut mammal = Class::new(iri("https://example.org/Mammal"));
+        mammal.add_parent(iri("https://example.org/Animal"));
+        let mut zebra = Class::new(iri("https://example.org/Zebra"));
+        zebra.add_parent(iri("https://example.org/Mammal"));
+        let mut aardvark = Class::new(iri("https://example.org/Aardvark"));
+        aardvark.add_parent(iri("https://example.org/Zebra"));
+        ontology
+            .add_class(Class::new(iri("https://example.org/Animal")))
+            .unwrap();
+        ontology.add_class(mammal).unwrap();
+        ontology.add_class(zebra).unwrap();
+        ontology.add_class(aardvark).unwrap();
+        service.repository().insert(ontology).await.unwrap();
+
+        assert_eq!(
+            service
+                .ancestors(&onto, &iri("https://example.org/Aardvark"))
+                .await
+                .unwrap(),
+            vec![
+                iri("https://example.org/Animal"),
+                iri("https://example.org/Mammal"),
+                iri("https://example.org/Zebra"),
+            ]
+        );
+        assert_eq!(
+            service
+                .descendants(&onto, &iri("https://example.org/Animal"))
+                .await
+                .unwrap(),
+            vec![
+                iri("https://example.org/Aardvark"),
+                iri("https://example.org/Mammal"),
+                iri("https://example.org/Zebra"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_service_reasons_over_preloaded_ontologies() {
+        let mut ontology = Ontology::new(iri("https://example.org/onto"));
+        let mut derived = Class::new(iri("https://example.org/Derived"));
+        derived.add_parent(iri("https://example.org/Base"));
+        ontology
+            .add_class(Class::new(iri("https://example.org/Base")))
+            .unwrap();
+        ontology.add_class(derived).unwrap();
+
+        let service = OntologyService::in_memory(vec![ontology]);
+
+        assert_eq!(
+            service
+                .ancestors(
+                    &iri("https://example.org/onto"),
+                    &iri("https://example.org/Derived")
+                )
+                .await
+                .unwrap(),
+            vec![iri("https://example.org/Base")]
+        );
+    }
+
+    #[tokio::test]
+    async fn lint_reports_range_violations_as_warnings() {
+        let mut ontology = Ontology::new(iri("https://example.org/onto"));
+        for class in ["Person", "Company"] {
+            ontology
+                .add_class(Class::new(iri(&format!("https://example.org/{class}"))))
+                .unwrap();
+        }
+        let mut employs = Property::new(iri("https://example.org/employs"), PropertyKind::Object);
+        employs.add_domain(iri("https://example.org/Company"));
+        employs.add_range(iri("https://example.org/Person"));
+        ontology.add_property(employs).unwrap();
+
+        let mut acme = Individual::new(iri("https://example.org/acme"));
+        acme.assert_type(iri("https://example.org/Company"));
+        acme.add_property_assertion(
+            iri("https://example.org/employs"),
+            PropertyAssertion::Individual(iri("https://example.org/globex")),
+        );
+        ontology.add_individual(acme).unwrap();
+        let mut globex = Individual::new(iri("https://example.org/globex"));
+        globex.assert_type(iri("https://example.org/Company"));
+        ontology.add_individual(globex).unwrap();
+
+        let service = OntologyService::in_memory(vec![ontology]);
+        let findings = service
+            .lint(&iri("https://example.org/onto"))
+            .await
+            .unwrap();
+
+        let warnings: Vec<_> = findings
+            .iter()
+            .filter(|finding| finding.severity == FindingSeverity::Warning)
+            .collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].check, "range_violation");
+        assert_eq!(warnings[0].subject, iri("https://example.org/acme"));
+        assert!(matches!(
+            service.lint(&iri("https://example.org/absent")).await,
+            Err(OntologyServiceError::Missing { .. })
+        ));
+    }
+}
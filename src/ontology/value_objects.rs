@@ -23,11 +23,120 @@ impl Iri {
         Ok(Self { value })
     }
 
+    /// Applies `normalization` to `value` before validating it, so IRIs from
+    /// different sources can be deduplicated at ingestion.
+    pub fn new_with(
+        value: impl Into<String>,
+        normalization: IriNormalization,
+    ) -> Result<Self, IriError> {
+        Self::new(normalization.apply(value.into()))
+    }
+
+    /// Returns the IRI with every [`IriNormalization`] rule applied, e.g.
+    /// `https://example.org/Class` for `HTTPS://Example.org/Class/`.
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        Self::new_with(self.value.as_str(), IriNormalization::all())
+            .unwrap_or_else(|_| self.clone())
+    }
+
+    /// Parses every value, returning either all IRIs in input order or the
+    /// index and error of each value that failed, so callers can report every
+    /// bad input at once.
+    pub fn parse_all(values: &[String]) -> Result<Vec<Self>, Vec<(usize, IriError)>> {
+        let mut iris = Vec::with_capacity(values.len());
+        let mut errors = Vec::new();
+        for (index, value) in values.iter().enumerate() {
+            match Self::new(value.as_str()) {
+                Ok(iri) => iris.push(iri),
+                Err(err) => errors.push((index, err)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(iris)
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Returns the underlying textual representation.
     #[must_use]
     pub fn as_str(&self) -> &str {
         &self.value
     }
+
+    /// Returns the part after the last `#`, or the last `/` when there is no
+    /// fragment, e.g. `Person` for `https://example.org/onto#Person`. A
+    /// trailing separator is ignored; without any separator the whole IRI is
+    /// returned.
+    #[must_use]
+    pub fn local_name(&self) -> &str {
+        let (start, end) = self.local_name_bounds();
+        &self.value[start..end]
+    }
+
+    /// Returns everything before [`Self::local_name`], including the
+    /// separator, e.g. `https://example.org/onto#`. Empty when the IRI has no
+    /// separator.
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.value[..self.local_name_bounds().0]
+    }
+
+    fn local_name_bounds(&self) -> (usize, usize) {
+        let trimmed = self
+            .value
+            .strip_suffix(|c: char| c == '#' || c == '/')
+            .unwrap_or(&self.value);
+        let start = trimmed
+            .rfind('#')
+            .or_else(|| trimmed.rfind('/'))
+            .map_or(0, |index| index + 1);
+        (start, trimmed.len())
+    }
+}
+
+/// Rewrites applied by [`Iri::new_with`]. Nothing is rewritten by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IriNormalization {
+    /// Drops a single trailing `/`, e.g. `https://example.org/Class/`.
+    pub strip_trailing_slash: bool,
+    /// Lowercases the scheme and host. The user info, path, query and
+    /// fragment keep their case.
+    pub lowercase_scheme_and_host: bool,
+}
+
+impl IriNormalization {
+    /// Enables every rewrite.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self {
+            strip_trailing_slash: true,
+            lowercase_scheme_and_host: true,
+        }
+    }
+
+    fn apply(self, mut value: String) -> String {
+        if self.lowercase_scheme_and_host {
+            if let Some(scheme_end) = value.find(':') {
+                value[..scheme_end].make_ascii_lowercase();
+                if value[scheme_end..].starts_with("://") {
+                    let authority_start = scheme_end + 3;
+                    let authority_end = value[authority_start..]
+                        .find(['/', '?', '#'])
+                        .map_or(value.len(), |index| authority_start + index);
+                    let host_start = value[authority_start..authority_end]
+                        .rfind('@')
+                        .map_or(authority_start, |index| authority_start + index + 1);
+                    value[host_start..authority_end].make_ascii_lowercase();
+                }
+            }
+        }
+        if self.strip_trailing_slash && value.ends_with('/') && !value.ends_with("//") {
+            value.pop();
+        }
+        value
+    }
 }
 
 impl Display for Iri {
@@ -62,7 +171,7 @@ pub enum IriError {
 
 #[cfg(test)]
 mod tests {
-    use super::Iri;
+    use super::{Iri, IriError, IriNormalization};
 
     #[test]
     fn accepts_valid_iri() {
@@ -70,9 +179,106 @@ mod tests {
         assert_eq!(iri.as_str(), "https://example.org/resource");
     }
 
+    #[test]
+    fn local_name_prefers_the_fragment() {
+        let iri = Iri::new("https://example.org/onto#Person").expect("valid IRI");
+        assert_eq!(iri.local_name(), "Person");
+        assert_eq!(iri.namespace(), "https://example.org/onto#");
+    }
+
+    #[test]
+    fn local_name_falls_back_to_the_last_path_segment() {
+        let iri = Iri::new("https://example.org/people/alice").expect("valid IRI");
+        assert_eq!(iri.local_name(), "alice");
+        assert_eq!(iri.namespace(), "https://example.org/people/");
+
+        let trailing = Iri::new("https://example.org/people/").expect("valid IRI");
+        assert_eq!(trailing.local_name(), "people");
+        assert_eq!(trailing.namespace(), "https://example.org/");
+    }
+
+    #[test]
+    fn local_name_without_separator_is_the_whole_iri() {
+        let iri = Iri::new("urn:isbn:0451450523").expect("valid IRI");
+        assert_eq!(iri.local_name(), "urn:isbn:0451450523");
+        assert_eq!(iri.namespace(), "");
+    }
+
+    #[test]
+    fn parse_all_reports_every_invalid_value() {
+        let values = vec![
+            "https://example.org/a".to_string(),
+            "not an iri".to_string(),
+            "https://example.org/b".to_string(),
+            "also invalid".to_string(),
+        ];
+
+        let errors = Iri::parse_all(&values).expect_err("invalid values");
+
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    1,
+                    IriError::Invalid {
+                        value: "not an iri".to_string()
+                    }
+                ),
+                (
+                    3,
+                    IriError::Invalid {
+                        value: "also invalid".to_string()
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_all_keeps_input_order_when_valid() {
+        let values = vec![
+            "https://example.org/b".to_string(),
+            "https://example.org/a".to_string(),
+        ];
+
+        let iris = Iri::parse_all(&values).expect("valid values");
+
+        assert_eq!(
+            iris.iter().map(Iri::as_str).collect::<Vec<_>>(),
+            vec!["https://example.org/b", "https://example.org/a"]
+        );
+    }
+
     #[test]
     fn rejects_invalid_iri() {
         let err = Iri::new("not an iri").expect_err("invalid IRI");
         assert!(matches!(err, super::IriError::Invalid { value } if value == "not an iri"));
     }
+
+    #[test]
+    fn normalization_strips_a_trailing_slash() {
+        let iri = Iri::new("https://example.org/Class/").expect("valid IRI");
+        assert_eq!(iri.normalized().as_str(), "https://example.org/Class");
+        assert_eq!(
+            iri.normalized(),
+            Iri::new("https://example.org/Class").unwrap().normalized()
+        );
+        assert_eq!(
+            Iri::new_with("https://example.org/Class/", IriNormalization::default())
+                .unwrap()
+                .as_str(),
+            "https://example.org/Class/"
+        );
+    }
+
+    #[test]
+    fn normalization_lowercases_scheme_and_host_only() {
+        let normalization = IriNormalization {
+            lowercase_scheme_and_host: true,
+            ..IriNormalization::default()
+        };
+        let iri = Iri::new_with("HTTPS://User@Example.ORG/Path/Class#Frag", normalization)
+            .expect("valid IRI");
+        assert_eq!(iri.as_str(), "https://User@example.org/Path/Class#Frag");
+    }
 }
@@ -22,6 +22,22 @@ fn get_re_is_cron_syntax() -> &'static Regex {
     RE_IS_CRON_SYNTAX.get_or_init(|| Regex::new(r"^[\*\d]").unwrap())
 }
 
+/// Returns `schedule` as a cron expression, converting English schedules
+/// such as `every day at 4:00 am` with [`english_to_cron`].
+///
+/// # Errors
+///
+/// When an English schedule cannot be converted.
+pub(crate) fn cron_syntax(schedule: &str) -> Result<String, Error> {
+    if get_re_is_cron_syntax().is_match(schedule) {
+        return Ok(schedule.to_string());
+    }
+    english_to_cron::str_cron_syntax(schedule).map_err(|err| Error::InvalidCronSyntax {
+        cron: schedule.to_string(),
+        error: err.to_string(),
+    })
+}
+
 /// Errors that may occur while operating the scheduler.
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -298,16 +314,7 @@ impl Scheduler {
             let job_description =
                 job.prepare_command(&self.binary_path, &self.default_output, &self.environment);
 
-            let cron_syntax = if get_re_is_cron_syntax().is_match(&job.cron) {
-                job.cron.clone()
-            } else {
-                english_to_cron::str_cron_syntax(&job.cron).map_err(|err| {
-                    Error::InvalidCronSyntax {
-                        cron: job.cron.clone(),
-                        error: err.to_string(),
-                    }
-                })?
-            };
+            let cron_syntax = cron_syntax(&job.cron)?;
 
             if job.run_on_start {
                 let job_description = job_description.clone();
@@ -1,13 +1,23 @@
-use std::net::SocketAddr;
+use std::{collections::HashMap, net::SocketAddr};
 
-use axum::{http::StatusCode, Router};
+use axum::{
+    http::{header, HeaderValue, Method, StatusCode},
+    routing::get,
+    Router,
+};
+use chrono::{TimeZone, Utc};
 use insta::assert_snapshot;
 use loco_rs::{
     app::Hooks,
+    boot::{run_app, StartMode},
+    controller::ListRoutes,
+    introspection::assistant::{DoctorFinding, DoctorStatus},
+    introspection::graph::domain::ComponentKind,
     introspection::graph::service::{
-        ApplicationGraphService, GraphIntrospectionSeed, GraphQueryService,
+        ApplicationGraphService, DoctorHealth, GraphIntrospectionSeed, GraphQueryService,
+        GraphSnapshot, MethodOrder, GRAPH_SCHEMA_VERSION,
     },
-    TestServer,
+    scheduler, TestServer,
 };
 use serde_json::Value;
 
@@ -35,12 +45,16 @@ async fn graph_endpoint_matches_cli_snapshot() {
 
     assert_eq!(response.status_code(), StatusCode::OK);
 
-    let http_json: Value = response.json::<Value>();
+    let mut http_json: Value = response.json::<Value>();
     assert!(http_json.get("routes").is_some());
     assert!(http_json.get("dependencies").is_some());
     assert_eq!(http_json["health"]["ok"], Value::Bool(true));
+    assert_eq!(
+        http_json["schema_version"],
+        Value::from(GRAPH_SCHEMA_VERSION)
+    );
 
-    let cli_value = {
+    let mut cli_value = {
         let seed = ctx
             .shared_store
             .get_ref::<GraphIntrospectionSeed>()
@@ -48,9 +62,516 @@ async fn graph_endpoint_matches_cli_snapshot() {
         serde_json::to_value(seed.into_service(&ctx).snapshot()).unwrap()
     };
 
+    // next-run times depend on the wall clock, so they are left out of the comparison
+    strip_next_run(&mut http_json);
+    strip_next_run(&mut cli_value);
     assert_eq!(http_json, cli_value);
     assert_snapshot!(
         "graph_cli_snapshot",
         serde_json::to_string_pretty(&cli_value).expect("serialize graph snapshot")
     );
 }
+
+fn strip_next_run(snapshot: &mut Value) {
+    if let Some(jobs) = snapshot["dependencies"]["scheduler_jobs"].as_array_mut() {
+        for job in jobs {
+            if let Some(job) = job.as_object_mut() {
+                job.remove("next_run");
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn scheduler_jobs_report_next_run_from_clock() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let job = |schedule: &str| scheduler::Job {
+        run: "cleanup".to_string(),
+        shell: false,
+        run_on_start: false,
+        cron: schedule.to_string(),
+        tags: None,
+        output: None,
+    };
+    let config = scheduler::Config {
+        jobs: HashMap::from([
+            ("midnight".to_string(), job("0 0 * * *")),
+            ("broken".to_string(), job("not a cron")),
+        ]),
+        output: scheduler::Output::default(),
+    };
+    let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 30, 0).unwrap();
+
+    let jobs = ApplicationGraphService::from_route_descriptors("demo", Vec::new(), &ctx)
+        .with_scheduler_config(Some(&config))
+        .with_clock(now)
+        .snapshot()
+        .dependencies
+        .scheduler_jobs;
+
+    assert_eq!(jobs.len(), 2);
+    assert_eq!(jobs[0].name, "broken");
+    assert_eq!(jobs[0].next_run, None);
+    assert_eq!(jobs[1].name, "midnight");
+    assert_eq!(
+        jobs[1].next_run.as_deref(),
+        Some("2024-01-02T00:00:00+00:00")
+    );
+}
+
+#[tokio::test]
+async fn snapshot_health_reflects_doctor_findings() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let service = ApplicationGraphService::from_route_descriptors("demo", Vec::new(), &ctx);
+    assert!(service.snapshot().health.ok);
+
+    ctx.shared_store
+        .insert(DoctorHealth::new(vec![DoctorFinding {
+            resource: "Database".to_string(),
+            status: DoctorStatus::Failing,
+            message: "connection refused".to_string(),
+            detail: None,
+        }]));
+
+    assert!(!service.snapshot().health.ok);
+}
+
+#[tokio::test]
+async fn boot_does_not_run_doctor_checks() {
+    let ctx = tests_cfg::app::get_app_context().await;
+
+    run_app::<tests_cfg::db::AppHook>(&StartMode::ServerOnly, ctx.clone())
+        .await
+        .expect("boot app");
+
+    assert!(!ctx.shared_store.contains::<DoctorHealth>());
+    let seed = ctx
+        .shared_store
+        .get_ref::<GraphIntrospectionSeed>()
+        .expect("graph metadata should be seeded");
+    assert!(seed.into_service(&ctx).snapshot().health.ok);
+}
+
+#[tokio::test]
+async fn graph_endpoint_paginates_routes() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let app_routes = tests_cfg::db::AppHook::routes(&ctx);
+    let route_descriptors = ["/api/posts", "/api/users"]
+        .into_iter()
+        .map(
+            |path| loco_rs::introspection::graph::domain::RouteDescriptor {
+                path: path.to_string(),
+                methods: vec!["GET".to_string()],
+                handler: None,
+                params: Vec::new(),
+            },
+        )
+        .collect();
+    ctx.shared_store.insert(GraphIntrospectionSeed::new(
+        tests_cfg::db::AppHook::app_name(),
+        route_descriptors,
+    ));
+
+    let router = app_routes
+        .to_router::<tests_cfg::db::AppHook>(ctx.clone(), Router::new())
+        .expect("build monitoring router");
+    let server = TestServer::new(router.into_make_service_with_connect_info::<SocketAddr>())
+        .expect("start test server");
+
+    let page: Value = server.get("/__loco/graph?offset=1&limit=1").await.json();
+    assert_eq!(page["routes_total"], Value::from(2));
+    assert_eq!(page["routes"].as_array().map(Vec::len), Some(1));
+    assert_eq!(page["routes"][0]["path"], "/api/users");
+
+    let full: Value = server.get("/__loco/graph").await.json();
+    assert!(full.get("routes_total").is_none());
+    assert_eq!(full["routes"].as_array().map(Vec::len), Some(2));
+}
+
+#[tokio::test]
+async fn graph_endpoint_filters_scheduler_jobs_by_tag() {
+    let mut ctx = tests_cfg::app::get_app_context().await;
+    let job = |tags: Option<Vec<String>>| scheduler::Job {
+        run: "cleanup".to_string(),
+        shell: false,
+        run_on_start: false,
+        cron: "0 0 * * *".to_string(),
+        tags,
+        output: None,
+    };
+    ctx.config.scheduler = Some(scheduler::Config {
+        jobs: HashMap::from([
+            ("report".to_string(), job(Some(vec!["reports".to_string()]))),
+            (
+                "cleanup".to_string(),
+                job(Some(vec!["maintenance".to_string()])),
+            ),
+            ("untagged".to_string(), job(None)),
+        ]),
+        output: scheduler::Output::default(),
+    });
+    let app_routes = tests_cfg::db::AppHook::routes(&ctx);
+    ctx.shared_store.insert(GraphIntrospectionSeed::new(
+        tests_cfg::db::AppHook::app_name(),
+        Vec::new(),
+    ));
+
+    let router = app_routes
+        .to_router::<tests_cfg::db::AppHook>(ctx.clone(), Router::new())
+        .expect("build monitoring router");
+    let server = TestServer::new(router.into_make_service_with_connect_info::<SocketAddr>())
+        .expect("start test server");
+
+    let job_names = |body: Value| -> Vec<String> {
+        body["dependencies"]["scheduler_jobs"]
+            .as_array()
+            .expect("scheduler jobs")
+            .iter()
+            .map(|job| job["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let tagged: Value = server.get("/__loco/graph?tag=reports").await.json();
+    assert_eq!(job_names(tagged), vec!["report"]);
+
+    let all: Value = server.get("/__loco/graph").await.json();
+    assert_eq!(job_names(all).len(), 3);
+}
+
+#[tokio::test]
+async fn graph_stream_emits_one_line_per_node_and_edge() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let app_routes = tests_cfg::db::AppHook::routes(&ctx);
+    let seed = GraphIntrospectionSeed::new(
+        tests_cfg::db::AppHook::app_name(),
+        ApplicationGraphService::collect_route_descriptors(&app_routes.collect()),
+    );
+    let expected = seed.into_service(&ctx).build_graph();
+    ctx.shared_store.insert(seed);
+
+    let router = app_routes
+        .to_router::<tests_cfg::db::AppHook>(ctx.clone(), Router::new())
+        .expect("build monitoring router");
+    let server = TestServer::new(router.into_make_service_with_connect_info::<SocketAddr>())
+        .expect("start test server");
+
+    let response = server.get("/__loco/graph/stream").await;
+    assert_eq!(
+        response.header(header::CONTENT_TYPE),
+        HeaderValue::from_static("application/x-ndjson")
+    );
+    let lines: Vec<Value> = response
+        .text()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is JSON"))
+        .collect();
+
+    assert_eq!(lines[0]["type"], "meta");
+    assert_eq!(
+        lines[0]["schema_version"],
+        Value::from(GRAPH_SCHEMA_VERSION)
+    );
+    let count = |kind: &str| lines.iter().filter(|line| line["type"] == kind).count();
+    assert_eq!(count("node"), expected.nodes.len());
+    assert_eq!(count("edge"), expected.edges.len());
+    assert!(count("node") > 0);
+}
+
+#[tokio::test]
+async fn graph_endpoint_indents_json_when_pretty() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let app_routes = tests_cfg::db::AppHook::routes(&ctx);
+    ctx.shared_store.insert(GraphIntrospectionSeed::new(
+        tests_cfg::db::AppHook::app_name(),
+        Vec::new(),
+    ));
+
+    let router = app_routes
+        .to_router::<tests_cfg::db::AppHook>(ctx.clone(), Router::new())
+        .expect("build monitoring router");
+    let server = TestServer::new(router.into_make_service_with_connect_info::<SocketAddr>())
+        .expect("start test server");
+
+    let compact = server.get("/__loco/graph").await;
+    let pretty = server.get("/__loco/graph?pretty=true").await;
+
+    assert!(!compact.text().contains('\n'));
+    assert!(pretty.text().contains('\n'));
+    assert_eq!(
+        pretty.header(header::CONTENT_TYPE),
+        HeaderValue::from_static("application/json")
+    );
+    assert_eq!(pretty.json::<Value>(), compact.json::<Value>());
+}
+
+#[tokio::test]
+async fn graph_endpoint_answers_not_modified_for_matching_etag() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let app_routes = tests_cfg::db::AppHook::routes(&ctx);
+    ctx.shared_store.insert(GraphIntrospectionSeed::new(
+        tests_cfg::db::AppHook::app_name(),
+        Vec::new(),
+    ));
+
+    let router = app_routes
+        .to_router::<tests_cfg::db::AppHook>(ctx.clone(), Router::new())
+        .expect("build monitoring router");
+    let server = TestServer::new(router.into_make_service_with_connect_info::<SocketAddr>())
+        .expect("start test server");
+
+    let first = server.get("/__loco/graph").await;
+    assert_eq!(first.status_code(), StatusCode::OK);
+    let etag = first.header("etag");
+
+    let second = server
+        .get("/__loco/graph")
+        .add_header(header::IF_NONE_MATCH, etag.clone())
+        .await;
+    assert_eq!(second.status_code(), StatusCode::NOT_MODIFIED);
+    assert!(second.text().is_empty());
+
+    let stale = server
+        .get("/__loco/graph")
+        .add_header(header::IF_NONE_MATCH, HeaderValue::from_static("\"stale\""))
+        .await;
+    assert_eq!(stale.status_code(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn graph_metrics_endpoint_renders_prometheus_gauges() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let app_routes = tests_cfg::db::AppHook::routes(&ctx);
+    let route_descriptors = vec![
+        loco_rs::introspection::graph::domain::RouteDescriptor {
+            path: "/api/users".to_string(),
+            methods: vec!["GET".to_string()],
+            handler: None,
+            params: Vec::new(),
+        },
+        loco_rs::introspection::graph::domain::RouteDescriptor {
+            path: "/api/posts".to_string(),
+            methods: vec!["GET".to_string(), "POST".to_string()],
+            handler: None,
+            params: Vec::new(),
+        },
+    ];
+    ctx.shared_store.insert(GraphIntrospectionSeed::new(
+        tests_cfg::db::AppHook::app_name(),
+        route_descriptors,
+    ));
+
+    let router = app_routes
+        .to_router::<tests_cfg::db::AppHook>(ctx.clone(), Router::new())
+        .expect("build monitoring router");
+    let server = TestServer::new(router.into_make_service_with_connect_info::<SocketAddr>())
+        .expect("start test server");
+
+    let response = server.get("/__loco/graph/metrics").await;
+
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_eq!(response.header("content-type"), "text/plain; version=0.0.4");
+    let body = response.text();
+    let lines: Vec<&str> = body.lines().collect();
+    assert!(lines.contains(&"# TYPE loco_routes_total gauge"));
+    assert!(lines.contains(&"loco_routes_total 2"));
+    assert!(lines.contains(&"loco_workers_total 0"));
+    assert!(lines.contains(&"loco_scheduler_jobs_total 1"));
+    assert!(lines.contains(&"loco_tasks_total 0"));
+    assert!(lines.contains(&"graph_snapshot_health 1"));
+}
+
+#[tokio::test]
+async fn from_list_routes_carries_handler_names() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let routes = vec![ListRoutes {
+        uri: "/api/users".into(),
+        actions: vec![Method::GET],
+        method: get(|| async { "users" }),
+        handler: Some("users::list".into()),
+    }];
+
+    let service = ApplicationGraphService::from_list_routes("demo", routes, &ctx);
+
+    let snapshot = service.snapshot();
+    assert_eq!(snapshot.routes.len(), 1);
+    assert_eq!(snapshot.routes[0].handler.as_deref(), Some("users::list"));
+
+    let graph = service.build_graph();
+    assert!(graph.nodes.iter().any(|node| matches!(
+        &node.kind,
+        ComponentKind::HttpRoute { path, handler, .. }
+            if path == "/api/users" && handler.as_deref() == Some("users::list")
+    )));
+}
+
+#[tokio::test]
+async fn duplicate_routes_are_reported_and_mark_graph_unhealthy() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let route = |method: Method, handler: &str| ListRoutes {
+        uri: "/api/users".into(),
+        actions: vec![method.clone()],
+        method: match method {
+            Method::POST => axum::routing::post(|| async { "create" }),
+            _ => get(|| async { "list" }),
+        },
+        handler: Some(handler.into()),
+    };
+    let routes = vec![
+        route(Method::GET, "users::list"),
+        route(Method::GET, "users::index"),
+        route(Method::POST, "users::create"),
+    ];
+
+    let service = ApplicationGraphService::from_list_routes("demo", routes, &ctx);
+
+    assert_eq!(
+        service.duplicate_routes(),
+        vec![("/api/users".to_string(), "GET".to_string())]
+    );
+    let snapshot = service.snapshot();
+    assert_eq!(snapshot.routes.len(), 1);
+    assert!(!snapshot.health.ok);
+    assert_eq!(snapshot.health.duplicate_routes, 1);
+}
+
+#[tokio::test]
+async fn route_descriptors_carry_path_params() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let routes = ["/users/:id/posts/:post_id", "/teams/{team}", "/api/users"]
+        .into_iter()
+        .map(|uri| ListRoutes {
+            uri: uri.into(),
+            actions: vec![Method::GET],
+            method: get(|| async { "ok" }),
+            handler: None,
+        })
+        .collect();
+
+    let service = ApplicationGraphService::from_list_routes("demo", routes, &ctx);
+
+    let params: Vec<(String, Vec<String>)> = service
+        .snapshot()
+        .routes
+        .into_iter()
+        .map(|route| (route.path, route.params))
+        .collect();
+    assert_eq!(
+        params,
+        vec![
+            ("/api/users".to_string(), vec![]),
+            ("/teams/{team}".to_string(), vec!["team".to_string()]),
+            (
+                "/users/:id/posts/:post_id".to_string(),
+                vec!["id".to_string(), "post_id".to_string()]
+            ),
+        ]
+    );
+
+    let graph = service.build_graph();
+    assert!(graph.nodes.iter().any(|node| matches!(
+        &node.kind,
+        ComponentKind::HttpRoute { path, params, .. }
+            if path == "/users/:id/posts/:post_id" && params == &["id", "post_id"]
+    )));
+}
+
+#[tokio::test]
+async fn excluded_prefixes_hide_internal_routes() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let routes = ["/__loco/graph", "/__loco/doctor", "/api/users"]
+        .into_iter()
+        .map(|uri| ListRoutes {
+            uri: uri.into(),
+            actions: vec![Method::GET],
+            method: get(|| async { "ok" }),
+            handler: None,
+        })
+        .collect();
+
+    let service = ApplicationGraphService::from_list_routes("demo", routes, &ctx)
+        .with_excluded_prefixes(["/__loco"]);
+
+    let snapshot = service.snapshot();
+    let paths: Vec<&str> = snapshot
+        .routes
+        .iter()
+        .map(|route| route.path.as_str())
+        .collect();
+    assert_eq!(paths, vec!["/api/users"]);
+
+    let graph = service.build_graph();
+    assert!(!graph.nodes.iter().any(|node| matches!(
+        &node.kind,
+        ComponentKind::HttpRoute { path, .. } if path.starts_with("/__loco")
+    )));
+}
+
+#[tokio::test]
+async fn excluded_prefixes_match_whole_segments() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let routes = ["/api", "/api/users", "/apiary"]
+        .into_iter()
+        .map(|uri| ListRoutes {
+            uri: uri.into(),
+            actions: vec![Method::GET],
+            method: get(|| async { "ok" }),
+            handler: None,
+        })
+        .collect();
+
+    let service = ApplicationGraphService::from_list_routes("demo", routes, &ctx)
+        .with_excluded_prefixes(["/api"]);
+
+    let paths: Vec<String> = service
+        .snapshot()
+        .routes
+        .into_iter()
+        .map(|route| route.path)
+        .collect();
+    assert_eq!(paths, vec!["/apiary".to_string()]);
+}
+
+#[tokio::test]
+async fn method_order_controls_route_methods() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let methods = |order: MethodOrder| {
+        let routes = vec![ListRoutes {
+            uri: "/api/users".into(),
+            actions: vec![Method::POST, Method::DELETE, Method::GET],
+            method: get(|| async { "ok" }),
+            handler: None,
+        }];
+        let service = ApplicationGraphService::from_list_routes("demo", routes, &ctx)
+            .with_method_order(order);
+        let snapshot_methods = service.snapshot().routes[0].methods.clone();
+        let node_methods = service
+            .build_graph()
+            .nodes
+            .into_iter()
+            .find_map(|node| match node.kind {
+                ComponentKind::HttpRoute { methods, .. } => Some(methods),
+                _ => None,
+            })
+            .expect("route node");
+        assert_eq!(snapshot_methods, node_methods);
+        snapshot_methods
+    };
+
+    assert_eq!(
+        methods(MethodOrder::Alphabetical),
+        vec!["DELETE", "GET", "POST"]
+    );
+    assert_eq!(
+        methods(MethodOrder::HttpSemantic),
+        vec!["GET", "POST", "DELETE"]
+    );
+}
+
+#[test]
+fn example_snapshot_exposes_schema_version() {
+    let value = serde_json::to_value(GraphSnapshot::example()).expect("serialize example");
+
+    assert_eq!(value["schema_version"], Value::from(GRAPH_SCHEMA_VERSION));
+    assert!(value["routes"].is_array());
+    assert!(value["dependencies"]["tasks"].is_array());
+}
@@ -1,5 +1,5 @@
 use std::sync::{
-    atomic::{AtomicUsize, Ordering},
+    atomic::{AtomicBool, AtomicUsize, Ordering},
     Arc,
 };
 
@@ -22,17 +22,23 @@ use tower::ServiceExt;
 #[derive(Default)]
 struct SpyGenerator {
     calls: AtomicUsize,
+    dry_run: AtomicBool,
 }
 
 impl SpyGenerator {
     fn call_count(&self) -> usize {
         self.calls.load(Ordering::SeqCst)
     }
+
+    fn last_dry_run(&self) -> bool {
+        self.dry_run.load(Ordering::SeqCst)
+    }
 }
 
 impl ScaffoldGenerator for SpyGenerator {
-    fn generate(&self, _command: NodeCreationCommand) -> loco_rs::Result<ScaffoldGeneration> {
+    fn generate(&self, command: NodeCreationCommand) -> loco_rs::Result<ScaffoldGeneration> {
         self.calls.fetch_add(1, Ordering::SeqCst);
+        self.dry_run.store(command.dry_run, Ordering::SeqCst);
         Ok(ScaffoldGeneration::new("ok"))
     }
 }
@@ -71,4 +77,43 @@ async fn http_node_generation_invokes_generator() {
 
     assert_eq!(response.status(), StatusCode::OK);
     assert_eq!(generator.call_count(), 1);
+    assert!(!generator.last_dry_run());
+}
+
+#[tokio::test]
+async fn http_node_generation_forwards_dry_run() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let generator = Arc::new(SpyGenerator::default());
+
+    let generator_clone: Arc<dyn ScaffoldGenerator> = generator.clone();
+    ctx.shared_store.insert(GraphMutationService::new(
+        tests_cfg::db::AppHook::app_name(),
+        generator_clone,
+    ));
+
+    let router = Router::new()
+        .route("/__loco/graph/nodes", post(monitoring::create_graph_node))
+        .with_state(ctx);
+
+    let payload = serde_json::json!({
+        "component": "task",
+        "name": "cleanup",
+        "dry_run": true,
+    });
+
+    let response = router
+        .oneshot(
+            Request::builder()
+                .uri("/__loco/graph/nodes")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(payload.to_string()))
+                .expect("build request"),
+        )
+        .await
+        .expect("http response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(generator.call_count(), 1);
+    assert!(generator.last_dry_run());
 }
@@ -1,7 +1,8 @@
 use loco_rs::introspection::graph::domain::{
-    ApplicationGraph, BackgroundWorkerDescriptor, BackgroundWorkerRepository, ComponentKind,
-    EdgeKind, GraphBuilder, GraphEdge, GraphNode, RouteDescriptor, RoutesRepository,
-    SchedulerJobDescriptor, SchedulerRepository, TaskDescriptor, TaskRepository,
+    merge_graphs, route_params, ApplicationGraph, BackgroundWorkerDescriptor,
+    BackgroundWorkerRepository, ComponentKind, EdgeKind, GraphBuilder, GraphEdge, GraphMergeError,
+    GraphNode, RouteDescriptor, RoutesRepository, SchedulerJobDescriptor, SchedulerRepository,
+    TaskDescriptor, TaskRepository,
 };
 
 struct RoutesStub {
@@ -62,10 +63,14 @@ fn builds_graph_with_routes_workers_jobs_and_tasks() {
             RouteDescriptor {
                 path: "/health".into(),
                 methods: vec!["GET".into()],
+                handler: None,
+                params: Vec::new(),
             },
             RouteDescriptor {
                 path: "/users".into(),
                 methods: vec!["GET".into(), "POST".into()],
+                handler: Some("users::list".into()),
+                params: Vec::new(),
             },
         ],
     };
@@ -85,6 +90,7 @@ fn builds_graph_with_routes_workers_jobs_and_tasks() {
             run_on_start: false,
             shell: false,
             tags: vec!["maintenance".into()],
+            next_run: None,
         }],
     };
 
@@ -93,10 +99,12 @@ fn builds_graph_with_routes_workers_jobs_and_tasks() {
             TaskDescriptor {
                 name: "cleanup".into(),
                 detail: Some("Cleanup stale data".into()),
+                enqueues: Vec::new(),
             },
             TaskDescriptor {
                 name: "send_welcome_email".into(),
                 detail: Some("Send welcome email".into()),
+                enqueues: Vec::new(),
             },
         ],
     };
@@ -148,6 +156,89 @@ fn builds_graph_with_routes_workers_jobs_and_tasks() {
     assert!(graph.edges.contains(&trigger_edge));
 }
 
+#[test]
+fn task_enqueueing_a_known_worker_triggers_it() {
+    let routes = RoutesStub { routes: vec![] };
+    let workers = WorkersStub {
+        workers: vec![
+            BackgroundWorkerDescriptor {
+                name: "DownloadWorker".into(),
+                queue: None,
+            },
+            BackgroundWorkerDescriptor {
+                name: "ReportWorker".into(),
+                queue: None,
+            },
+        ],
+    };
+    let scheduler = SchedulerStub { jobs: vec![] };
+    let tasks = TasksStub {
+        tasks: vec![
+            TaskDescriptor {
+                name: "sync".into(),
+                detail: None,
+                enqueues: vec!["DownloadWorker".into(), "UnknownWorker".into()],
+            },
+            TaskDescriptor {
+                name: "report".into(),
+                detail: Some("Enqueue ReportWorker for every account".into()),
+                enqueues: Vec::new(),
+            },
+        ],
+    };
+
+    let graph = GraphBuilder::new("demo", &routes, &workers, &scheduler, &tasks).build();
+
+    let triggers: Vec<_> = graph
+        .edges
+        .iter()
+        .filter(|edge| edge.kind == EdgeKind::Triggers)
+        .map(|edge| (edge.from.as_str(), edge.to.as_str()))
+        .collect();
+    assert_eq!(
+        triggers,
+        vec![
+            ("task:report", "worker:ReportWorker"),
+            ("task:sync", "worker:DownloadWorker"),
+        ]
+    );
+}
+
+#[test]
+fn worker_on_several_queues_gets_a_node_per_queue() {
+    let routes = RoutesStub { routes: vec![] };
+    let workers = WorkersStub {
+        workers: vec![
+            BackgroundWorkerDescriptor {
+                name: "DownloadWorker".into(),
+                queue: Some("default".into()),
+            },
+            BackgroundWorkerDescriptor {
+                name: "DownloadWorker".into(),
+                queue: Some("mailer".into()),
+            },
+        ],
+    };
+    let scheduler = SchedulerStub { jobs: vec![] };
+    let tasks = TasksStub { tasks: vec![] };
+
+    let graph = GraphBuilder::new("demo", &routes, &workers, &scheduler, &tasks).build();
+
+    let worker_ids: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter(|node| matches!(&node.kind, ComponentKind::BackgroundWorker { .. }))
+        .map(|node| node.id.as_str())
+        .collect();
+    assert_eq!(
+        worker_ids,
+        vec![
+            "worker:DownloadWorker@default",
+            "worker:DownloadWorker@mailer"
+        ]
+    );
+}
+
 #[test]
 fn scheduler_job_without_matching_task_has_no_trigger_edge() {
     let routes = RoutesStub { routes: vec![] };
@@ -160,6 +251,7 @@ fn scheduler_job_without_matching_task_has_no_trigger_edge() {
             run_on_start: true,
             shell: true,
             tags: vec![],
+            next_run: None,
         }],
     };
     let tasks = TasksStub { tasks: vec![] };
@@ -177,3 +269,228 @@ fn scheduler_job_without_matching_task_has_no_trigger_edge() {
         .filter(|edge| edge.from == scheduler_node.id)
         .all(|edge| edge.kind != EdgeKind::Triggers));
 }
+
+fn shell_trigger_targets(command: &str, task_names: &[&str]) -> Vec<String> {
+    let routes = RoutesStub { routes: vec![] };
+    let workers = WorkersStub { workers: vec![] };
+    let scheduler = SchedulerStub {
+        jobs: vec![SchedulerJobDescriptor {
+            name: "shell_job".into(),
+            schedule: "0 * * * *".into(),
+            command: command.into(),
+            run_on_start: false,
+            shell: true,
+            tags: vec![],
+            next_run: None,
+        }],
+    };
+    let tasks = TasksStub {
+        tasks: task_names
+            .iter()
+            .map(|name| TaskDescriptor {
+                name: (*name).into(),
+                detail: None,
+                enqueues: Vec::new(),
+            })
+            .collect(),
+    };
+
+    let graph = GraphBuilder::new("demo", &routes, &workers, &scheduler, &tasks).build();
+
+    graph
+        .edges
+        .iter()
+        .filter(|edge| edge.from == "scheduler:shell_job" && edge.kind == EdgeKind::Triggers)
+        .map(|edge| edge.to.clone())
+        .collect()
+}
+
+#[test]
+fn shell_job_with_bare_task_prefix_triggers_task() {
+    assert_eq!(
+        shell_trigger_targets("task cleanup", &["cleanup"]),
+        vec!["task:cleanup".to_string()]
+    );
+}
+
+#[test]
+fn shell_job_with_cargo_loco_task_triggers_task() {
+    assert_eq!(
+        shell_trigger_targets(
+            "cd /app && cargo loco task cleanup --env prod",
+            &["cleanup", "reindex"]
+        ),
+        vec!["task:cleanup".to_string()]
+    );
+}
+
+#[test]
+fn shell_job_with_piped_task_triggers_first_task() {
+    assert_eq!(
+        shell_trigger_targets(
+            "echo start | task reindex; cargo loco task cleanup",
+            &["cleanup", "reindex"]
+        ),
+        vec!["task:reindex".to_string()]
+    );
+}
+
+#[test]
+fn shell_job_without_task_invocation_has_no_trigger() {
+    assert!(shell_trigger_targets("cd /app && cargo build --release", &["cleanup"]).is_empty());
+}
+
+#[test]
+fn scheduler_descriptor_from_config_sorts_and_dedupes_tags() {
+    let job = loco_rs::scheduler::Job {
+        run: "cleanup".to_string(),
+        shell: false,
+        run_on_start: true,
+        cron: "0 0 * * *".to_string(),
+        tags: Some(vec![
+            "nightly".to_string(),
+            "maintenance".to_string(),
+            "nightly".to_string(),
+        ]),
+        output: None,
+    };
+
+    let descriptor = SchedulerJobDescriptor::from_config("cleanup_job", &job);
+
+    assert_eq!(
+        descriptor,
+        SchedulerJobDescriptor {
+            name: "cleanup_job".to_string(),
+            schedule: "0 0 * * *".to_string(),
+            command: "cleanup".to_string(),
+            run_on_start: true,
+            shell: false,
+            tags: vec!["maintenance".to_string(), "nightly".to_string()],
+            next_run: None,
+        }
+    );
+}
+
+#[test]
+fn route_params_extracts_named_and_wildcard_segments() {
+    assert_eq!(
+        route_params("/users/:id/posts/:post_id"),
+        vec!["id", "post_id"]
+    );
+    assert_eq!(
+        route_params("/files/{owner}/{*path}"),
+        vec!["owner", "path"]
+    );
+    assert!(route_params("/_health").is_empty());
+}
+
+#[test]
+fn edge_kinds_have_distinct_sort_keys_and_labels() {
+    let kinds: Vec<EdgeKind> = EdgeKind::all().collect();
+    assert_eq!(kinds, EdgeKind::ALL);
+
+    let keys: std::collections::BTreeSet<u8> = kinds.iter().map(|kind| kind.sort_key()).collect();
+    let labels: std::collections::BTreeSet<&str> = kinds.iter().map(|kind| kind.label()).collect();
+    assert_eq!(keys.len(), kinds.len());
+    assert_eq!(labels.len(), kinds.len());
+    assert!(kinds
+        .windows(2)
+        .all(|pair| pair[0].sort_key() < pair[1].sort_key()));
+}
+
+fn single_route_graph(root_id: &str, path: &str) -> ApplicationGraph {
+    let routes = RoutesStub {
+        routes: vec![RouteDescriptor {
+            path: path.into(),
+            methods: vec!["GET".into()],
+            handler: None,
+            params: Vec::new(),
+        }],
+    };
+    let workers = WorkersStub { workers: vec![] };
+    let scheduler = SchedulerStub { jobs: vec![] };
+    let tasks = TasksStub { tasks: vec![] };
+
+    GraphBuilder::new("api", &routes, &workers, &scheduler, &tasks)
+        .with_root_id(root_id)
+        .build()
+}
+
+#[test]
+fn root_id_override_keeps_app_roots_apart() {
+    let billing = single_route_graph("app:billing/api", "/health");
+    let catalog = single_route_graph("app:catalog/api", "/health");
+
+    for (graph, root_id) in [(&billing, "app:billing/api"), (&catalog, "app:catalog/api")] {
+        let root = find_node(
+            graph,
+            |node| matches!(&node.kind, ComponentKind::Application { name } if name == "api"),
+        );
+        assert_eq!(root.id, root_id);
+        assert!(graph
+            .edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::Contains)
+            .all(|edge| edge.from == root_id));
+    }
+
+    let billing_ids: std::collections::BTreeSet<&str> =
+        billing.nodes.iter().map(|node| node.id.as_str()).collect();
+    assert!(catalog
+        .nodes
+        .iter()
+        .all(|node| !billing_ids.contains(node.id.as_str())));
+}
+
+#[test]
+fn merged_graphs_union_nodes_and_edges() {
+    let billing = single_route_graph("app:billing/api", "/invoices");
+    let catalog = single_route_graph("app:catalog/api", "/products");
+
+    let merged = merge_graphs(vec![billing.clone(), catalog, billing]).expect("graphs merge");
+
+    assert_eq!(merged.nodes.len(), 4);
+    assert_eq!(merged.edges.len(), 2);
+    assert!(merged.nodes.windows(2).all(|pair| pair[0].id < pair[1].id));
+}
+
+#[test]
+fn merged_graphs_namespace_every_node_by_root() {
+    let billing = single_route_graph("app:billing/api", "/health");
+    let catalog = single_route_graph("app:catalog/api", "/health");
+
+    let merged = merge_graphs(vec![billing, catalog]).expect("graphs merge");
+
+    let ids: Vec<&str> = merged.nodes.iter().map(|node| node.id.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "app:billing/api",
+            "app:billing/api::route:/health",
+            "app:catalog/api",
+            "app:catalog/api::route:/health",
+        ]
+    );
+    assert!(merged
+        .edges
+        .iter()
+        .all(|edge| edge.to.starts_with(&format!("{}::", edge.from))));
+}
+
+#[test]
+fn merging_conflicting_nodes_fails() {
+    let first = single_route_graph("app:shared", "/invoices");
+    let mut second = single_route_graph("app:shared", "/invoices");
+    for node in &mut second.nodes {
+        if let ComponentKind::HttpRoute { methods, .. } = &mut node.kind {
+            *methods = vec!["POST".into()];
+        }
+    }
+
+    assert_eq!(
+        merge_graphs(vec![first, second]),
+        Err(GraphMergeError::ConflictingNode {
+            id: "app:shared::route:/invoices".into()
+        })
+    );
+}
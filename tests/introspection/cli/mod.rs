@@ -1,10 +1,20 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc, Mutex},
+    time::{Duration, Instant},
+};
 
-use loco_rs::introspection::cli::adapters::cargo::CargoCliAutomationService;
+use async_trait::async_trait;
+use loco_rs::cli::automation::CargoAutomationCommandBuilder;
+use loco_rs::introspection::cli::adapters::cargo::{CargoCliAutomationService, StdCommandExecutor};
 use loco_rs::introspection::cli::{
-    CliAutomationService, CliCommand, CommandExecutor, CommandOutput, EnqueueJobRequest,
-    JobStatusRequest, JobStatusResponse, ListGeneratorsRequest, ListJobsRequest, ListTasksRequest,
-    RunDoctorRequest, RunGeneratorRequest, RunTaskRequest,
+    redact, AsyncCliAutomationService, AsyncCommandExecutor, AutomationProgram, CancelJobRequest,
+    CliAutomationService, CliCommand, CommandExecutor, CommandOutput, DbStatusRequest,
+    EnqueueJobRequest, JobState, JobStatusRequest, JobStatusResponse, JobStatusWaitRequest,
+    ListGeneratorsRequest, ListJobsRequest, ListTasksRequest, ListWorkersRequest,
+    MigrateDownRequest, MigrateRequest, RunDoctorRequest, RunGeneratorRequest, RunTaskRequest,
+    SeedRequest, StreamingCommandExecutor,
 };
 use loco_rs::Result;
 
@@ -43,6 +53,30 @@ impl CommandExecutor for FakeCommandExecutor {
     }
 }
 
+#[derive(Clone, Default)]
+struct FakeAsyncCommandExecutor {
+    commands: Arc<Mutex<Vec<CliCommand>>>,
+    output: CommandOutput,
+}
+
+impl FakeAsyncCommandExecutor {
+    fn recorded(&self) -> Vec<CliCommand> {
+        self.commands.lock().expect("lock poisoned").clone()
+    }
+}
+
+#[async_trait]
+impl AsyncCommandExecutor for FakeAsyncCommandExecutor {
+    async fn execute(&self, command: &CliCommand) -> Result<CommandOutput> {
+        tokio::task::yield_now().await;
+        self.commands
+            .lock()
+            .expect("lock poisoned")
+            .push(command.clone());
+        Ok(self.output.clone())
+    }
+}
+
 fn service_with_executor(
     executor: Arc<FakeCommandExecutor>,
 ) -> CargoCliAutomationService<FakeCommandExecutor> {
@@ -55,6 +89,7 @@ fn list_generators_uses_generate_help() {
     let service = service_with_executor(Arc::clone(&executor));
     let request = ListGeneratorsRequest {
         environment: Some("development".into()),
+        env: BTreeMap::new(),
     };
 
     let output = service
@@ -109,6 +144,7 @@ fn list_tasks_honours_environment() {
     let service = service_with_executor(Arc::clone(&executor));
     let request = ListTasksRequest {
         environment: Some("qa".into()),
+        env: BTreeMap::new(),
     };
 
     service.list_tasks(&request).expect("command to succeed");
@@ -134,12 +170,16 @@ fn run_task_composes_arguments() {
         environment: Some("dev".into()),
         task: "parse_args".into(),
         arguments: vec!["foo:bar".into(), "alpha:one".into()],
+        timeout: None,
+        env: BTreeMap::new(),
+        capture_nonzero: true,
     };
 
     service.run_task(&request).expect("command to succeed");
 
     let commands = executor.recorded();
     assert_eq!(commands.len(), 1);
+    assert!(commands[0].capture_nonzero);
     assert_eq!(
         commands[0].args,
         vec![
@@ -163,6 +203,7 @@ fn list_jobs_includes_filters() {
         config_path: Some("config/scheduler.yml".into()),
         name: Some("nightly".into()),
         tag: Some("reports".into()),
+        env: BTreeMap::new(),
     };
 
     service.list_jobs(&request).expect("command to succeed");
@@ -199,6 +240,7 @@ fn enqueue_job_appends_all_options() {
         tags: vec!["fast".into(), "nightly".into()],
         payload: Some("{\"scope\":\"all\"}".into()),
         arguments: vec!["priority=high".into()],
+        env: BTreeMap::new(),
     };
 
     service.enqueue_job(&request).expect("command to succeed");
@@ -229,6 +271,41 @@ fn enqueue_job_appends_all_options() {
     );
 }
 
+#[test]
+fn enqueue_job_rejects_malformed_payload_before_executing() {
+    let executor = Arc::new(FakeCommandExecutor::default());
+    let service = service_with_executor(Arc::clone(&executor));
+    let request = EnqueueJobRequest {
+        job_name: "CleanupJob".into(),
+        payload: Some("{\"scope\":".into()),
+        ..Default::default()
+    };
+
+    let err = service
+        .enqueue_job(&request)
+        .expect_err("payload to be rejected");
+
+    assert!(matches!(err, loco_rs::Error::BadRequest(_)));
+    assert!(executor.recorded().is_empty());
+}
+
+#[test]
+fn enqueue_job_accepts_valid_or_empty_payload() {
+    let executor = Arc::new(FakeCommandExecutor::default());
+    let service = service_with_executor(Arc::clone(&executor));
+
+    for payload in [Some("[1, 2, 3]"), Some(""), None] {
+        let request = EnqueueJobRequest {
+            job_name: "CleanupJob".into(),
+            payload: payload.map(Into::into),
+            ..Default::default()
+        };
+        service.enqueue_job(&request).expect("command to succeed");
+    }
+
+    assert_eq!(executor.recorded().len(), 3);
+}
+
 #[test]
 fn run_doctor_pushes_flags() {
     let executor = Arc::new(FakeCommandExecutor::default());
@@ -239,6 +316,9 @@ fn run_doctor_pushes_flags() {
         config: true,
         graph: false,
         assistant: false,
+        timeout: None,
+        env: BTreeMap::new(),
+        capture_nonzero: false,
     };
 
     service.run_doctor(&request).expect("command to succeed");
@@ -269,6 +349,7 @@ fn job_status_builds_command_with_environment() {
     let request = JobStatusRequest {
         environment: Some("prod".into()),
         job_id: "job-9".into(),
+        env: BTreeMap::new(),
     };
 
     service
@@ -318,3 +399,614 @@ fn job_status_parses_cli_output() {
         }
     );
 }
+
+#[tokio::test]
+async fn async_service_runs_commands_through_async_executor() {
+    let executor = Arc::new(FakeAsyncCommandExecutor {
+        output: CommandOutput::new(0, r#"{"id":"job-3","state":"running"}"#, ""),
+        ..FakeAsyncCommandExecutor::default()
+    });
+    let service = CargoCliAutomationService::new(Arc::clone(&executor));
+
+    let run = service
+        .run_task(&RunTaskRequest {
+            task: "cleanup".into(),
+            ..RunTaskRequest::default()
+        })
+        .await
+        .expect("command to succeed");
+    let status = service
+        .job_status(&JobStatusRequest {
+            job_id: "job-3".into(),
+            ..JobStatusRequest::default()
+        })
+        .await
+        .expect("job status to succeed");
+
+    assert_eq!(run.status, 0);
+    assert_eq!(status.state, JobState::Running);
+    let commands = executor.recorded();
+    assert_eq!(commands.len(), 2);
+    assert_eq!(
+        commands[0].args,
+        vec![
+            "loco".to_string(),
+            "task".to_string(),
+            "cleanup".to_string()
+        ]
+    );
+    assert_eq!(
+        commands[1].args,
+        vec![
+            "loco".to_string(),
+            "jobs".to_string(),
+            "status".to_string(),
+            "job-3".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn run_task_propagates_timeout_to_command() {
+    let executor = Arc::new(FakeCommandExecutor::default());
+    let service = service_with_executor(Arc::clone(&executor));
+    let request = RunTaskRequest {
+        task: "cleanup".into(),
+        timeout: Some(Duration::from_secs(5)),
+        ..RunTaskRequest::default()
+    };
+
+    service.run_task(&request).expect("command to succeed");
+
+    let commands = executor.recorded();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].timeout, Some(Duration::from_secs(5)));
+}
+
+#[test]
+fn run_doctor_propagates_timeout_to_command() {
+    let executor = Arc::new(FakeCommandExecutor::default());
+    let service = service_with_executor(Arc::clone(&executor));
+    let request = RunDoctorRequest {
+        timeout: Some(Duration::from_millis(250)),
+        ..RunDoctorRequest::default()
+    };
+
+    service.run_doctor(&request).expect("command to succeed");
+
+    let commands = executor.recorded();
+    assert_eq!(commands[0].timeout, Some(Duration::from_millis(250)));
+}
+
+#[cfg(unix)]
+#[test]
+fn std_executor_kills_commands_after_timeout() {
+    let command = CliCommand::new("sleep", vec!["5".to_string()])
+        .with_timeout(Some(Duration::from_millis(50)));
+
+    let error = StdCommandExecutor
+        .execute(&command)
+        .expect_err("command should time out");
+
+    assert_eq!(error.to_string(), "command timed out");
+}
+
+#[cfg(unix)]
+#[test]
+fn std_executor_kills_streamed_commands_when_cancelled() {
+    let command = CliCommand::new("sleep", vec!["5".to_string()]);
+    let cancel = AtomicBool::new(true);
+    let started = Instant::now();
+
+    let error = StdCommandExecutor
+        .execute_streaming(&command, &cancel, &mut |_| {})
+        .expect_err("command should be cancelled");
+
+    assert_eq!(error.to_string(), "command cancelled");
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[cfg(unix)]
+#[test]
+fn std_executor_fails_on_nonzero_exit_by_default() {
+    let command = CliCommand::new(
+        "sh",
+        vec!["-c".to_string(), "echo broken >&2; exit 3".to_string()],
+    );
+
+    let error = StdCommandExecutor
+        .execute(&command)
+        .expect_err("non-zero exit should fail");
+
+    assert!(error.to_string().contains("failed with status 3: broken"));
+}
+
+#[cfg(unix)]
+#[test]
+fn std_executor_captures_nonzero_exit_when_allowed() {
+    let command = CliCommand::new(
+        "sh",
+        vec![
+            "-c".to_string(),
+            "echo partial; echo broken >&2; exit 3".to_string(),
+        ],
+    )
+    .with_capture_nonzero(true);
+
+    let output = StdCommandExecutor
+        .execute(&command)
+        .expect("non-zero exit should be captured");
+
+    assert_eq!(output, CommandOutput::new(3, "partial\n", "broken\n"));
+}
+
+#[test]
+fn run_generator_propagates_env_vars() {
+    let executor = Arc::new(FakeCommandExecutor::default());
+    let service = service_with_executor(Arc::clone(&executor));
+    let request = RunGeneratorRequest {
+        generator: "model".into(),
+        arguments: vec!["posts".into()],
+        env: BTreeMap::from([
+            (
+                "DATABASE_URL".to_string(),
+                "postgres://localhost/app".to_string(),
+            ),
+            ("RUST_LOG".to_string(), "debug".to_string()),
+        ]),
+        ..RunGeneratorRequest::default()
+    };
+
+    service.run_generator(&request).expect("command to succeed");
+
+    let commands = executor.recorded();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(
+        commands[0].env.get("DATABASE_URL").map(String::as_str),
+        Some("postgres://localhost/app")
+    );
+    assert_eq!(
+        commands[0].env.get("RUST_LOG").map(String::as_str),
+        Some("debug")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn std_executor_applies_env_vars() {
+    let command = CliCommand::new(
+        "sh",
+        vec![
+            "-c".to_string(),
+            "printf %s \"$LOCO_AUTOMATION_TEST\"".to_string(),
+        ],
+    )
+    .with_env(BTreeMap::from([(
+        "LOCO_AUTOMATION_TEST".to_string(),
+        "visible".to_string(),
+    )]));
+
+    let output = StdCommandExecutor
+        .execute(&command)
+        .expect("command to succeed");
+
+    assert_eq!(output.stdout, "visible");
+}
+
+#[test]
+fn service_stamps_working_dir_on_commands() {
+    let executor = Arc::new(FakeCommandExecutor::default());
+    let service = service_with_executor(Arc::clone(&executor))
+        .with_working_dir(Some(PathBuf::from("apps/backend")));
+
+    service
+        .list_tasks(&ListTasksRequest::default())
+        .expect("command to succeed");
+
+    let commands = executor.recorded();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].working_dir, Some(PathBuf::from("apps/backend")));
+}
+
+#[test]
+fn service_runs_cargo_loco_by_default() {
+    let executor = Arc::new(FakeCommandExecutor::default());
+    let service = service_with_executor(Arc::clone(&executor));
+
+    service
+        .run_generator(&RunGeneratorRequest {
+            generator: "model".into(),
+            arguments: args(&["post"]),
+            ..RunGeneratorRequest::default()
+        })
+        .expect("command to succeed");
+
+    let commands = executor.recorded();
+    assert_eq!(commands[0].program, "cargo");
+    assert_eq!(
+        commands[0].args,
+        args(&["loco", "generate", "model", "post"])
+    );
+}
+
+#[test]
+fn service_runs_direct_binary_without_loco_subcommand() {
+    let executor = Arc::new(FakeCommandExecutor::default());
+    let service = service_with_executor(Arc::clone(&executor))
+        .with_program(AutomationProgram::binary("./target/release/demo-cli"));
+
+    service
+        .run_generator(&RunGeneratorRequest {
+            generator: "model".into(),
+            arguments: args(&["post"]),
+            ..RunGeneratorRequest::default()
+        })
+        .expect("command to succeed");
+
+    let commands = executor.recorded();
+    assert_eq!(commands[0].program, "./target/release/demo-cli");
+    assert_eq!(commands[0].args, args(&["generate", "model", "post"]));
+}
+
+#[test]
+fn custom_program_can_keep_loco_subcommand() {
+    let program = AutomationProgram {
+        program: "./bin/cargo-wrapper".into(),
+        ..AutomationProgram::cargo()
+    };
+
+    let command = program.apply(CargoAutomationCommandBuilder::list_tasks(
+        &ListTasksRequest::default(),
+    ));
+
+    assert_eq!(command.program, "./bin/cargo-wrapper");
+    assert_eq!(command.args, args(&["loco", "task"]));
+}
+
+#[test]
+fn workspace_selection_expands_the_loco_alias() {
+    let program = AutomationProgram::cargo()
+        .with_manifest_path(Some(PathBuf::from("apps/backend/Cargo.toml")))
+        .with_package(Some("backend".into()));
+
+    let command = program.apply(CargoAutomationCommandBuilder::run_generator(
+        &RunGeneratorRequest {
+            generator: "model".into(),
+            arguments: args(&["post"]),
+            environment: Some("test".into()),
+            ..RunGeneratorRequest::default()
+        },
+    ));
+
+    assert_eq!(command.program, "cargo");
+    assert_eq!(
+        command.args,
+        args(&[
+            "run",
+            "--manifest-path",
+            "apps/backend/Cargo.toml",
+            "-p",
+            "backend",
+            "--",
+            "generate",
+            "model",
+            "post",
+            "--environment",
+            "test",
+        ])
+    );
+}
+
+#[test]
+fn workspace_selection_is_skipped_for_direct_binaries() {
+    let program = AutomationProgram::binary("./demo-cli").with_package(Some("backend".into()));
+
+    let command = program.apply(CargoAutomationCommandBuilder::list_tasks(
+        &ListTasksRequest::default(),
+    ));
+
+    assert_eq!(command.args, args(&["task"]));
+}
+
+#[test]
+fn std_executor_rejects_missing_working_dir() {
+    let command = CliCommand::new("cargo", vec!["--version".to_string()])
+        .with_working_dir(Some(PathBuf::from("does/not/exist")));
+
+    let error = StdCommandExecutor
+        .execute(&command)
+        .expect_err("missing directory should fail");
+
+    assert!(error
+        .to_string()
+        .contains("working directory `does/not/exist`"));
+}
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn builder_composes_migrate_command() {
+    let command = CargoAutomationCommandBuilder::migrate(&MigrateRequest {
+        environment: Some("test".into()),
+        ..MigrateRequest::default()
+    });
+
+    assert_eq!(command.program, "cargo");
+    assert_eq!(
+        command.args,
+        args(&["loco", "db", "migrate", "--environment", "test"])
+    );
+}
+
+#[test]
+fn builder_composes_migrate_down_command() {
+    let command = CargoAutomationCommandBuilder::migrate_down(&MigrateDownRequest {
+        environment: Some("test".into()),
+        steps: Some(2),
+        ..MigrateDownRequest::default()
+    });
+    assert_eq!(
+        command.args,
+        args(&["loco", "db", "down", "2", "--environment", "test"])
+    );
+
+    let command = CargoAutomationCommandBuilder::migrate_down(&MigrateDownRequest::default());
+    assert_eq!(command.args, args(&["loco", "db", "down"]));
+}
+
+#[test]
+fn builder_composes_db_status_command() {
+    let command = CargoAutomationCommandBuilder::db_status(&DbStatusRequest {
+        environment: Some("production".into()),
+        env: BTreeMap::new(),
+    });
+
+    assert_eq!(
+        command.args,
+        args(&["loco", "db", "status", "--environment", "production"])
+    );
+}
+
+#[test]
+fn builder_composes_seed_command() {
+    let command = CargoAutomationCommandBuilder::seed(&SeedRequest {
+        environment: Some("development".into()),
+        reset: true,
+        from: Some("src/fixtures".into()),
+        ..SeedRequest::default()
+    });
+    assert_eq!(
+        command.args,
+        args(&[
+            "loco",
+            "db",
+            "seed",
+            "--reset",
+            "--from",
+            "src/fixtures",
+            "--environment",
+            "development",
+        ])
+    );
+
+    let command = CargoAutomationCommandBuilder::seed(&SeedRequest::default());
+    assert_eq!(command.args, args(&["loco", "db", "seed"]));
+}
+
+#[test]
+fn builder_composes_list_workers_command() {
+    let command = CargoAutomationCommandBuilder::list_workers(&ListWorkersRequest {
+        environment: Some("production".into()),
+        queue: Some("mailers".into()),
+        env: BTreeMap::new(),
+    });
+    assert_eq!(
+        command.args,
+        args(&[
+            "loco",
+            "jobs",
+            "workers",
+            "--queue",
+            "mailers",
+            "--environment",
+            "production",
+        ])
+    );
+
+    let command = CargoAutomationCommandBuilder::list_workers(&ListWorkersRequest::default());
+    assert_eq!(command.args, args(&["loco", "jobs", "workers"]));
+}
+
+#[test]
+fn builder_composes_cancel_job_command() {
+    let command = CargoAutomationCommandBuilder::cancel_job(&CancelJobRequest {
+        environment: Some("production".into()),
+        name: "ReportWorker".into(),
+        env: BTreeMap::new(),
+    });
+
+    assert_eq!(
+        command.args,
+        args(&[
+            "loco",
+            "jobs",
+            "cancel",
+            "--name",
+            "ReportWorker",
+            "--environment",
+            "production",
+        ])
+    );
+}
+
+#[test]
+fn redact_masks_capture_groups_or_whole_matches() {
+    let patterns = vec![
+        regex::Regex::new(r"password=([^\s&;]+)").unwrap(),
+        regex::Regex::new(r"sk-[A-Za-z0-9]+").unwrap(),
+    ];
+    let output = CommandOutput::new(0, "password=hunter2&user=loco", "token sk-abc123 expired");
+
+    let redacted = redact(output, &patterns);
+
+    assert_eq!(redacted.status, 0);
+    assert_eq!(redacted.stdout, "password=***&user=loco");
+    assert_eq!(redacted.stderr, "token *** expired");
+}
+
+#[test]
+fn default_redactions_keep_json_intact() {
+    let patterns = loco_rs::config::ConsoleConfig::default()
+        .redaction_patterns()
+        .unwrap();
+    let output = CommandOutput::new(
+        0,
+        r#"{"dsn":"redis://host?password=hunter2","pwd=s3cr3t":["x"]}"#,
+        "",
+    );
+
+    let redacted = redact(output, &patterns);
+
+    assert_eq!(
+        redacted.stdout,
+        r#"{"dsn":"redis://host?password=***","pwd=***":["x"]}"#
+    );
+    serde_json::from_str::<serde_json::Value>(&redacted.stdout).unwrap();
+}
+
+struct SequencedCommandExecutor {
+    outputs: Mutex<Vec<CommandOutput>>,
+    calls: Mutex<usize>,
+}
+
+impl SequencedCommandExecutor {
+    fn new(outputs: Vec<CommandOutput>) -> Self {
+        Self {
+            outputs: Mutex::new(outputs),
+            calls: Mutex::new(0),
+        }
+    }
+}
+
+impl CommandExecutor for SequencedCommandExecutor {
+    fn execute(&self, _command: &CliCommand) -> Result<CommandOutput> {
+        *self.calls.lock().expect("lock poisoned") += 1;
+        let mut outputs = self.outputs.lock().expect("lock poisoned");
+        if outputs.len() > 1 {
+            Ok(outputs.remove(0))
+        } else {
+            Ok(outputs[0].clone())
+        }
+    }
+}
+
+#[test]
+fn job_status_wait_polls_until_terminal_state() {
+    let executor = Arc::new(SequencedCommandExecutor::new(vec![
+        CommandOutput::new(0, r#"{"id":"job-3","state":"queued"}"#, ""),
+        CommandOutput::new(0, r#"{"id":"job-3","state":"completed"}"#, ""),
+    ]));
+    let service = CargoCliAutomationService::new(Arc::clone(&executor));
+    let request = JobStatusWaitRequest {
+        job_id: "job-3".into(),
+        timeout: Duration::from_secs(5),
+        poll_interval: Duration::from_millis(1),
+        ..JobStatusWaitRequest::default()
+    };
+
+    let response = service
+        .job_status_wait(&request)
+        .expect("job status wait to succeed");
+
+    assert_eq!(response.state, JobState::Completed);
+    assert_eq!(*executor.calls.lock().expect("lock poisoned"), 2);
+}
+
+#[test]
+fn job_status_wait_returns_latest_status_on_timeout() {
+    let executor = Arc::new(SequencedCommandExecutor::new(vec![CommandOutput::new(
+        0,
+        r#"{"id":"job-4","state":"running"}"#,
+        "",
+    )]));
+    let service = CargoCliAutomationService::new(Arc::clone(&executor));
+    let request = JobStatusWaitRequest {
+        job_id: "job-4".into(),
+        ..JobStatusWaitRequest::default()
+    };
+
+    let response = service
+        .job_status_wait(&request)
+        .expect("job status wait to succeed");
+
+    assert_eq!(response.state, JobState::Running);
+    assert_eq!(*executor.calls.lock().expect("lock poisoned"), 1);
+}
+
+#[test]
+fn job_status_wait_never_polls_in_a_tight_loop() {
+    assert_eq!(
+        JobStatusWaitRequest::default().effective_poll_interval(),
+        JobStatusWaitRequest::DEFAULT_POLL_INTERVAL
+    );
+
+    let request = JobStatusWaitRequest {
+        poll_interval: Duration::ZERO,
+        ..JobStatusWaitRequest::default()
+    };
+    assert_eq!(
+        request.effective_poll_interval(),
+        JobStatusWaitRequest::MIN_POLL_INTERVAL
+    );
+}
+
+#[test]
+fn job_state_parses_known_states() {
+    for (raw, state) in [
+        ("queued", JobState::Queued),
+        ("running", JobState::Running),
+        ("completed", JobState::Completed),
+        ("failed", JobState::Failed),
+        ("cancelled", JobState::Cancelled),
+    ] {
+        let parsed = JobState::from(raw);
+        assert_eq!(parsed, state);
+        assert_eq!(parsed.to_string(), raw);
+    }
+    assert!(JobState::Completed.is_terminal());
+    assert!(!JobState::Running.is_terminal());
+}
+
+#[test]
+fn job_state_keeps_unknown_states_verbatim() {
+    let state = JobState::from("Paused");
+
+    assert_eq!(state, JobState::Unknown("Paused".into()));
+    assert_eq!(state.to_string(), "Paused");
+    assert_eq!(serde_json::to_value(&state).unwrap(), "Paused");
+    assert!(!state.is_terminal());
+}
+
+#[test]
+fn cli_command_display_quotes_shell_arguments() {
+    let command = CliCommand::new(
+        "cargo",
+        args(&[
+            "loco",
+            "jobs",
+            "enqueue",
+            "--payload",
+            r#"{"scope":"all"}"#,
+            "--tag",
+            "nightly run",
+            "it's",
+            "name:posts",
+        ]),
+    );
+
+    let expected = r#"cargo loco jobs enqueue --payload '{"scope":"all"}' --tag 'nightly run' 'it'\''s' name:posts"#;
+    assert_eq!(command.to_shell_string(), expected);
+    assert_eq!(command.to_string(), expected);
+}
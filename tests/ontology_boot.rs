@@ -7,7 +7,8 @@ use loco_rs::{
     boot,
     environment::Environment,
     ontology::{
-        value_objects::Iri, Class, Individual, Ontology, Property, PropertyAssertion, PropertyKind,
+        value_objects::Iri, Class, Individual, Ontology, OntologyService, Property,
+        PropertyAssertion, PropertyKind,
     },
     tests_cfg::{config::test_config, db::AppHook},
 };
@@ -92,3 +93,76 @@ async fn context_exposes_reasoner_from_config() {
 
     let _ = fs::remove_file(seed_path);
 }
+
+#[tokio::test]
+async fn descendants_include_every_level_of_the_hierarchy() {
+    let config = test_config();
+    let service =
+        OntologyService::from_config(&config.ontology, &config.reasoner).expect("ontology service");
+
+    let ontology_id = Iri::new("https://example.org/hierarchy").expect("ontology iri");
+    let root_id = Iri::new("https://example.org/Root").expect("root class");
+    let child_id = Iri::new("https://example.org/Child").expect("child class");
+    let grandchild_id = Iri::new("https://example.org/Grandchild").expect("grandchild class");
+
+    let mut ontology = Ontology::new(ontology_id.clone());
+    let mut child = Class::new(child_id.clone());
+    child.add_parent(root_id.clone());
+    let mut grandchild = Class::new(grandchild_id.clone());
+    grandchild.add_parent(child_id.clone());
+    ontology
+        .add_class(Class::new(root_id.clone()))
+        .expect("root class");
+    ontology.add_class(child).expect("child class");
+    ontology.add_class(grandchild).expect("grandchild class");
+    service
+        .repository()
+        .insert(ontology)
+        .await
+        .expect("ontology inserted");
+
+    let descendants = service
+        .reasoner()
+        .descendants_of(&ontology_id, &root_id)
+        .await
+        .expect("descendants");
+    assert_eq!(descendants, vec![child_id, grandchild_id]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_ancestor_queries_complete() {
+    let config = test_config();
+    let service =
+        OntologyService::from_config(&config.ontology, &config.reasoner).expect("ontology service");
+
+    let ontology_id = Iri::new("https://example.org/concurrent").expect("ontology iri");
+    let root_id = Iri::new("https://example.org/Root").expect("root class");
+    let leaf_id = Iri::new("https://example.org/Leaf").expect("leaf class");
+
+    let mut ontology = Ontology::new(ontology_id.clone());
+    let mut leaf = Class::new(leaf_id.clone());
+    leaf.add_parent(root_id.clone());
+    ontology
+        .add_class(Class::new(root_id.clone()))
+        .expect("root class");
+    ontology.add_class(leaf).expect("leaf class");
+    service
+        .repository()
+        .insert(ontology)
+        .await
+        .expect("ontology inserted");
+
+    let handles = (0..16)
+        .map(|_| {
+            let reasoner = service.reasoner();
+            let ontology_id = ontology_id.clone();
+            let leaf_id = leaf_id.clone();
+            tokio::spawn(async move { reasoner.ancestors_of(&ontology_id, &leaf_id).await })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        let ancestors = handle.await.expect("task completed").expect("ancestors");
+        assert_eq!(ancestors, vec![root_id.clone()]);
+    }
+}